@@ -0,0 +1,99 @@
+use hodl_model::{
+    airdrop::{Airdrop, AirdropApi, AirdropIndex, AirdropLeaf},
+    lockup::{LockupCreate, LockupIndex},
+    schedule::Schedule,
+    WrappedBalance,
+};
+use near_sdk::{assert_one_yocto, env, json_types::Base58CryptoHash, log, near_bindgen, Promise};
+
+use crate::{
+    event::{emit, EventKind, FtLockupCreateAirdrop, FtLockupRegisterAirdropLockup},
+    Contract, ContractExt,
+};
+
+#[near_bindgen]
+impl AirdropApi for Contract {
+    #[payable]
+    fn create_merkle_airdrop(
+        &mut self,
+        root: Base58CryptoHash,
+        num_leaves: u32,
+        total_balance: WrappedBalance,
+    ) -> AirdropIndex {
+        assert_one_yocto();
+        self.assert_manager();
+        let airdrop_id = AirdropIndex::try_from(self.airdrops.len()).unwrap();
+        self.airdrops.push(&Airdrop::new(root, num_leaves, total_balance.0));
+
+        emit(EventKind::FtLockupCreateAirdrop(FtLockupCreateAirdrop {
+            id: airdrop_id,
+            root,
+            num_leaves,
+            total_balance,
+        }));
+        airdrop_id
+    }
+
+    #[payable]
+    fn register_airdrop_lockup(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        leaf_index: u32,
+        schedule: Schedule,
+        proof: Vec<Base58CryptoHash>,
+    ) -> LockupIndex {
+        let initial_storage_usage = env::storage_usage();
+        let account_id = env::predecessor_account_id();
+        self.assert_not_blocked(&account_id);
+        let mut airdrop = self.airdrops.get(u64::from(airdrop_id)).expect("airdrop not found");
+        assert!(
+            self.airdrop_registrations.insert(&(airdrop_id, leaf_index)),
+            "airdrop lockup is already registered"
+        );
+        let leaf = AirdropLeaf {
+            account_id: account_id.clone(),
+            schedule,
+        };
+        let deposit_id = airdrop.register(&leaf, leaf_index, &proof);
+        self.airdrops.replace(u64::from(airdrop_id), &airdrop);
+
+        let total_balance = leaf.schedule.total_balance();
+        let lockup_create = LockupCreate {
+            schedule: leaf.schedule,
+            ..LockupCreate::new_unlocked(account_id.clone(), total_balance)
+        };
+        // the manager posting the airdrop is its creator, and the sender of the funding is the payer
+        let payer_id = self.deposits.get(u64::from(deposit_id)).unwrap().sender_id;
+        let lockup = lockup_create.into_lockup(&payer_id);
+        lockup.assert_new_valid(total_balance);
+        let manager = self.manager.clone();
+        let index = self.internal_add_created_lockup(&lockup, &lockup_create, &payer_id, &manager);
+        self.internal_record_funding(index, deposit_id, total_balance);
+        log!(
+            "Registered lockup #{} of airdrop {} for {}",
+            index,
+            airdrop_id,
+            account_id
+        );
+
+        let event = self.internal_create_lockup_event(index, lockup, None);
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        emit(EventKind::FtLockupRegisterAirdropLockup(
+            FtLockupRegisterAirdropLockup {
+                id: airdrop_id,
+                leaf_index,
+                lockup_id: index,
+            },
+        ));
+
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul(u128::from(env::storage_usage().saturating_sub(initial_storage_usage)));
+        let refund = env::attached_deposit()
+            .checked_sub(storage_cost)
+            .unwrap_or_else(|| panic!("The attached deposit doesn't cover the storage cost of {storage_cost}"));
+        if !refund.is_zero() {
+            Promise::new(account_id).transfer(refund);
+        }
+        index
+    }
+}
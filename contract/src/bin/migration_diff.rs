@@ -0,0 +1,49 @@
+//! Reports the lockups whose balances change when the contract state is migrated.
+//!
+//! Usage: `migration-diff <snapshot.json> <contract_account_id> [timestamp_sec]`,
+//! where the snapshot is the result of the `view_state` RPC query for the contract account
+//! taken before the upgrade. Exits with a non-zero code if any lockup balances changed.
+
+use std::{
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use hodl_lockup::migration::diff::{diff, StateSnapshot};
+use near_sdk::{serde_json, AccountId};
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (Some(snapshot_path), Some(contract_id)) = (args.next(), args.next()) else {
+        eprintln!("Usage: migration-diff <snapshot.json> <contract_account_id> [timestamp_sec]");
+        return ExitCode::FAILURE;
+    };
+
+    let snapshot = std::fs::read_to_string(&snapshot_path).expect("Failed to read the snapshot");
+    let snapshot: StateSnapshot = serde_json::from_str(&snapshot).expect("Failed to parse the snapshot");
+    let contract_id: AccountId = contract_id.parse().expect("Invalid contract account id");
+    let timestamp = args.next().map_or_else(
+        || {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Invalid system time");
+            u32::try_from(now.as_secs()).expect("Timestamp overflow")
+        },
+        |timestamp| timestamp.parse().expect("Invalid timestamp"),
+    );
+
+    let changes = diff(snapshot, contract_id, timestamp);
+    for change in &changes {
+        println!(
+            "{}",
+            serde_json::to_string(change).expect("Failed to serialize the change")
+        );
+    }
+    eprintln!("{} lockups changed", changes.len());
+
+    if changes.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -2,136 +2,679 @@ use std::collections::HashMap;
 
 use hodl_model::{
     draft::{DraftGroup, DraftGroupIndex, DraftIndex},
-    lockup::{Lockup, LockupClaim, LockupIndex},
+    fee::ClaimFee,
+    lockup::{ClaimResult, Lockup, LockupClaim, LockupIndex},
     util::current_timestamp_sec,
-    WrappedBalance,
+    Balance, TimestampSec, WrappedBalance,
 };
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_sdk::{PromiseOrValue, PromiseResult};
 
 use crate::{
-    emit, ext_contract, is_promise_success, log, near_bindgen, AccountId, Contract, ContractExt, EventKind,
-    FtLockupClaimLockup, FtLockupCreateLockup, Into,
+    emit, env,
+    event::{
+        emit_batch, FtLockupClaimLien, FtLockupCreateAccount, FtLockupDraftGroupCompleted,
+        FtLockupDraftGroupDiscrepancy, FtLockupKeeperClaim, FtLockupRejectBlockedAccount, FtLockupSweepToEscrow,
+    },
+    ext_contract, is_promise_success, log, near_bindgen, serde_json, validate_unvested_balance_within_tolerance,
+    AccountId, Contract, ContractExt, EventKind, FtLockupClaimLockup, FtLockupCreateLockup, Into, NearToken, Promise,
 };
 
 #[ext_contract(ext_self)]
 pub trait SelfCallbacks {
-    fn after_ft_transfer(&mut self, account_id: AccountId, lockup_claims: Vec<LockupClaim>) -> WrappedBalance;
+    fn after_ft_transfer(
+        &mut self,
+        account_id: AccountId,
+        lockup_claims: Vec<LockupClaim>,
+        has_more: bool,
+        claim_fee: Option<ClaimFee>,
+        clamped_lockups: Option<Vec<(LockupIndex, WrappedBalance)>>,
+        in_flight_lockups: Option<Vec<LockupIndex>>,
+    ) -> ClaimResult;
 
-    fn after_lockup_termination(&mut self, account_id: AccountId, amount: WrappedBalance) -> WrappedBalance;
+    fn after_keeper_claim(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        keeper_id: AccountId,
+        keeper_fee: WrappedBalance,
+        claim_fee: Option<ClaimFee>,
+    ) -> WrappedBalance;
+
+    fn after_lien_claim(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        holder_id: AccountId,
+    ) -> WrappedBalance;
+
+    fn after_escrow_sweep(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        escrow_id: AccountId,
+    ) -> WrappedBalance;
+
+    fn after_lockup_termination(
+        &mut self,
+        account_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: Option<LockupIndex>,
+    ) -> WrappedBalance;
+
+    fn after_termination_burn(
+        &mut self,
+        payer_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: LockupIndex,
+    ) -> WrappedBalance;
+
+    fn after_unclaimed_revocation(
+        &mut self,
+        beneficiary_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: LockupIndex,
+    ) -> WrappedBalance;
+
+    fn after_vesting_oracle(
+        &mut self,
+        lockup_index: LockupIndex,
+        termination_timestamp: TimestampSec,
+        expected_unvested: Option<WrappedBalance>,
+        terminated_by: AccountId,
+        reason: Option<String>,
+    ) -> PromiseOrValue<WrappedBalance>;
 
     fn convert_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> Vec<LockupIndex>;
+
+    fn after_ft_metadata(&mut self) -> Option<u8>;
+
+    fn after_account_creation(
+        &mut self,
+        draft_id: DraftIndex,
+        refund_account_id: AccountId,
+        deposit: NearToken,
+    ) -> Option<LockupIndex>;
 }
 
 #[near_bindgen]
 impl SelfCallbacks for Contract {
     #[private]
-    fn after_ft_transfer(&mut self, account_id: AccountId, lockup_claims: Vec<LockupClaim>) -> WrappedBalance {
-        let promise_success = is_promise_success();
+    fn after_ft_transfer(
+        &mut self,
+        account_id: AccountId,
+        lockup_claims: Vec<LockupClaim>,
+        has_more: bool,
+        claim_fee: Option<ClaimFee>,
+        // claims started before the upgrade introducing the clamp report resolve without it
+        clamped_lockups: Option<Vec<(LockupIndex, WrappedBalance)>>,
+        // as well as the report of the skipped lockups in flight
+        in_flight_lockups: Option<Vec<LockupIndex>>,
+    ) -> ClaimResult {
+        for lockup_claim in &lockup_claims {
+            self.internal_finish_claim(lockup_claim.index, lockup_claim.claim_amount.0);
+        }
+        // the fee is withheld per lockup, so a failed transfer is refunded to each lockup by its share
+        let claim_fee_amounts: Vec<Balance> = lockup_claims
+            .iter()
+            .map(|lockup_claim| {
+                claim_fee
+                    .as_ref()
+                    .map_or(0, |claim_fee| claim_fee.fee(lockup_claim.claim_amount.0))
+            })
+            .collect();
+        let is_success = |result_index| matches!(env::promise_result(result_index), PromiseResult::Successful(_));
+        // the owner transfer is the first promise, the claim fee transfer is the second one if any
+        let owner_paid = is_success(0);
+        // e.g. an owner not registered with the token, the owner part is forwarded to the claim escrow if any
+        let escrow_id = if owner_paid {
+            None
+        } else {
+            self.internal_claim_escrow(lockup_claims.len())
+        };
+        if !owner_paid {
+            match &escrow_id {
+                Some(escrow_id) => log!(
+                    "Token transfer has failed. Forwarding to the claim escrow {}.",
+                    escrow_id
+                ),
+                None => log!("Token transfer has failed. Refunding."),
+            }
+        }
+        let has_claim_fee = claim_fee_amounts.iter().any(|amount| *amount > 0);
+        let claim_fee_paid = has_claim_fee && is_success(1);
+        if has_claim_fee && !claim_fee_paid {
+            log!("Claim fee transfer has failed. Refunding.");
+        }
+
         let mut total_balance = 0;
-        if promise_success {
-            let mut remove_indices = vec![];
-            let mut events: Vec<FtLockupClaimLockup> = vec![];
-            for LockupClaim {
+        let mut total_claim_fee = 0;
+        let mut events: Vec<FtLockupClaimLockup> = vec![];
+        let mut modified = false;
+        let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+        let mut escrow_claims: Vec<LockupClaim> = vec![];
+        for (
+            LockupClaim {
                 index,
                 is_final,
                 claim_amount,
-            } in lockup_claims
-            {
-                if is_final {
-                    remove_indices.push(index);
+            },
+            claim_fee_amount,
+        ) in lockup_claims.into_iter().zip(claim_fee_amounts)
+        {
+            let mut refund_amount = 0;
+            let mut escrow_amount = 0;
+            if !owner_paid {
+                if escrow_id.is_some() {
+                    escrow_amount = claim_amount.0 - claim_fee_amount;
+                } else {
+                    refund_amount += claim_amount.0 - claim_fee_amount;
                 }
-                total_balance += claim_amount.0;
-                let event = FtLockupClaimLockup {
-                    id: index,
-                    amount: claim_amount,
-                };
-                events.push(event);
             }
-            if !remove_indices.is_empty() {
-                let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
-                for index in remove_indices {
-                    indices.remove(&index);
-                }
-                self.internal_save_account_lockups(&account_id, indices);
+            if claim_fee_amount > 0 && !claim_fee_paid {
+                refund_amount += claim_fee_amount;
             }
-            emit(EventKind::FtLockupClaimLockup(events));
-        } else {
-            log!("Token transfer has failed. Refunding.");
-            let mut modified = false;
-            let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
-            for LockupClaim {
-                index, claim_amount, ..
-            } in lockup_claims
-            {
-                if indices.insert(index) {
-                    modified = true;
-                }
-                let mut lockup = self.lockups.get(u64::from(index)).unwrap();
-                lockup.claimed_balance -= claim_amount.0;
+            if refund_amount > 0 {
+                let mut lockup = self.internal_get_lockup(index).unwrap();
+                lockup.claimed_balance -= refund_amount;
                 self.lockups.replace(u64::from(index), &lockup);
+                modified |= indices.insert(index);
+            } else if is_final && escrow_amount == 0 {
+                modified |= indices.remove(&index);
+                self.internal_prune_lockup_tags(index);
+            }
+            if escrow_amount > 0 {
+                // reserved again until the escrow resolves, which finalizes the lockup or refunds it
+                self.claims_in_flight.insert(&index, &escrow_amount);
+                escrow_claims.push(LockupClaim {
+                    index,
+                    claim_amount: escrow_amount.into(),
+                    is_final: is_final && refund_amount == 0,
+                });
             }
 
-            if modified {
-                self.internal_save_account_lockups(&account_id, indices);
+            let claimed_balance = claim_amount.0 - refund_amount - escrow_amount;
+            if claimed_balance > 0 {
+                total_balance += claimed_balance;
+                let fee = (claim_fee_paid && claim_fee_amount > 0).then_some(claim_fee_amount);
+                total_claim_fee += fee.unwrap_or_default();
+                events.push(FtLockupClaimLockup {
+                    id: index,
+                    amount: claimed_balance.into(),
+                    fee: fee.map(Into::into),
+                });
             }
         }
-        total_balance.into()
+        if modified {
+            self.internal_save_account_lockups(&account_id, indices);
+        }
+        let claimed_lockups = events.iter().map(|event| (event.id, event.amount)).collect();
+        if !events.is_empty() {
+            self.token_flows.claim(total_balance);
+            self.token_flows.collect_fee(total_claim_fee);
+            emit(EventKind::FtLockupClaimLockup(events));
+        }
+        let escrowed_lockups = escrow_claims
+            .iter()
+            .map(|lockup_claim| (lockup_claim.index, lockup_claim.claim_amount))
+            .collect();
+        if let Some(escrow_id) = escrow_id {
+            for lockup_claim in escrow_claims {
+                let memo = self
+                    .config
+                    .memo_templates
+                    .claim_escrow_memo(lockup_claim.claim_amount.0, lockup_claim.index);
+                self.internal_escrow_transfer(account_id.clone(), lockup_claim, escrow_id.clone(), memo);
+            }
+        }
+        ClaimResult {
+            claimed_balance: total_balance.into(),
+            has_more,
+            claimed_lockups,
+            clamped_lockups: clamped_lockups.unwrap_or_default(),
+            in_flight_lockups: in_flight_lockups.unwrap_or_default(),
+            escrowed_lockups,
+        }
+    }
+
+    #[private]
+    fn after_keeper_claim(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        keeper_id: AccountId,
+        keeper_fee: WrappedBalance,
+        claim_fee: Option<ClaimFee>,
+    ) -> WrappedBalance {
+        let LockupClaim {
+            index,
+            claim_amount,
+            is_final,
+        } = lockup_claim;
+        self.internal_finish_claim(index, claim_amount.0);
+        let claim_fee_amount = claim_fee.map_or(0, |claim_fee| claim_fee.fee(claim_amount.0));
+        let is_success = |result_index| matches!(env::promise_result(result_index), PromiseResult::Successful(_));
+        // the owner transfer is the first promise, followed by the keeper fee and the claim fee transfers if any
+        let mut refund_amount = 0;
+        if !is_success(0) {
+            log!("Owner token transfer has failed. Refunding.");
+            refund_amount += claim_amount
+                .0
+                .saturating_sub(keeper_fee.0)
+                .saturating_sub(claim_fee_amount);
+        }
+        let keeper_fee_paid = keeper_fee.0 > 0 && is_success(1);
+        if keeper_fee.0 > 0 && !keeper_fee_paid {
+            log!("Keeper fee transfer has failed. Refunding.");
+            refund_amount += keeper_fee.0;
+        }
+        let claim_fee_result_index = if keeper_fee.0 > 0 { 2 } else { 1 };
+        let claim_fee_paid = claim_fee_amount > 0 && is_success(claim_fee_result_index);
+        if claim_fee_amount > 0 && !claim_fee_paid {
+            log!("Claim fee transfer has failed. Refunding.");
+            refund_amount += claim_fee_amount;
+        }
+
+        if refund_amount > 0 {
+            let mut lockup = self.internal_get_lockup(index).unwrap();
+            lockup.claimed_balance -= refund_amount;
+            self.lockups.replace(u64::from(index), &lockup);
+        } else if is_final {
+            let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+            indices.remove(&index);
+            self.internal_save_account_lockups(&account_id, indices);
+            self.internal_prune_lockup_tags(index);
+        }
+
+        let claimed_balance = claim_amount.0 - refund_amount;
+        if claimed_balance > 0 {
+            self.token_flows.claim(claimed_balance);
+            let fee = claim_fee_paid.then_some(claim_fee_amount);
+            self.token_flows.collect_fee(fee.unwrap_or_default());
+            emit(EventKind::FtLockupClaimLockup(vec![FtLockupClaimLockup {
+                id: index,
+                amount: claimed_balance.into(),
+                fee: fee.map(Into::into),
+            }]));
+        }
+        if keeper_fee_paid {
+            emit(EventKind::FtLockupKeeperClaim(vec![FtLockupKeeperClaim {
+                id: index,
+                keeper_id,
+                keeper_fee,
+            }]));
+        }
+        claimed_balance.into()
+    }
+
+    #[private]
+    fn after_lien_claim(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        holder_id: AccountId,
+    ) -> WrappedBalance {
+        let LockupClaim {
+            index,
+            claim_amount,
+            is_final,
+        } = lockup_claim;
+        self.internal_finish_claim(index, claim_amount.0);
+        if !is_promise_success() {
+            log!("Lien claim transfer has failed. Refunding.");
+            let mut lockup = self.internal_get_lockup(index).unwrap();
+            lockup.claimed_balance -= claim_amount.0;
+            self.lockups.replace(u64::from(index), &lockup);
+            return 0.into();
+        }
+
+        // the lien may have been released while the transfer was in flight
+        if let Some(mut lien) = self.liens.get(&index) {
+            lien.amount = lien.amount.saturating_sub(claim_amount.0);
+            if lien.amount == 0 {
+                self.liens.remove(&index);
+            } else {
+                self.liens.insert(&index, &lien);
+            }
+        }
+        if is_final {
+            let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+            indices.remove(&index);
+            self.internal_save_account_lockups(&account_id, indices);
+            self.internal_prune_lockup_tags(index);
+        }
+        self.token_flows.claim(claim_amount.0);
+        emit(EventKind::FtLockupClaimLockup(vec![FtLockupClaimLockup {
+            id: index,
+            amount: claim_amount,
+            fee: None,
+        }]));
+        emit(EventKind::FtLockupClaimLien(vec![FtLockupClaimLien {
+            id: index,
+            holder_id,
+            amount: claim_amount,
+        }]));
+        claim_amount
     }
 
     #[private]
-    fn after_lockup_termination(&mut self, account_id: AccountId, amount: WrappedBalance) -> WrappedBalance {
+    fn after_escrow_sweep(
+        &mut self,
+        account_id: AccountId,
+        lockup_claim: LockupClaim,
+        escrow_id: AccountId,
+    ) -> WrappedBalance {
+        let LockupClaim {
+            index,
+            claim_amount,
+            is_final,
+        } = lockup_claim;
+        self.internal_finish_claim(index, claim_amount.0);
+        // `ft_transfer_call` resolves to the balance used by the escrow, the rest is refunded to the contract.
+        // The claims the owner couldn't receive are resolved here as well, see `Config::claim_escrow_id`.
+        let used_amount = match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<WrappedBalance>(&value)
+                .map_or(0, |used_amount| used_amount.0.min(claim_amount.0)),
+            PromiseResult::Failed => 0,
+        };
+        let refund_amount = claim_amount.0 - used_amount;
+        if refund_amount > 0 {
+            log!("Escrow has refused {refund_amount}. Refunding.");
+            let mut lockup = self.internal_get_lockup(index).unwrap();
+            lockup.claimed_balance -= refund_amount;
+            self.lockups.replace(u64::from(index), &lockup);
+        }
+        if used_amount == 0 {
+            return 0.into();
+        }
+
+        self.cliff_escrows.remove(&index);
+        if is_final && refund_amount == 0 {
+            let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+            indices.remove(&index);
+            self.internal_save_account_lockups(&account_id, indices);
+            self.internal_prune_lockup_tags(index);
+        }
+        self.token_flows.claim(used_amount);
+        emit(EventKind::FtLockupClaimLockup(vec![FtLockupClaimLockup {
+            id: index,
+            amount: used_amount.into(),
+            fee: None,
+        }]));
+        emit(EventKind::FtLockupSweepToEscrow(vec![FtLockupSweepToEscrow {
+            id: index,
+            escrow_id,
+            amount: used_amount.into(),
+        }]));
+        used_amount.into()
+    }
+
+    #[private]
+    fn after_lockup_termination(
+        &mut self,
+        account_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: Option<LockupIndex>,
+    ) -> WrappedBalance {
+        // the transfer isn't of a lockup balance for reclaimed draft groups
+        if let Some(lockup_index) = lockup_index {
+            self.terminations_in_flight.remove(&lockup_index);
+        }
         if is_promise_success() {
+            self.token_flows.refund(amount.0);
             return amount;
         }
 
         log!("Lockup termination transfer has failed.");
         // There is no internal balance, so instead we create a new lockup.
         let lockup = Lockup::new_unlocked_since(account_id, amount.0, current_timestamp_sec());
-        let lockup_index = self.internal_add_lockup(&lockup);
-        let event: FtLockupCreateLockup = (lockup_index, lockup, None).into();
+        let lockup_index = self.internal_add_lockup(&lockup, &[]);
+        let event = self.internal_create_lockup_event(lockup_index, lockup, None);
         emit(EventKind::FtLockupCreateLockup(vec![event]));
         0.into()
     }
 
+    #[private]
+    fn after_termination_burn(
+        &mut self,
+        payer_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: LockupIndex,
+    ) -> WrappedBalance {
+        self.terminations_in_flight.remove(&lockup_index);
+        if is_promise_success() {
+            self.token_flows.burn(amount.0);
+            return amount;
+        }
+
+        log!("Lockup termination burn has failed.");
+        // The payer receives the balance in a new lockup, as for a failed refund, and can burn it manually.
+        let lockup = Lockup::new_unlocked_since(payer_id, amount.0, current_timestamp_sec());
+        let lockup_index = self.internal_add_lockup(&lockup, &[]);
+        let event = self.internal_create_lockup_event(lockup_index, lockup, None);
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        0.into()
+    }
+
+    #[private]
+    fn after_unclaimed_revocation(
+        &mut self,
+        beneficiary_id: AccountId,
+        amount: WrappedBalance,
+        lockup_index: LockupIndex,
+    ) -> WrappedBalance {
+        self.terminations_in_flight.remove(&lockup_index);
+        if is_promise_success() {
+            self.token_flows.revoke(amount.0);
+            return amount;
+        }
+
+        log!("Unclaimed revocation transfer has failed.");
+        // As for a failed termination refund, the beneficiary receives the balance in a new lockup.
+        let lockup = Lockup::new_unlocked_since(beneficiary_id, amount.0, current_timestamp_sec());
+        let lockup_index = self.internal_add_lockup(&lockup, &[]);
+        let event = self.internal_create_lockup_event(lockup_index, lockup, None);
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        0.into()
+    }
+
+    #[private]
+    fn after_vesting_oracle(
+        &mut self,
+        lockup_index: LockupIndex,
+        termination_timestamp: TimestampSec,
+        expected_unvested: Option<WrappedBalance>,
+        terminated_by: AccountId,
+        reason: Option<String>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        self.terminations_in_flight.remove(&lockup_index);
+        let vested_balance = match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<WrappedBalance>(&value).ok(),
+            PromiseResult::Failed => None,
+        };
+        let Some(vested_balance) = vested_balance else {
+            log!("Vesting oracle has failed, lockup #{} is not terminated.", lockup_index);
+            return PromiseOrValue::Value(0.into());
+        };
+
+        // a panic would leave the termination in flight, so the lockup is validated upfront
+        let lockup = self.internal_get_lockup(lockup_index).unwrap();
+        let validation = lockup
+            .vesting_oracle()
+            .ok_or_else(|| "No termination config".to_string())
+            .and_then(|_| lockup.validate_vested_balance(vested_balance.0))
+            .and_then(|()| {
+                expected_unvested.map_or(Ok(()), |expected_unvested| {
+                    validate_unvested_balance_within_tolerance(
+                        lockup.schedule.total_balance() - vested_balance.0,
+                        expected_unvested.0,
+                    )
+                })
+            });
+        if let Err(error) = validation {
+            log!("{}, lockup #{} is not terminated.", error, lockup_index);
+            return PromiseOrValue::Value(0.into());
+        }
+
+        self.internal_complete_termination(
+            lockup_index,
+            lockup,
+            termination_timestamp,
+            vested_balance.0,
+            expected_unvested,
+            terminated_by,
+            reason,
+        )
+    }
+
     fn convert_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> Vec<LockupIndex> {
+        self.assert_drafts_not_paused();
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let mut events: Vec<FtLockupCreateLockup> = vec![];
+        let mut rejections: Vec<FtLockupRejectBlockedAccount> = vec![];
         let lockup_ids: Vec<LockupIndex> = draft_ids
             .iter()
-            .map(|draft_id| {
-                let draft = self.drafts.remove(draft_id as _).expect("draft not found");
+            .flat_map(|draft_id| {
+                let draft = self.drafts.get(draft_id as _).expect("draft not found");
+                if let Some(account_id) = self.internal_find_blocked(draft.account_ids()) {
+                    log!("Skipping draft {}: account {} is blocked", draft_id, account_id);
+                    rejections.push(FtLockupRejectBlockedAccount {
+                        account_id: account_id.clone(),
+                        operation: "convert_draft".to_string(),
+                        draft_id: Some(*draft_id),
+                    });
+                    return vec![];
+                }
+                // drafts stored before the upgrade weren't validated against the new fields
+                if let Err(error) = draft.validate_new() {
+                    log!("Skipping draft {}: {}", draft_id, error);
+                    return vec![];
+                }
+                self.drafts.remove(draft_id as _);
+                self.num_drafts -= 1;
+                self.drafts_pending_account.remove(draft_id);
                 let draft_group = draft_group_lookup.entry(draft.draft_group_id).or_insert_with(|| {
                     self.draft_groups
                         .get(&draft.draft_group_id as _)
                         .expect("draft group not found")
                 });
-                draft_group.assert_can_convert_draft();
-                let payer_id = draft_group.payer_id.as_mut().expect("expected present payer_id");
-
-                assert!(draft_group.draft_indices.remove(draft_id), "Invariant");
-                let amount = draft.total_balance();
-                assert!(draft_group.total_amount >= amount, "Invariant");
-                draft_group.total_amount -= amount;
+                let payer_id = draft_group.convert_draft(*draft_id, draft.total_balance());
+                let created_by = self
+                    .draft_group_creators
+                    .get(&draft.draft_group_id)
+                    .unwrap_or_else(|| payer_id.clone());
+                let deposit_id = self.draft_group_deposits.get(&draft.draft_group_id);
 
-                let lockup = draft.lockup_create.into_lockup(payer_id);
-                let index = self.internal_add_lockup(&lockup);
+                let lockup_creates = draft.lockup_creates().unwrap_or_else(|error| panic!("{error}"));
+                lockup_creates
+                    .into_iter()
+                    .map(|lockup_create| {
+                        let lockup = lockup_create.into_lockup(&payer_id);
+                        let index = self.internal_add_created_lockup(&lockup, &lockup_create, &payer_id, &created_by);
+                        if let Some(deposit_id) = deposit_id {
+                            self.internal_record_funding(index, deposit_id, lockup_create.schedule.total_balance());
+                        }
 
-                let event: FtLockupCreateLockup = (index, lockup, Some(*draft_id)).into();
-                events.push(event);
+                        let event = self.internal_create_lockup_event(index, lockup, Some(*draft_id));
+                        events.push(event);
 
-                index
+                        index
+                    })
+                    .collect::<Vec<LockupIndex>>()
             })
             .collect();
 
-        emit(EventKind::FtLockupCreateLockup(events));
+        if !rejections.is_empty() {
+            emit(EventKind::FtLockupRejectBlockedAccount(rejections));
+        }
+        emit_batch(EventKind::FtLockupCreateLockup, events, self.config.event_batch_threshold);
 
+        let discrepancies: Vec<FtLockupDraftGroupDiscrepancy> = draft_group_lookup
+            .iter()
+            .filter_map(|(draft_group_id, draft_group)| {
+                draft_group
+                    .funding_discrepancy()
+                    .map(|unconverted_amount| FtLockupDraftGroupDiscrepancy {
+                        id: *draft_group_id,
+                        total_amount: draft_group.total_amount.into(),
+                        unconverted_amount: unconverted_amount.into(),
+                    })
+            })
+            .collect();
+        if !discrepancies.is_empty() {
+            log!("Draft group bookkeeping diverges from the funding.");
+            emit(EventKind::FtLockupDraftGroupDiscrepancy(discrepancies));
+        }
+
+        let mut completions: Vec<FtLockupDraftGroupCompleted> = vec![];
         for (draft_group_id, draft_group) in &draft_group_lookup {
             if draft_group.draft_indices.is_empty() {
-                self.draft_groups.remove(draft_group_id as _);
+                self.internal_remove_draft_group(*draft_group_id, draft_group);
+                completions.push(FtLockupDraftGroupCompleted {
+                    id: *draft_group_id,
+                    converted: draft_group.converted_amount.into(),
+                    total_amount: draft_group.funded_amount.into(),
+                });
             } else {
                 self.draft_groups.insert(draft_group_id as _, draft_group);
             }
         }
+        if !completions.is_empty() {
+            completions.sort_unstable_by_key(|completion| completion.id);
+            emit(EventKind::FtLockupDraftGroupCompleted(completions));
+        }
 
         lockup_ids
     }
+
+    #[private]
+    fn after_ft_metadata(&mut self) -> Option<u8> {
+        let metadata = match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<FungibleTokenMetadata>(&value).ok(),
+            PromiseResult::Failed => None,
+        };
+        if let Some(metadata) = metadata {
+            self.token_decimals = Some(metadata.decimals);
+        } else {
+            log!("Failed to fetch the token metadata.");
+        }
+        self.token_decimals
+    }
+
+    #[private]
+    fn after_account_creation(
+        &mut self,
+        draft_id: DraftIndex,
+        refund_account_id: AccountId,
+        deposit: NearToken,
+    ) -> Option<LockupIndex> {
+        let created = match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<bool>(&value).unwrap_or(false),
+            PromiseResult::Failed => false,
+        };
+        if !created {
+            // the factory returns the deposit when it reports the failure, and the deposit of a failed call
+            // is refunded to this contract as its predecessor, so either way it's forwarded to the operator
+            log!("Account creation has failed. Refunding the deposit.");
+            Promise::new(refund_account_id).transfer(deposit);
+        }
+
+        let Some(draft) = self.drafts.get(&draft_id as _) else {
+            log!("Draft {} has been converted or deleted meanwhile.", draft_id);
+            return None;
+        };
+        emit(EventKind::FtLockupCreateAccount(FtLockupCreateAccount {
+            draft_id,
+            account_id: draft.lockup_create.account_id,
+            created,
+        }));
+
+        if created {
+            // the draft stays unconverted if its account was blocked meanwhile
+            self.convert_drafts(vec![draft_id]).first().copied()
+        } else {
+            self.drafts_pending_account.insert(&draft_id);
+            None
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use hodl_model::{
+    escrow::{CliffEscrowMessage, EscrowApi},
+    lockup::{LockupClaim, LockupIndex},
+    WrappedBalance,
+};
+use near_sdk::{assert_one_yocto, env, near_bindgen, serde_json, AccountId, Promise, PromiseOrValue};
+
+use crate::{callbacks::ext_self, Contract, ContractExt, FtTransferPromise};
+
+#[near_bindgen]
+impl EscrowApi for Contract {
+    fn sweep_to_escrow(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance> {
+        self.assert_claims_not_paused();
+        let escrow_id = self
+            .cliff_escrows
+            .get(&lockup_index)
+            .expect("lockup has no cliff escrow");
+        assert!(!self.internal_is_pending(lockup_index), "pending lockup can't be swept");
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        self.assert_attested(&lockup.account_id);
+        let sweep_amount = self.internal_unreserved_balance(lockup_index, &lockup);
+        assert!(sweep_amount > 0, "nothing to sweep before the cliff");
+        self.internal_consume_claim_quota(sweep_amount);
+        let lockup_claim = lockup.claim(lockup_index, sweep_amount);
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.internal_record_claim(lockup_index);
+        self.internal_start_claim(lockup_index, sweep_amount);
+        self.internal_charge_claim_gas(
+            std::slice::from_ref(&lockup_claim),
+            self.config.gas.after_ft_transfer_gas(1),
+        );
+
+        self.internal_escrow_transfer(
+            lockup.account_id,
+            lockup_claim,
+            escrow_id,
+            self.config
+                .memo_templates
+                .cliff_escrow_sweep_memo(sweep_amount, lockup_index),
+        )
+        .into()
+    }
+
+    #[payable]
+    fn remove_cliff_escrow(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        let lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        assert_eq!(
+            lockup.account_id,
+            env::predecessor_account_id(),
+            "Only the lockup owner can remove the cliff escrow"
+        );
+        assert!(
+            self.cliff_escrows.remove(&lockup_index).is_some(),
+            "lockup has no cliff escrow"
+        );
+    }
+}
+
+impl Contract {
+    /// Forwards the claimed balance of the lockup to the escrow with `ft_transfer_call`,
+    /// `after_escrow_sweep` returns the part refused by the escrow to the lockup.
+    pub(crate) fn internal_escrow_transfer(
+        &self,
+        owner_id: AccountId,
+        lockup_claim: LockupClaim,
+        escrow_id: AccountId,
+        memo: String,
+    ) -> Promise {
+        let msg = serde_json::to_string(&CliffEscrowMessage {
+            owner_id: owner_id.clone(),
+            lockup_id: self.internal_lockup_id(lockup_claim.index),
+        })
+        .unwrap();
+        Promise::new(self.token_account_id.clone())
+            .ft_transfer_call(
+                &escrow_id,
+                lockup_claim.claim_amount.0,
+                Some(memo),
+                msg,
+                self.config.gas.ft_transfer_call,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer_gas(1))
+                    .after_escrow_sweep(owner_id, lockup_claim, escrow_id),
+            )
+    }
+
+    /// The escrow of the claims the owner can't receive if it's configured and the remaining gas
+    /// is enough to forward `num_lockups` lockups to it.
+    pub(crate) fn internal_claim_escrow(&self, num_lockups: usize) -> Option<AccountId> {
+        let escrow_id = self.config.claim_escrow_id.clone()?;
+        let gas = &self.config.gas;
+        let gas_per_lockup =
+            gas.ft_transfer_call.as_gas() + gas.after_ft_transfer_gas(1).as_gas() + gas.ext_call_cost.as_gas();
+        let required_gas = gas_per_lockup * u64::try_from(num_lockups).unwrap();
+        let remaining_gas = env::prepaid_gas().as_gas().saturating_sub(env::used_gas().as_gas());
+        (remaining_gas >= required_gas).then_some(escrow_id)
+    }
+}
@@ -1,105 +1,569 @@
 use hodl_model::{
-    draft::{Draft, DraftGroupIndex, DraftIndex},
-    lockup::{Lockup, LockupIndex},
+    airdrop::AirdropIndex,
+    draft::{Draft, DraftGroupIndex, DraftIndex, DraftRecipient},
+    fee::ClaimFee,
+    grant::GrantIndex,
+    lockup::{Lockup, LockupId, LockupIndex, LockupStage},
+    merkle::merkle_root,
+    util::current_timestamp_sec,
     TimestampSec, WrappedBalance,
 };
+use near_sdk::{
+    collections::{LazyOption, LookupMap},
+    NearSchema,
+};
 
-use crate::{log, serde_json, AccountId, Into, Serialize, PACKAGE_NAME, VERSION};
+use crate::{
+    env, log, serde_json, AccountId, Base58CryptoHash, Into, Serialize, StorageKey, EVENT_STANDARD_VERSION,
+    PACKAGE_NAME,
+};
 
 /// Events to be generated by the contract according to NEP-297
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupUpdateContract {}
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupNew {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub token_account_id: AccountId,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupAddToDepositWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     pub account_ids: Vec<AccountId>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupRemoveFromDepositWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     pub account_ids: Vec<AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupAddToDraftOperatorsWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     pub account_ids: Vec<AccountId>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupRemoveFromDraftOperatorsWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub account_ids: Vec<AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupAddToPayerWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRemoveFromPayerWhitelist {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub account_ids: Vec<AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// The pause flags after a change, see `Config::pause_flags`.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupSetPauseFlags {
+    pub drafts: bool,
+    pub claims: bool,
+    pub terminations: bool,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupSetAttestation {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub account_ids: Vec<AccountId>,
+    pub attested: bool,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupSetBlocked {
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
     pub account_ids: Vec<AccountId>,
+    pub blocked: bool,
+}
+
+/// A lockup for a blocked account is not created: the deposit is refunded or the draft is left unconverted.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRejectBlockedAccount {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    /// `lockup_create`, `grant_create` or `convert_draft`
+    pub operation: String,
+    pub draft_id: Option<DraftIndex>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupChangePayoutAddress {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub payout_id: Option<AccountId>,
+    pub effective_at: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCancelPayoutAddressChange {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupChangeClaimFee {
+    pub claim_fee: Option<ClaimFee>,
+    pub effective_at: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCancelClaimFeeChange {}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupScheduleAccountRemap {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub old_account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub new_account_id: AccountId,
+    pub executable_at: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCancelAccountRemap {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub old_account_id: AccountId,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRemapLockup {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub old_account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub new_account_id: AccountId,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupScheduleExtension {
+    pub by_sec: TimestampSec,
+    pub executable_at: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCancelScheduleExtension {}
+
+/// A batch of lockups the scheduled extension was applied to, `extended_ids` lists the lockups whose schedules
+/// were postponed after `timestamp` and `skipped_ids` the lockups that can't be extended, the other lockups
+/// of the range were already fully unlocked.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupExtendSchedules {
+    pub from_index: LockupIndex,
+    pub to_index: LockupIndex,
+    pub by_sec: TimestampSec,
+    pub timestamp: TimestampSec,
+    pub extended_ids: Vec<LockupIndex>,
+    pub skipped_ids: Vec<LockupIndex>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupComputeMerkleRoot {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub root: Base58CryptoHash,
+    pub height: u32,
+    pub num_lockups: u32,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCreateAirdrop {
+    pub id: AirdropIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub root: Base58CryptoHash,
+    pub num_leaves: u32,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_balance: WrappedBalance,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupFundAirdrop {
+    pub id: AirdropIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+}
+
+/// An admin operation signed by the manager signing key is executed, see `execute_signed`.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupExecuteSignedAction {
+    pub nonce: u64,
+    /// The account relaying the signed action
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub relayer_id: AccountId,
+}
+
+/// A deposit credited to the lockup pool of the account, see `create_lockups`.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupFundLockupPool {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    /// The unallocated balance of the pool after the deposit
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub balance: WrappedBalance,
+}
+
+/// The unallocated balance of the lockup pool returned to the account.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupWithdrawLockupPool {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRegisterAirdropLockup {
+    pub id: AirdropIndex,
+    pub leaf_index: u32,
+    pub lockup_id: LockupIndex,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupResign {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub from_deposit_whitelist: bool,
+    pub from_draft_operators_whitelist: bool,
+    pub from_payer_whitelist: bool,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupCreateLockup {
     pub id: LockupIndex,
+    pub lockup_id: LockupId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub balance: WrappedBalance,
     pub start: TimestampSec,
     pub finish: TimestampSec,
     pub terminatable: bool,
     pub draft_id: Option<DraftIndex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub agreement_hash: Option<Base58CryptoHash>,
+    pub stage: LockupStage,
 }
 
-impl From<(LockupIndex, Lockup, Option<DraftIndex>)> for FtLockupCreateLockup {
-    fn from(tuple: (LockupIndex, Lockup, Option<DraftIndex>)) -> Self {
-        let (id, lockup, draft_id) = tuple;
+impl From<(LockupIndex, LockupId, Lockup, Option<DraftIndex>)> for FtLockupCreateLockup {
+    fn from(tuple: (LockupIndex, LockupId, Lockup, Option<DraftIndex>)) -> Self {
+        let (id, lockup_id, lockup, draft_id) = tuple;
+        let stage = lockup.stage(current_timestamp_sec());
         Self {
             id,
+            lockup_id,
             account_id: lockup.account_id,
             balance: lockup.schedule.total_balance().into(),
             start: lockup.schedule.0.first().unwrap().timestamp,
             finish: lockup.schedule.0.last().unwrap().timestamp,
             terminatable: lockup.termination_config.is_some(),
             draft_id,
+            agreement_hash: None,
+            stage,
         }
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCreateGrant {
+    pub id: GrantIndex,
+    pub lockup_ids: Vec<LockupIndex>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupClaimLockup {
     pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    /// The protocol fee withheld from the amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub fee: Option<WrappedBalance>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupWarnUnclaimedRevocation {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub revocable_at: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupKeeperClaim {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub keeper_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub keeper_fee: WrappedBalance,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupPlaceLien {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub holder_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    pub redirect_claims: bool,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupReleaseLien {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub holder_id: AccountId,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupClaimLien {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub holder_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupSweepToEscrow {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub escrow_id: AccountId,
+    /// The balance accepted by the escrow
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRevokeUnclaimed {
+    pub id: LockupIndex,
+    /// The lockup owner
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRenounceLockup {
+    pub id: LockupIndex,
+    /// The lockup owner
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    /// The forfeited unclaimed balance
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub amount: WrappedBalance,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupTerminateLockup {
     pub id: LockupIndex,
+    /// The lockup owner
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
     pub termination_timestamp: TimestampSec,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub unvested_balance: WrappedBalance,
+    /// The account ID that received the unvested balance
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
+    pub stage: LockupStage,
+    /// The reason stated for the termination
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRelockLockup {
+    pub id: LockupIndex,
+    pub old_finish: TimestampSec,
+    pub new_finish: TimestampSec,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupClaimPendingLockup {
+    pub id: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupCreateDraftGroup {
     pub id: DraftGroupIndex,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupCreateDraft {
     pub id: DraftIndex,
     pub draft_group_id: DraftGroupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub account_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub balance: WrappedBalance,
     pub start: TimestampSec,
     pub finish: TimestampSec,
     pub terminatable: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<DraftRecipient>,
 }
 
 impl From<(DraftIndex, Draft)> for FtLockupCreateDraft {
@@ -113,54 +577,235 @@ impl From<(DraftIndex, Draft)> for FtLockupCreateDraft {
             start: draft.lockup_create.schedule.0.first().unwrap().timestamp,
             finish: draft.lockup_create.schedule.0.last().unwrap().timestamp,
             terminatable: draft.lockup_create.vesting_schedule.is_some(),
+            recipients: draft.recipients,
         }
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupApproveDraftGroup {
+    pub id: DraftGroupIndex,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupFundDraftGroup {
     pub id: DraftGroupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub amount: WrappedBalance,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupFreezeDraftGroup {
+    pub id: DraftGroupIndex,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupUnfreezeDraftGroup {
+    pub id: DraftGroupIndex,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupDiscardDraftGroup {
     pub id: DraftGroupIndex,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupVoidDraftGroup {
+    pub id: DraftGroupIndex,
+    pub draft_ids: Vec<DraftIndex>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+}
+
+/// Funding of a missing or discarded draft group, the transferred balance is refunded.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRefuseDraftGroupFunding {
+    pub id: DraftGroupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub sender_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    pub reason: String,
+}
+
+/// A deposit or a part of it returned to the sender by `ft_on_transfer`.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupRefund {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub sender_id: AccountId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: WrappedBalance,
+    pub reason: String,
+}
+
+/// The last draft of a draft group is converted, the group is removed.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupDraftGroupCompleted {
+    pub id: DraftGroupIndex,
+    /// The balance of the drafts converted into lockups
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub converted: WrappedBalance,
+    /// The balance funded by the payer
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_amount: WrappedBalance,
+}
+
+/// The bookkeeping of a draft group diverges from its funding, reported on conversion.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupDraftGroupDiscrepancy {
+    pub id: DraftGroupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_amount: WrappedBalance,
+    /// The funded amount not converted yet
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub unconverted_amount: WrappedBalance,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct FtLockupDeleteDraft {
     pub id: DraftIndex,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupCreateAccount {
+    pub draft_id: DraftIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub created: bool,
+}
+
+/// Replaces the per-item events of a bulk operation.
+/// The leaves of the merkle tree are sha256 hashes of the JSON of items as they would be emitted.
+#[derive(Serialize, Debug, NearSchema)]
+#[serde(crate = "near_sdk::serde")]
+#[abi(json)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtLockupBatchSummary {
+    pub event: String,
+    pub count: u32,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub merkle_root: Base58CryptoHash,
+}
+
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
 #[allow(clippy::enum_variant_names)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub(crate) enum EventKind {
     FtLockupNew(FtLockupNew),
     FtLockupAddToDepositWhitelist(FtLockupAddToDepositWhitelist),
     FtLockupRemoveFromDepositWhitelist(FtLockupRemoveFromDepositWhitelist),
     FtLockupAddToDraftOperatorsWhitelist(FtLockupAddToDraftOperatorsWhitelist),
     FtLockupRemoveFromDraftOperatorsWhitelist(FtLockupRemoveFromDraftOperatorsWhitelist),
+    FtLockupAddToPayerWhitelist(FtLockupAddToPayerWhitelist),
+    FtLockupRemoveFromPayerWhitelist(FtLockupRemoveFromPayerWhitelist),
+    FtLockupSetAttestation(FtLockupSetAttestation),
+    FtLockupSetBlocked(FtLockupSetBlocked),
+    FtLockupRejectBlockedAccount(Vec<FtLockupRejectBlockedAccount>),
+    FtLockupSetPauseFlags(FtLockupSetPauseFlags),
+    FtLockupChangePayoutAddress(FtLockupChangePayoutAddress),
+    FtLockupCancelPayoutAddressChange(FtLockupCancelPayoutAddressChange),
+    FtLockupChangeClaimFee(FtLockupChangeClaimFee),
+    FtLockupCancelClaimFeeChange(FtLockupCancelClaimFeeChange),
+    FtLockupScheduleAccountRemap(FtLockupScheduleAccountRemap),
+    FtLockupCancelAccountRemap(FtLockupCancelAccountRemap),
+    FtLockupRemapLockup(Vec<FtLockupRemapLockup>),
+    FtLockupScheduleExtension(FtLockupScheduleExtension),
+    FtLockupCancelScheduleExtension(FtLockupCancelScheduleExtension),
+    FtLockupExtendSchedules(FtLockupExtendSchedules),
+    FtLockupComputeMerkleRoot(FtLockupComputeMerkleRoot),
+    FtLockupCreateAirdrop(FtLockupCreateAirdrop),
+    FtLockupFundAirdrop(FtLockupFundAirdrop),
+    FtLockupFundLockupPool(FtLockupFundLockupPool),
+    FtLockupExecuteSignedAction(FtLockupExecuteSignedAction),
+    FtLockupWithdrawLockupPool(FtLockupWithdrawLockupPool),
+    FtLockupRegisterAirdropLockup(FtLockupRegisterAirdropLockup),
+    FtLockupResign(FtLockupResign),
     FtLockupCreateLockup(Vec<FtLockupCreateLockup>),
+    FtLockupCreateGrant(FtLockupCreateGrant),
     FtLockupClaimLockup(Vec<FtLockupClaimLockup>),
+    FtLockupKeeperClaim(Vec<FtLockupKeeperClaim>),
+    FtLockupPlaceLien(Vec<FtLockupPlaceLien>),
+    FtLockupReleaseLien(Vec<FtLockupReleaseLien>),
+    FtLockupClaimLien(Vec<FtLockupClaimLien>),
+    FtLockupSweepToEscrow(Vec<FtLockupSweepToEscrow>),
     FtLockupTerminateLockup(Vec<FtLockupTerminateLockup>),
+    FtLockupWarnUnclaimedRevocation(Vec<FtLockupWarnUnclaimedRevocation>),
+    FtLockupRevokeUnclaimed(Vec<FtLockupRevokeUnclaimed>),
+    FtLockupRenounceLockup(Vec<FtLockupRenounceLockup>),
+    FtLockupRelockLockup(Vec<FtLockupRelockLockup>),
+    FtLockupClaimPendingLockup(Vec<FtLockupClaimPendingLockup>),
     FtLockupCreateDraftGroup(Vec<FtLockupCreateDraftGroup>),
     FtLockupCreateDraft(Vec<FtLockupCreateDraft>),
+    FtLockupFreezeDraftGroup(Vec<FtLockupFreezeDraftGroup>),
+    FtLockupUnfreezeDraftGroup(Vec<FtLockupUnfreezeDraftGroup>),
+    FtLockupApproveDraftGroup(Vec<FtLockupApproveDraftGroup>),
     FtLockupFundDraftGroup(Vec<FtLockupFundDraftGroup>),
     FtLockupDiscardDraftGroup(Vec<FtLockupDiscardDraftGroup>),
+    FtLockupVoidDraftGroup(Vec<FtLockupVoidDraftGroup>),
+    FtLockupDraftGroupDiscrepancy(Vec<FtLockupDraftGroupDiscrepancy>),
+    FtLockupDraftGroupCompleted(Vec<FtLockupDraftGroupCompleted>),
+    FtLockupRefuseDraftGroupFunding(Vec<FtLockupRefuseDraftGroupFunding>),
+    FtLockupRefund(FtLockupRefund),
     FtLockupDeleteDraft(Vec<FtLockupDeleteDraft>),
+    FtLockupCreateAccount(FtLockupCreateAccount),
     FtLockupUpdateContract(FtLockupUpdateContract),
+    FtLockupBatchSummary(FtLockupBatchSummary),
+}
+
+impl EventKind {
+    fn name(&self) -> String {
+        serde_json::to_value(self).unwrap()["event"]
+            .as_str()
+            .expect("event name is expected")
+            .to_string()
+    }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, NearSchema)]
 #[serde(crate = "near_sdk::serde")]
+#[abi(json)]
 #[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub(crate) struct NearEvent {
     standard: String,
     version: String,
@@ -172,7 +817,7 @@ impl From<EventKind> for NearEvent {
     fn from(event_kind: EventKind) -> Self {
         Self {
             standard: PACKAGE_NAME.into(),
-            version: VERSION.into(),
+            version: EVENT_STANDARD_VERSION.into(),
             event_kind,
         }
     }
@@ -183,12 +828,86 @@ impl NearEvent {
         serde_json::to_string(self).unwrap()
     }
 
-    fn to_json_event_string(&self) -> String {
-        format!("EVENT_JSON:{}", self.to_json_string())
+    /// The event in the previous schema version. When the shape of an event changes,
+    /// its data is converted back to the previous shape here until the transition is over.
+    fn to_legacy_json_string(&self, legacy_version: &str) -> String {
+        let mut event = serde_json::to_value(self).unwrap();
+        event["version"] = legacy_version.into();
+        event.to_string()
     }
 
+    /// Logs the event, also in the legacy schema during a transition. Only the current schema is kept for replay.
     pub(crate) fn emit(self) {
-        log!("{}", &self.to_json_event_string());
+        let event = self.to_json_string();
+        log!("EVENT_JSON:{}", &event);
+        if let Some(legacy_version) = LEGACY_EVENT_STANDARD_VERSION {
+            log!("EVENT_JSON:{}", self.to_legacy_json_string(legacy_version));
+        }
+        let mut event_log = EventLog::new();
+        if event_log.is_enabled() {
+            event_log.push(event);
+        }
+    }
+}
+
+/// The previous event schema version while indexers migrate to `EVENT_STANDARD_VERSION`, each event is
+/// then emitted in both versions. Set on a bump of the schema version and reset once the transition is over.
+pub(crate) const LEGACY_EVENT_STANDARD_VERSION: Option<&str> = None;
+
+/// The number of the latest events kept in state for replay.
+pub(crate) const EVENT_LOG_SIZE: u64 = 256;
+
+/// A bounded on-chain buffer of emitted events keyed by a monotonically increasing sequence number.
+/// Only the latest `EVENT_LOG_SIZE` events are kept, older ones are evicted on push.
+pub(crate) struct EventLog {
+    next_seq: LazyOption<u64>,
+    events: LookupMap<u64, String>,
+    /// Set by the manager to stop paying for the storage writes, the kept events stay available.
+    disabled: LazyOption<bool>,
+}
+
+impl EventLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_seq: LazyOption::new(StorageKey::EventSeq, None),
+            events: LookupMap::new(StorageKey::EventLog),
+            disabled: LazyOption::new(StorageKey::EventLogDisabled, None),
+        }
+    }
+
+    pub(crate) fn next_seq(&self) -> u64 {
+        self.next_seq.get().unwrap_or_default()
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.disabled.get().unwrap_or_default()
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        if enabled {
+            self.disabled.remove();
+        } else {
+            self.disabled.set(&true);
+        }
+    }
+
+    fn push(&mut self, event: String) {
+        let seq = self.next_seq();
+        self.events.insert(&seq, &event);
+        if seq >= EVENT_LOG_SIZE {
+            self.events.remove(&(seq - EVENT_LOG_SIZE));
+        }
+        self.next_seq.set(&(seq + 1));
+    }
+
+    /// Returns the kept events starting from `seq`, skipping the ones already evicted.
+    pub(crate) fn events_since(&self, seq: u64, limit: u64) -> Vec<(u64, String)> {
+        let next_seq = self.next_seq();
+        let from = seq.max(next_seq.saturating_sub(EVENT_LOG_SIZE));
+        let to = next_seq.min(from.saturating_add(limit));
+        (from..to)
+            .map(|seq| (seq, self.events.get(&seq).expect("event is expected")))
+            .collect()
     }
 }
 
@@ -196,6 +915,29 @@ pub(crate) fn emit(event_kind: EventKind) {
     NearEvent::from(event_kind).emit();
 }
 
+/// Emits the items as a single event, or as a summary event if their number exceeds the batch threshold.
+pub(crate) fn emit_batch<T: Serialize>(
+    event_kind: fn(Vec<T>) -> EventKind,
+    items: Vec<T>,
+    batch_threshold: Option<u32>,
+) {
+    let count = u32::try_from(items.len()).unwrap();
+    if batch_threshold.map_or(true, |threshold| count <= threshold) {
+        emit(event_kind(items));
+        return;
+    }
+
+    let leaves = items
+        .iter()
+        .map(|item| env::sha256_array(&serde_json::to_vec(item).unwrap()))
+        .collect();
+    emit(EventKind::FtLockupBatchSummary(FtLockupBatchSummary {
+        event: event_kind(vec![]).name(),
+        count,
+        merkle_root: merkle_root(leaves).into(),
+    }));
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -211,7 +953,7 @@ mod tests {
     use super::*;
 
     pub fn get_context() -> VMContext {
-        VMContextBuilder::new().is_view(true).build()
+        VMContextBuilder::new().build()
     }
 
     fn compare_json(log: &str, value: Value) {
@@ -232,7 +974,23 @@ mod tests {
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_new",
+                "data": { "token_account_id": "token.near" },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_legacy_event_version() {
+        let token_account_id = AccountId::from_str("token.near").unwrap();
+        let event = NearEvent::from(EventKind::FtLockupNew(FtLockupNew { token_account_id }));
+
+        compare_json(
+            &format!("EVENT_JSON:{}", event.to_legacy_json_string("1.0.0")),
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": "1.0.0",
                 "event": "ft_lockup_new",
                 "data": { "token_account_id": "token.near" },
             }),
@@ -254,7 +1012,7 @@ mod tests {
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
                 "event": "ft_lockup_add_to_deposit_whitelist",
                 "data": { "account_ids": ["alice.near", "bob.near"] },
             }),
@@ -270,13 +1028,16 @@ mod tests {
             .map(|s| AccountId::from_str(s).unwrap())
             .collect();
         emit(EventKind::FtLockupRemoveFromDepositWhitelist(
-            FtLockupRemoveFromDepositWhitelist { account_ids },
+            FtLockupRemoveFromDepositWhitelist {
+                account_ids,
+                reason: None,
+            },
         ));
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
                 "event": "ft_lockup_remove_from_deposit_whitelist",
                 "data": { "account_ids": ["alice.near", "bob.near"] },
             }),
@@ -298,7 +1059,7 @@ mod tests {
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
                 "event": "ft_lockup_add_to_draft_operators_whitelist",
                 "data": { "account_ids": ["alice.near", "bob.near"] },
             }),
@@ -314,13 +1075,16 @@ mod tests {
             .map(|s| AccountId::from_str(s).unwrap())
             .collect();
         emit(EventKind::FtLockupRemoveFromDraftOperatorsWhitelist(
-            FtLockupRemoveFromDraftOperatorsWhitelist { account_ids },
+            FtLockupRemoveFromDraftOperatorsWhitelist {
+                account_ids,
+                reason: None,
+            },
         ));
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
                 "event": "ft_lockup_remove_from_draft_operators_whitelist",
                 "data": { "account_ids": ["alice.near", "bob.near"] },
             }),
@@ -328,59 +1092,860 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_lockup() {
+    fn test_ft_lockup_add_to_payer_whitelist() {
         testing_env!(get_context());
 
-        let account_id = AccountId::from_str("alice.near").unwrap();
-        let balance: WrappedBalance = 10_000.into();
-        let timestamp: TimestampSec = 1_500_000_000;
-        let lockup = Lockup::new_unlocked_since(account_id.clone(), balance.0, timestamp);
-        let lockup_id: LockupIndex = 100;
-        let draft_id: DraftIndex = 33;
-
-        let event: FtLockupCreateLockup = (100, lockup, Some(draft_id)).into();
-
-        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|s| AccountId::from_str(s).unwrap())
+            .collect();
+        emit(EventKind::FtLockupAddToPayerWhitelist(FtLockupAddToPayerWhitelist {
+            account_ids,
+        }));
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_create_lockup",
-                "data": [
-                    {
-                        "id": lockup_id,
-                        "account_id": account_id,
-                        "balance": balance,
-                        "start": timestamp - 1,
-                        "finish": timestamp,
-                        "terminatable": false,
-                        "draft_id": Some(draft_id),
-                    },
-                ],
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_add_to_payer_whitelist",
+                "data": { "account_ids": ["alice.near", "bob.near"] },
             }),
         );
     }
 
     #[test]
-    fn test_ft_lockup_claim_lockup() {
+    fn test_ft_lockup_remove_from_payer_whitelist() {
         testing_env!(get_context());
 
-        let lockup_id: LockupIndex = 100;
-        let amount: WrappedBalance = 10000.into();
-
-        let event = FtLockupClaimLockup { id: lockup_id, amount };
-
-        emit(EventKind::FtLockupClaimLockup(vec![event]));
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|s| AccountId::from_str(s).unwrap())
+            .collect();
+        emit(EventKind::FtLockupRemoveFromPayerWhitelist(
+            FtLockupRemoveFromPayerWhitelist {
+                account_ids,
+                reason: None,
+            },
+        ));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_remove_from_payer_whitelist",
+                "data": { "account_ids": ["alice.near", "bob.near"] },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remove_from_payer_whitelist_with_reason() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupRemoveFromPayerWhitelist(
+            FtLockupRemoveFromPayerWhitelist {
+                account_ids: vec![AccountId::from_str("alice.near").unwrap()],
+                reason: Some("Offboarded payroll provider".to_string()),
+            },
+        ));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_remove_from_payer_whitelist",
+                "data": {
+                    "account_ids": ["alice.near"],
+                    "reason": "Offboarded payroll provider",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_set_attestation() {
+        testing_env!(get_context());
+
+        let account_ids: Vec<AccountId> = vec!["alice.near", "bob.near"]
+            .iter()
+            .map(|s| AccountId::from_str(s).unwrap())
+            .collect();
+        emit(EventKind::FtLockupSetAttestation(FtLockupSetAttestation {
+            account_ids,
+            attested: true,
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_set_attestation",
+                "data": { "account_ids": ["alice.near", "bob.near"], "attested": true },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_reject_blocked_account() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupRejectBlockedAccount(vec![
+            FtLockupRejectBlockedAccount {
+                account_id: AccountId::from_str("alice.near").unwrap(),
+                operation: "convert_draft".to_string(),
+                draft_id: Some(7),
+            },
+        ]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_reject_blocked_account",
+                "data": [{ "account_id": "alice.near", "operation": "convert_draft", "draft_id": 7 }],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_set_pause_flags() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupSetPauseFlags(FtLockupSetPauseFlags {
+            drafts: true,
+            claims: false,
+            terminations: false,
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_set_pause_flags",
+                "data": { "drafts": true, "claims": false, "terminations": false },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_change_payout_address() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let payout_id = AccountId::from_str("alice-cold.near").unwrap();
+        emit(EventKind::FtLockupChangePayoutAddress(FtLockupChangePayoutAddress {
+            account_id: account_id.clone(),
+            payout_id: Some(payout_id.clone()),
+            effective_at: 1_500_172_800,
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_change_payout_address",
+                "data": {
+                    "account_id": account_id,
+                    "payout_id": payout_id,
+                    "effective_at": 1_500_172_800,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_cancel_payout_address_change() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        emit(EventKind::FtLockupCancelPayoutAddressChange(
+            FtLockupCancelPayoutAddressChange {
+                account_id: account_id.clone(),
+            },
+        ));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_cancel_payout_address_change",
+                "data": { "account_id": account_id },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_resign() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        emit(EventKind::FtLockupResign(FtLockupResign {
+            account_id,
+            from_deposit_whitelist: false,
+            from_draft_operators_whitelist: true,
+            from_payer_whitelist: false,
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_resign",
+                "data": {
+                    "account_id": "alice.near",
+                    "from_deposit_whitelist": false,
+                    "from_draft_operators_whitelist": true,
+                    "from_payer_whitelist": false,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_lockup() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let balance: WrappedBalance = 10_000.into();
+        let timestamp: TimestampSec = 1_500_000_000;
+        let lockup = Lockup::new_unlocked_since(account_id.clone(), balance.0, timestamp);
+        let lockup_id: LockupIndex = 100;
+        let draft_id: DraftIndex = 33;
+
+        let event: FtLockupCreateLockup = (100, 120, lockup, Some(draft_id)).into();
+
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "lockup_id": 120,
+                        "account_id": account_id,
+                        "balance": balance,
+                        "start": timestamp - 1,
+                        "finish": timestamp,
+                        "terminatable": false,
+                        "draft_id": Some(draft_id),
+                        "stage": "Active",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_lockup_with_agreement() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let lockup = Lockup::new_unlocked_since(account_id.clone(), 10_000, 1_500_000_000);
+        let agreement_hash: Base58CryptoHash = env::sha256_array(b"agreement").into();
+
+        let mut event: FtLockupCreateLockup = (100, 100, lockup, None).into();
+        event.agreement_hash = Some(agreement_hash);
+
+        emit(EventKind::FtLockupCreateLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_lockup",
+                "data": [
+                    {
+                        "id": 100,
+                        "lockup_id": 100,
+                        "account_id": account_id,
+                        "balance": "10000",
+                        "start": 1_499_999_999,
+                        "finish": 1_500_000_000,
+                        "terminatable": false,
+                        "draft_id": null,
+                        "agreement_hash": agreement_hash,
+                        "stage": "Active",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_grant() {
+        testing_env!(get_context());
+
+        let grant_id: GrantIndex = 5;
+
+        emit(EventKind::FtLockupCreateGrant(FtLockupCreateGrant {
+            id: grant_id,
+            lockup_ids: vec![10, 11],
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_grant",
+                "data": { "id": grant_id, "lockup_ids": [10, 11] },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_claim_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let amount: WrappedBalance = 10000.into();
+
+        let event = FtLockupClaimLockup {
+            id: lockup_id,
+            amount,
+            fee: None,
+        };
+
+        emit(EventKind::FtLockupClaimLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_claim_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "amount": amount,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_claim_lockup_with_fee() {
+        testing_env!(get_context());
+
+        let event = FtLockupClaimLockup {
+            id: 100,
+            amount: 10000.into(),
+            fee: Some(100.into()),
+        };
+
+        emit(EventKind::FtLockupClaimLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_claim_lockup",
+                "data": [
+                    {
+                        "id": 100,
+                        "amount": "10000",
+                        "fee": "100",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_change_claim_fee() {
+        testing_env!(get_context());
+
+        let collector_id = AccountId::from_str("fees.near").unwrap();
+        emit(EventKind::FtLockupChangeClaimFee(FtLockupChangeClaimFee {
+            claim_fee: Some(ClaimFee {
+                fee_bps: 50,
+                collector_id: collector_id.clone(),
+            }),
+            effective_at: 1_500_604_800,
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_change_claim_fee",
+                "data": {
+                    "claim_fee": { "fee_bps": 50, "collector_id": collector_id },
+                    "effective_at": 1_500_604_800,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_warn_unclaimed_revocation() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let revocable_at: TimestampSec = 1_800_000_000;
+
+        let event = FtLockupWarnUnclaimedRevocation {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            revocable_at,
+        };
+
+        emit(EventKind::FtLockupWarnUnclaimedRevocation(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_warn_unclaimed_revocation",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "account_id": account_id,
+                        "revocable_at": revocable_at,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_keeper_claim() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let keeper_id = AccountId::from_str("keeper.near").unwrap();
+        let keeper_fee: WrappedBalance = 50.into();
+
+        let event = FtLockupKeeperClaim {
+            id: lockup_id,
+            keeper_id: keeper_id.clone(),
+            keeper_fee,
+        };
+
+        emit(EventKind::FtLockupKeeperClaim(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_keeper_claim",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "keeper_id": keeper_id,
+                        "keeper_fee": keeper_fee,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_place_lien() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let holder_id = AccountId::from_str("lender.near").unwrap();
+        let amount: WrappedBalance = 5000.into();
+
+        let event = FtLockupPlaceLien {
+            id: lockup_id,
+            holder_id: holder_id.clone(),
+            amount,
+            redirect_claims: true,
+        };
+
+        emit(EventKind::FtLockupPlaceLien(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_place_lien",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "holder_id": holder_id,
+                        "amount": amount,
+                        "redirect_claims": true,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_sweep_to_escrow() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let escrow_id = AccountId::from_str("staking-escrow.near").unwrap();
+        let amount: WrappedBalance = 5000.into();
+
+        let event = FtLockupSweepToEscrow {
+            id: lockup_id,
+            escrow_id: escrow_id.clone(),
+            amount,
+        };
+
+        emit(EventKind::FtLockupSweepToEscrow(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_sweep_to_escrow",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "escrow_id": escrow_id,
+                        "amount": amount,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_revoke_unclaimed() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let amount: WrappedBalance = 10000.into();
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let beneficiary_id = AccountId::from_str("treasury.near").unwrap();
+
+        let event = FtLockupRevokeUnclaimed {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            amount,
+            beneficiary_id: beneficiary_id.clone(),
+        };
+
+        emit(EventKind::FtLockupRevokeUnclaimed(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_revoke_unclaimed",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "account_id": account_id,
+                        "amount": amount,
+                        "beneficiary_id": beneficiary_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_renounce_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let amount: WrappedBalance = 10000.into();
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let beneficiary_id = AccountId::from_str("treasury.near").unwrap();
+
+        let event = FtLockupRenounceLockup {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            amount,
+            beneficiary_id: beneficiary_id.clone(),
+        };
+
+        emit(EventKind::FtLockupRenounceLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_renounce_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "account_id": account_id,
+                        "amount": amount,
+                        "beneficiary_id": beneficiary_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_remap_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let old_account_id = AccountId::from_str("alice.near").unwrap();
+        let new_account_id = AccountId::from_str("alice-recovered.near").unwrap();
+
+        let event = FtLockupRemapLockup {
+            id: lockup_id,
+            old_account_id: old_account_id.clone(),
+            new_account_id: new_account_id.clone(),
+        };
+
+        emit(EventKind::FtLockupRemapLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_remap_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "old_account_id": old_account_id,
+                        "new_account_id": new_account_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_register_airdrop_lockup() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupRegisterAirdropLockup(
+            FtLockupRegisterAirdropLockup {
+                id: 2,
+                leaf_index: 41_999,
+                lockup_id: 100,
+            },
+        ));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_register_airdrop_lockup",
+                "data": {
+                    "id": 2,
+                    "leaf_index": 41_999,
+                    "lockup_id": 100,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_extend_schedules() {
+        testing_env!(get_context());
+
+        let timestamp: TimestampSec = 1_800_000_000;
+        let by_sec: TimestampSec = 14 * 24 * 60 * 60;
+
+        emit(EventKind::FtLockupExtendSchedules(FtLockupExtendSchedules {
+            from_index: 0,
+            to_index: 100,
+            by_sec,
+            timestamp,
+            extended_ids: vec![3, 42],
+            skipped_ids: vec![7],
+        }));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_extend_schedules",
+                "data": {
+                    "from_index": 0,
+                    "to_index": 100,
+                    "by_sec": by_sec,
+                    "timestamp": timestamp,
+                    "extended_ids": [3, 42],
+                    "skipped_ids": [7],
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_terminate_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let termination_timestamp: TimestampSec = 1_800_000_000;
+        let unvested_balance: WrappedBalance = 10000.into();
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let beneficiary_id = AccountId::from_str("treasury.near").unwrap();
+
+        let event = FtLockupTerminateLockup {
+            id: lockup_id,
+            account_id: account_id.clone(),
+            termination_timestamp,
+            unvested_balance,
+            beneficiary_id: beneficiary_id.clone(),
+            stage: LockupStage::Terminated,
+            reason: None,
+        };
+
+        emit(EventKind::FtLockupTerminateLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_terminate_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "account_id": account_id,
+                        "termination_timestamp": termination_timestamp,
+                        "unvested_balance": unvested_balance,
+                        "beneficiary_id": beneficiary_id,
+                        "stage": "Terminated",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_relock_lockup() {
+        testing_env!(get_context());
+
+        let lockup_id: LockupIndex = 100;
+        let old_finish: TimestampSec = 1_800_000_000;
+        let new_finish: TimestampSec = 1_900_000_000;
+
+        let event = FtLockupRelockLockup {
+            id: lockup_id,
+            old_finish,
+            new_finish,
+        };
+
+        emit(EventKind::FtLockupRelockLockup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_relock_lockup",
+                "data": [
+                    {
+                        "id": lockup_id,
+                        "old_finish": old_finish,
+                        "new_finish": new_finish,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+
+        let event = FtLockupCreateDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupCreateDraftGroup(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_draft_group",
+                "data": [
+                    {
+                        "id": draft_group_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_create_draft() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let balance: WrappedBalance = 10_000.into();
+        let timestamp: TimestampSec = 1_500_000_000;
+        let lockup_create = LockupCreate {
+            account_id: account_id.clone(),
+            schedule: Schedule::new_unlocked_since(balance.0, timestamp),
+            vesting_schedule: None,
+            tags: vec![],
+            owner_key: None,
+            owner_email_hash: None,
+            unclaimed_revocation_after_sec: None,
+            smoothing_sec: None,
+            agreement_hash: None,
+            keeper_incentive: None,
+            burn_on_termination: false,
+            curve: None,
+            cliff_escrow_id: None,
+            vesting: None,
+        };
+        let draft_group_id: DraftGroupIndex = 123;
+        let draft = Draft {
+            draft_group_id,
+            lockup_create,
+            recipients: vec![],
+        };
+        let draft_id: DraftIndex = 33;
+
+        let event: FtLockupCreateDraft = (draft_id, draft).into();
+
+        emit(EventKind::FtLockupCreateDraft(vec![event]));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_draft",
+                "data": [
+                    {
+                        "id": draft_id,
+                        "draft_group_id": draft_group_id,
+                        "account_id": account_id.to_string(),
+                        "balance": balance,
+                        "start": timestamp - 1,
+                        "finish": timestamp,
+                        "terminatable": false,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_fund_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+        let amount: WrappedBalance = 10000.into();
+
+        let event = FtLockupFundDraftGroup {
+            id: draft_group_id,
+            amount,
+        };
+
+        emit(EventKind::FtLockupFundDraftGroup(vec![event]));
+
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_claim_lockup",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_fund_draft_group",
                 "data": [
                     {
-                        "id": lockup_id,
+                        "id": draft_group_id,
                         "amount": amount,
                     },
                 ],
@@ -389,31 +1954,24 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_terminate_lockup() {
+    fn test_ft_lockup_freeze_draft_group() {
         testing_env!(get_context());
 
-        let lockup_id: LockupIndex = 100;
-        let termination_timestamp: TimestampSec = 1_800_000_000;
-        let unvested_balance: WrappedBalance = 10000.into();
+        let draft_group_id: DraftGroupIndex = 22;
 
-        let event = FtLockupTerminateLockup {
-            id: lockup_id,
-            termination_timestamp,
-            unvested_balance,
-        };
+        let event = FtLockupFreezeDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupFreezeDraftGroup(vec![event]));
 
-        emit(EventKind::FtLockupTerminateLockup(vec![event]));
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_terminate_lockup",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_freeze_draft_group",
                 "data": [
                     {
-                        "id": lockup_id,
-                        "termination_timestamp": termination_timestamp,
-                        "unvested_balance": unvested_balance,
+                        "id": draft_group_id,
                     },
                 ],
             }),
@@ -421,20 +1979,21 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_draft_group() {
+    fn test_ft_lockup_approve_draft_group() {
         testing_env!(get_context());
 
         let draft_group_id: DraftGroupIndex = 22;
 
-        let event = FtLockupCreateDraftGroup { id: draft_group_id };
+        let event = FtLockupApproveDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupApproveDraftGroup(vec![event]));
 
-        emit(EventKind::FtLockupCreateDraftGroup(vec![event]));
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_create_draft_group",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_approve_draft_group",
                 "data": [
                     {
                         "id": draft_group_id,
@@ -445,43 +2004,218 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_create_draft() {
+    fn test_ft_lockup_unfreeze_draft_group() {
         testing_env!(get_context());
 
-        let account_id = AccountId::from_str("alice.near").unwrap();
-        let balance: WrappedBalance = 10_000.into();
-        let timestamp: TimestampSec = 1_500_000_000;
-        let lockup_create = LockupCreate {
-            account_id: account_id.clone(),
-            schedule: Schedule::new_unlocked_since(balance.0, timestamp),
-            vesting_schedule: None,
+        let draft_group_id: DraftGroupIndex = 22;
+
+        let event = FtLockupUnfreezeDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupUnfreezeDraftGroup(vec![event]));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_unfreeze_draft_group",
+                "data": [
+                    {
+                        "id": draft_group_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_discard_draft_group() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+
+        let event = FtLockupDiscardDraftGroup { id: draft_group_id };
+
+        emit(EventKind::FtLockupDiscardDraftGroup(vec![event]));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_discard_draft_group",
+                "data": [
+                    {
+                        "id": draft_group_id,
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_refuse_draft_group_funding() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+        let sender_id = AccountId::from_str("treasury.near").unwrap();
+        let event = FtLockupRefuseDraftGroupFunding {
+            id: draft_group_id,
+            sender_id: sender_id.clone(),
+            amount: 5_000.into(),
+            reason: "draft group is discarded".to_string(),
         };
-        let draft_group_id: DraftGroupIndex = 123;
-        let draft = Draft {
-            draft_group_id,
-            lockup_create,
+
+        emit(EventKind::FtLockupRefuseDraftGroupFunding(vec![event]));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_refuse_draft_group_funding",
+                "data": [
+                    {
+                        "id": draft_group_id,
+                        "sender_id": sender_id,
+                        "amount": "5000",
+                        "reason": "draft group is discarded",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_draft_group_discrepancy() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+        let event = FtLockupDraftGroupDiscrepancy {
+            id: draft_group_id,
+            total_amount: 5_050.into(),
+            unconverted_amount: 5_000.into(),
         };
-        let draft_id: DraftIndex = 33;
 
-        let event: FtLockupCreateDraft = (draft_id, draft).into();
+        emit(EventKind::FtLockupDraftGroupDiscrepancy(vec![event]));
 
-        emit(EventKind::FtLockupCreateDraft(vec![event]));
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_draft_group_discrepancy",
+                "data": [
+                    {
+                        "id": draft_group_id,
+                        "total_amount": "5050",
+                        "unconverted_amount": "5000",
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_refund() {
+        testing_env!(get_context());
+
+        let sender_id = AccountId::from_str("treasury.near").unwrap();
+        emit(EventKind::FtLockupRefund(FtLockupRefund {
+            sender_id: sender_id.clone(),
+            amount: 50.into(),
+            reason: "overfunding".to_string(),
+        }));
 
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_create_draft",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_refund",
+                "data": {
+                    "sender_id": sender_id,
+                    "amount": "50",
+                    "reason": "overfunding",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_execute_signed_action() {
+        testing_env!(get_context());
+
+        let relayer_id = AccountId::from_str("relayer.near").unwrap();
+        emit(EventKind::FtLockupExecuteSignedAction(FtLockupExecuteSignedAction {
+            nonce: 7,
+            relayer_id: relayer_id.clone(),
+        }));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_execute_signed_action",
+                "data": {
+                    "nonce": 7,
+                    "relayer_id": relayer_id,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_fund_lockup_pool() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("operator.near").unwrap();
+        emit(EventKind::FtLockupFundLockupPool(FtLockupFundLockupPool {
+            account_id: account_id.clone(),
+            amount: 1_000.into(),
+            balance: 1_500.into(),
+        }));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_fund_lockup_pool",
+                "data": {
+                    "account_id": account_id,
+                    "amount": "1000",
+                    "balance": "1500",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_draft_group_completed() {
+        testing_env!(get_context());
+
+        let draft_group_id: DraftGroupIndex = 22;
+        let event = FtLockupDraftGroupCompleted {
+            id: draft_group_id,
+            converted: 5_000.into(),
+            total_amount: 5_000.into(),
+        };
+
+        emit(EventKind::FtLockupDraftGroupCompleted(vec![event]));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_draft_group_completed",
                 "data": [
                     {
-                        "id": draft_id,
-                        "draft_group_id": draft_group_id,
-                        "account_id": account_id.to_string(),
-                        "balance": balance,
-                        "start": timestamp - 1,
-                        "finish": timestamp,
-                        "terminatable": false,
+                        "id": draft_group_id,
+                        "converted": "5000",
+                        "total_amount": "5000",
                     },
                 ],
             }),
@@ -489,28 +2223,30 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_fund_draft_group() {
+    fn test_ft_lockup_void_draft_group() {
         testing_env!(get_context());
 
         let draft_group_id: DraftGroupIndex = 22;
-        let amount: WrappedBalance = 10000.into();
+        let amount: WrappedBalance = 5_000.into();
 
-        let event = FtLockupFundDraftGroup {
+        let event = FtLockupVoidDraftGroup {
             id: draft_group_id,
+            draft_ids: vec![3, 4],
             amount,
         };
 
-        emit(EventKind::FtLockupFundDraftGroup(vec![event]));
+        emit(EventKind::FtLockupVoidDraftGroup(vec![event]));
 
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_fund_draft_group",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_void_draft_group",
                 "data": [
                     {
                         "id": draft_group_id,
+                        "draft_ids": [3, 4],
                         "amount": amount,
                     },
                 ],
@@ -519,30 +2255,110 @@ mod tests {
     }
 
     #[test]
-    fn test_ft_lockup_discard_draft_group() {
+    fn test_ft_lockup_claim_pending_lockup() {
         testing_env!(get_context());
 
-        let draft_group_id: DraftGroupIndex = 22;
-
-        let event = FtLockupDiscardDraftGroup { id: draft_group_id };
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let lockup_index: LockupIndex = 10;
 
-        emit(EventKind::FtLockupDiscardDraftGroup(vec![event]));
+        emit(EventKind::FtLockupClaimPendingLockup(vec![
+            FtLockupClaimPendingLockup {
+                id: lockup_index,
+                account_id,
+            },
+        ]));
 
         compare_json(
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
-                "event": "ft_lockup_discard_draft_group",
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_claim_pending_lockup",
                 "data": [
                     {
-                        "id": draft_group_id,
+                        "id": lockup_index,
+                        "account_id": "alice.near",
                     },
                 ],
             }),
         );
     }
 
+    #[test]
+    fn test_ft_lockup_create_account() {
+        testing_env!(get_context());
+
+        let account_id = AccountId::from_str("alice.factory.near").unwrap();
+        let draft_id: DraftIndex = 33;
+
+        emit(EventKind::FtLockupCreateAccount(FtLockupCreateAccount {
+            draft_id,
+            account_id,
+            created: false,
+        }));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_create_account",
+                "data": {
+                    "draft_id": draft_id,
+                    "account_id": "alice.factory.near",
+                    "created": false,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_batch_summary() {
+        testing_env!(get_context());
+
+        let events: Vec<FtLockupDeleteDraft> = (0..3).map(|id| FtLockupDeleteDraft { id }).collect();
+        let leaves = events
+            .iter()
+            .map(|event| env::sha256_array(&serde_json::to_vec(event).unwrap()))
+            .collect();
+        let root: Base58CryptoHash = merkle_root(leaves).into();
+
+        emit_batch(EventKind::FtLockupDeleteDraft, events, Some(2));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_batch_summary",
+                "data": {
+                    "event": "ft_lockup_delete_draft",
+                    "count": 3,
+                    "merkle_root": root,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ft_lockup_batch_below_threshold() {
+        testing_env!(get_context());
+
+        let events: Vec<FtLockupDeleteDraft> = (0..2).map(|id| FtLockupDeleteDraft { id }).collect();
+
+        emit_batch(EventKind::FtLockupDeleteDraft, events, Some(2));
+
+        compare_json(
+            &test_utils::get_logs()[0],
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_delete_draft",
+                "data": [{ "id": 0 }, { "id": 1 }],
+            }),
+        );
+    }
+
     #[test]
     fn test_ft_lockup_delete_draft() {
         testing_env!(get_context());
@@ -557,7 +2373,7 @@ mod tests {
             &test_utils::get_logs()[0],
             json!({
                 "standard": PACKAGE_NAME,
-                "version": VERSION,
+                "version": EVENT_STANDARD_VERSION,
                 "event": "ft_lockup_delete_draft",
                 "data": [
                     {
@@ -567,4 +2383,51 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn test_event_log_keeps_latest_events() {
+        testing_env!(get_context());
+
+        for id in 0..EVENT_LOG_SIZE + 2 {
+            emit(EventKind::FtLockupDeleteDraft(vec![FtLockupDeleteDraft {
+                id: id.try_into().unwrap(),
+            }]));
+        }
+
+        let event_log = EventLog::new();
+        assert_eq!(event_log.next_seq(), EVENT_LOG_SIZE + 2);
+
+        let events = event_log.events_since(0, 3);
+        assert_eq!(events.iter().map(|(seq, _)| *seq).collect::<Vec<_>>(), vec![2, 3, 4]);
+        compare_json(
+            &format!("EVENT_JSON:{}", events[0].1),
+            json!({
+                "standard": PACKAGE_NAME,
+                "version": EVENT_STANDARD_VERSION,
+                "event": "ft_lockup_delete_draft",
+                "data": [{ "id": 2 }],
+            }),
+        );
+
+        assert!(event_log.events_since(EVENT_LOG_SIZE + 2, 10).is_empty());
+    }
+
+    #[test]
+    fn test_event_log_disabled() {
+        testing_env!(get_context());
+
+        emit(EventKind::FtLockupDeleteDraft(vec![FtLockupDeleteDraft { id: 0 }]));
+        EventLog::new().set_enabled(false);
+        emit(EventKind::FtLockupDeleteDraft(vec![FtLockupDeleteDraft { id: 1 }]));
+        assert_eq!(test_utils::get_logs().len(), 2);
+
+        let mut event_log = EventLog::new();
+        assert!(!event_log.is_enabled());
+        assert_eq!(event_log.next_seq(), 1);
+        assert_eq!(event_log.events_since(0, 10).len(), 1);
+
+        event_log.set_enabled(true);
+        emit(EventKind::FtLockupDeleteDraft(vec![FtLockupDeleteDraft { id: 2 }]));
+        assert_eq!(EventLog::new().next_seq(), 2);
+    }
 }
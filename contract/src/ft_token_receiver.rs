@@ -1,27 +1,116 @@
-use hodl_model::ft_message::FtMessage;
+use hodl_model::{draft::DraftGroupStatus, ft_message::FtMessage, grant::GrantIndex, Balance};
 use near_sdk::Gas;
 
 use crate::{
-    emit, env, log, near_bindgen, serde_json, AccountId, Contract, ContractExt, EventKind, FtLockupCreateLockup,
-    FtLockupFundDraftGroup, FungibleTokenReceiver, PromiseOrValue, GAS_EXT_CALL_COST, GAS_MIN_FOR_CONVERT, U128,
+    emit, env,
+    event::{
+        FtLockupFundAirdrop, FtLockupFundLockupPool, FtLockupRefund, FtLockupRefuseDraftGroupFunding,
+        FtLockupRejectBlockedAccount,
+    },
+    log,
+    measure::GasMeter,
+    near_bindgen, serde_json, AccountId, Contract, ContractExt, EventKind, FtLockupCreateGrant, FtLockupCreateLockup,
+    FtLockupFundDraftGroup, FungibleTokenReceiver, PromiseOrValue, U128,
 };
 
+impl Contract {
+    /// Returns the deposit or a part of it to the sender, the token contract refunds the unused amount.
+    fn refund_deposit(&self, sender_id: AccountId, amount: Balance, reason: &str) -> PromiseOrValue<U128> {
+        log!("Refunding {} to {}: {}", amount, sender_id, reason);
+        emit(EventKind::FtLockupRefund(FtLockupRefund {
+            sender_id,
+            amount: amount.into(),
+            reason: reason.to_string(),
+        }));
+        PromiseOrValue::Value(amount.into())
+    }
+}
+
 #[near_bindgen]
 impl FungibleTokenReceiver for Contract {
     fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        let mut meter = GasMeter::start();
         assert_eq!(env::predecessor_account_id(), self.token_account_id, "Invalid token ID");
         let amount = amount.into();
-        self.assert_deposit_whitelist(&sender_id);
 
-        let ft_message: FtMessage = serde_json::from_str(&msg).unwrap();
+        let Ok(ft_message) = serde_json::from_str::<FtMessage>(&msg) else {
+            return self.refund_deposit(sender_id, amount, "invalid msg");
+        };
+        let sender_policy = self.config.ft_on_transfer_policy.sender_policy(&ft_message);
+        if !self.internal_is_sender_allowed(&sender_id, sender_policy) {
+            let reason = format!("sender is not allowed by {sender_policy:?} policy");
+            return self.refund_deposit(sender_id, amount, &reason);
+        }
+        let blocked = match &ft_message {
+            FtMessage::LockupCreate(lockup_create) => self
+                .internal_find_blocked([&lockup_create.account_id])
+                .map(|account_id| ("lockup_create", account_id)),
+            FtMessage::GrantCreate(grant_create) => self
+                .internal_find_blocked(grant_create.stages.iter().map(|stage| &stage.account_id))
+                .map(|account_id| ("grant_create", account_id)),
+            _ => None,
+        };
+        if let Some((operation, account_id)) = blocked {
+            let reason = format!("account {account_id} is blocked");
+            emit(EventKind::FtLockupRejectBlockedAccount(vec![
+                FtLockupRejectBlockedAccount {
+                    account_id: account_id.clone(),
+                    operation: operation.to_string(),
+                    draft_id: None,
+                },
+            ]));
+            return self.refund_deposit(sender_id, amount, &reason);
+        }
+        if let Some(max_managed_balance) = self.config.max_managed_balance {
+            let managed_balance = self.token_flows.managed_balance();
+            if managed_balance.saturating_add(amount) > max_managed_balance.0 {
+                let reason = format!("deposit exceeds the managed balance cap of {}", max_managed_balance.0);
+                return self.refund_deposit(sender_id, amount, &reason);
+            }
+        }
+        // the excess transferred to fund a draft group is returned
+        let mut refund = 0;
+        if let FtMessage::DraftGroupFunding(funding) = &ft_message {
+            let reason = match self.draft_groups.get(&funding.draft_group_id) {
+                None => Some("draft group not found"),
+                Some(_) if self.config.pause_flags.drafts => Some("drafts are paused"),
+                Some(draft_group) if draft_group.status == DraftGroupStatus::Discarded => {
+                    Some("draft group is discarded")
+                }
+                Some(draft_group) => {
+                    refund = amount.saturating_sub(draft_group.total_amount);
+                    None
+                }
+            };
+            if let Some(reason) = reason {
+                log!("Refusing funding of draft group {}: {}", funding.draft_group_id, reason);
+                emit(EventKind::FtLockupRefuseDraftGroupFunding(vec![
+                    FtLockupRefuseDraftGroupFunding {
+                        id: funding.draft_group_id,
+                        sender_id: sender_id.clone(),
+                        amount: amount.into(),
+                        reason: reason.to_string(),
+                    },
+                ]));
+                return self.refund_deposit(sender_id, amount, reason);
+            }
+        }
+        let amount = amount - refund;
+        self.token_flows.deposit(amount);
+        let deposit_id = self.internal_record_deposit(&sender_id, amount);
 
         match ft_message {
             FtMessage::LockupCreate(lockup_create) => {
+                lockup_create.assert_valid();
+                self.assert_unlocked_at_creation(&lockup_create);
                 let lockup = lockup_create.into_lockup(&sender_id);
                 lockup.assert_new_valid(amount);
-                let index = self.internal_add_lockup(&lockup);
+                meter.phase("schedule math");
+                let index = self.internal_add_created_lockup(&lockup, &lockup_create, &sender_id, &sender_id);
+                self.internal_record_funding(index, deposit_id, amount);
+                meter.phase("serialization");
                 log!("Created new lockup for {} with index {}", lockup.account_id, index);
-                let event: FtLockupCreateLockup = (index, lockup, None).into();
+                let event = self.internal_create_lockup_event(index, lockup, None);
                 emit(EventKind::FtLockupCreateLockup(vec![event]));
             }
             FtMessage::DraftGroupFunding(funding) => {
@@ -34,17 +123,22 @@ impl FungibleTokenReceiver for Contract {
                     draft_group.total_amount, amount,
                     "The draft group total balance doesn't match the transferred balance",
                 );
-                draft_group.fund(&sender_id);
+                draft_group.fund(
+                    &sender_id,
+                    amount,
+                    self.config.draft_group_approval_threshold.map(|threshold| threshold.0),
+                );
                 self.draft_groups.insert(&draft_group_id as _, &draft_group);
+                self.draft_group_deposits.insert(&draft_group_id, &deposit_id);
                 log!("Funded draft group {}", draft_group_id);
 
                 if funding.try_convert.unwrap_or(false) {
                     // Using remaining gas to try convert drafts, not waiting for results
                     if let Some(remaining_gas) = env::prepaid_gas()
                         .as_gas()
-                        .checked_sub(env::used_gas().as_gas() + GAS_EXT_CALL_COST.as_gas())
+                        .checked_sub(env::used_gas().as_gas() + self.config.gas.ext_call_cost.as_gas())
                     {
-                        if remaining_gas > GAS_MIN_FOR_CONVERT.as_gas() {
+                        if remaining_gas > self.config.gas.min_for_convert.as_gas() {
                             crate::callbacks::ext_self::ext(env::current_account_id())
                                 .with_static_gas(Gas::from_gas(remaining_gas))
                                 .convert_drafts(draft_group.draft_indices.into_iter().collect());
@@ -57,8 +151,61 @@ impl FungibleTokenReceiver for Contract {
                 };
                 emit(EventKind::FtLockupFundDraftGroup(vec![event]));
             }
+            FtMessage::GrantCreate(grant_create) => {
+                grant_create.assert_valid();
+                assert_eq!(
+                    grant_create.total_balance(),
+                    amount,
+                    "The grant total balance doesn't match the transferred balance",
+                );
+                let grant_id = GrantIndex::try_from(self.grants.len()).unwrap();
+                let mut events: Vec<FtLockupCreateLockup> = vec![];
+                let mut lockup_ids = vec![];
+                for stage in &grant_create.stages {
+                    self.assert_unlocked_at_creation(stage);
+                    let lockup = stage.into_lockup(&sender_id);
+                    lockup.assert_new_valid(stage.schedule.total_balance());
+                    let index = self.internal_add_created_lockup(&lockup, stage, &sender_id, &sender_id);
+                    self.internal_record_funding(index, deposit_id, stage.schedule.total_balance());
+                    self.lockup_grants.insert(&index, &grant_id);
+                    lockup_ids.push(index);
+                    events.push(self.internal_create_lockup_event(index, lockup, None));
+                }
+                self.grants.push(&lockup_ids);
+                log!("Created new grant with index {}", grant_id);
+                emit(EventKind::FtLockupCreateLockup(events));
+                emit(EventKind::FtLockupCreateGrant(FtLockupCreateGrant {
+                    id: grant_id,
+                    lockup_ids,
+                }));
+            }
+            FtMessage::AirdropFunding(funding) => {
+                let airdrop_id = funding.airdrop_id;
+                let mut airdrop = self.airdrops.get(u64::from(airdrop_id)).expect("airdrop not found");
+                airdrop.fund(amount, deposit_id);
+                self.airdrops.replace(u64::from(airdrop_id), &airdrop);
+                log!("Funded airdrop {}", airdrop_id);
+                emit(EventKind::FtLockupFundAirdrop(FtLockupFundAirdrop {
+                    id: airdrop_id,
+                    amount: amount.into(),
+                }));
+            }
+            FtMessage::LockupPoolFunding(_) => {
+                let mut pool = self.lockup_pools.get(&sender_id).unwrap_or_default();
+                pool.fund(deposit_id, amount);
+                self.lockup_pools.insert(&sender_id, &pool);
+                log!("Funded lockup pool of {}", sender_id);
+                emit(EventKind::FtLockupFundLockupPool(FtLockupFundLockupPool {
+                    account_id: sender_id.clone(),
+                    amount: amount.into(),
+                    balance: pool.balance().into(),
+                }));
+            }
         }
 
+        if refund > 0 {
+            return self.refund_deposit(sender_id, refund, "overfunding");
+        }
         PromiseOrValue::Value(0.into())
     }
 }
@@ -1,14 +1,54 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
-use hodl_model::lockup::{Lockup, LockupIndex};
+use hodl_model::{
+    config::{SenderPolicy, TerminationPolicy},
+    draft::{DraftGroup, DraftGroupIndex, DraftIndex},
+    fee::ClaimFee,
+    lien::Lien,
+    lockup::{Lockup, LockupClaim, LockupCreate, LockupId, LockupIndex, LockupStage, LockupStatus, LockupView},
+    provenance::{Deposit, DepositIndex, FundingShare},
+    reason::validate_admin_reason,
+    revocation::UnclaimedRevocation,
+    stats::LockupClaimStats,
+    termination::TerminationBurn,
+    util::current_timestamp_sec,
+    Balance, TimestampSec,
+};
 
-use crate::{AccountId, Contract};
+use crate::{
+    callbacks::ext_self,
+    env,
+    event::{emit, EventKind, FtLockupCreateLockup, FtLockupSetPauseFlags},
+    log, AccountId, Contract, FtTransferPromise, Gas, Promise, StorageKey, UnorderedSet,
+};
 
 impl Contract {
+    pub(crate) fn assert_manager(&self) {
+        assert_eq!(env::predecessor_account_id(), self.manager, "Not a manager");
+    }
+
     pub(crate) fn assert_deposit_whitelist(&self, account_id: &AccountId) {
         assert!(self.deposit_whitelist.contains(account_id), "Not in deposit whitelist");
     }
 
+    /// Checks the caller can terminate the lockup or revoke its unclaimed balance.
+    pub(crate) fn assert_can_terminate(&self, lockup_index: LockupIndex) {
+        let account_id = env::predecessor_account_id();
+        match self.config.termination_policy {
+            TerminationPolicy::DepositWhitelist => self.assert_deposit_whitelist(&account_id),
+            TerminationPolicy::CreatorOrManager => {
+                if account_id != self.manager {
+                    self.assert_deposit_whitelist(&account_id);
+                    assert_eq!(
+                        self.lockup_creators.get(&lockup_index),
+                        Some(account_id),
+                        "Only the lockup creator or the manager can terminate the lockup"
+                    );
+                }
+            }
+        }
+    }
+
     pub(crate) fn assert_draft_operators_whitelist(&self, account_id: &AccountId) {
         assert!(
             (self.deposit_whitelist.contains(account_id) || self.draft_operators_whitelist.contains(account_id)),
@@ -16,16 +56,464 @@ impl Contract {
         );
     }
 
-    pub(crate) fn internal_add_lockup(&mut self, lockup: &Lockup) -> LockupIndex {
+    pub(crate) fn assert_attestor(&self, account_id: &AccountId) {
+        assert_eq!(Some(account_id), self.config.attestor_id.as_ref(), "Not an attestor");
+    }
+
+    pub(crate) fn assert_attested(&self, account_id: &AccountId) {
+        assert!(
+            self.internal_is_attested(account_id),
+            "Account {account_id} is not attested to claim"
+        );
+    }
+
+    pub(crate) fn internal_is_attested(&self, account_id: &AccountId) -> bool {
+        self.config.attestor_id.is_none() || self.attested_accounts.contains(account_id)
+    }
+
+    /// Removes the group left without drafts, keeping its amounts for `get_draft_group_status`.
+    pub(crate) fn internal_remove_draft_group(&mut self, draft_group_id: DraftGroupIndex, draft_group: &DraftGroup) {
+        self.draft_groups.remove(&draft_group_id as _);
+        self.closed_draft_groups.insert(&draft_group_id, draft_group);
+    }
+
+    /// Validates the reason of a termination or a whitelist removal, required by `config.require_admin_reason`.
+    pub(crate) fn assert_admin_reason(&self, reason: Option<&str>) {
+        match reason {
+            Some(reason) => validate_admin_reason(reason).unwrap_or_else(|error| panic!("{error}")),
+            None => assert!(
+                !self.config.require_admin_reason,
+                "A reason is required for terminations and whitelist removals"
+            ),
+        }
+    }
+
+    pub(crate) fn assert_blocklist_manager(&self, account_id: &AccountId) {
+        assert_eq!(
+            Some(account_id),
+            self.config.blocklist_manager_id.as_ref(),
+            "Not a blocklist manager"
+        );
+    }
+
+    pub(crate) fn assert_not_blocked(&self, account_id: &AccountId) {
+        assert!(
+            !self.blocked_accounts.contains(account_id),
+            "Account {account_id} is blocked"
+        );
+    }
+
+    /// Returns the first blocked account among the lockup owners, if any.
+    pub(crate) fn internal_find_blocked<'a>(
+        &self,
+        account_ids: impl IntoIterator<Item = &'a AccountId>,
+    ) -> Option<&'a AccountId> {
+        account_ids
+            .into_iter()
+            .find(|account_id| self.blocked_accounts.contains(account_id))
+    }
+
+    pub(crate) fn internal_is_sender_allowed(&self, sender_id: &AccountId, sender_policy: SenderPolicy) -> bool {
+        match sender_policy {
+            SenderPolicy::DepositWhitelist => self.deposit_whitelist.contains(sender_id),
+            SenderPolicy::PayerWhitelist => self.payer_whitelist.contains(sender_id),
+            SenderPolicy::DepositOrPayerWhitelist => {
+                self.deposit_whitelist.contains(sender_id) || self.payer_whitelist.contains(sender_id)
+            }
+        }
+    }
+
+    pub(crate) fn internal_refresh_token_metadata(&self) -> Promise {
+        Promise::new(self.token_account_id.clone())
+            .ft_metadata(self.config.gas.ft_metadata)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_metadata)
+                    .after_ft_metadata(),
+            )
+    }
+
+    pub(crate) fn internal_lockup_view(
+        &self,
+        lockup_index: LockupIndex,
+        lockup: Lockup,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> LockupView {
+        let timestamp = at_timestamp.unwrap_or_else(current_timestamp_sec);
+        let stage = self.internal_lockup_stage(lockup_index, &lockup, timestamp);
+        let mut view = LockupView::at_timestamp(lockup, timestamp);
+        view.stage = stage;
+        view.tags = self.lockup_tags.get(&lockup_index).unwrap_or_default();
+        view.pending_owner_key = self.pending_lockups.get(&lockup_index);
+        view.pending_owner_email_hash = self.pending_email_lockups.get(&lockup_index).map(Into::into);
+        view.grant_id = self.lockup_grants.get(&lockup_index);
+        view.agreement_hash = self.lockup_agreements.get(&lockup_index).map(Into::into);
+        view.keeper_incentive = self.keeper_incentives.get(&lockup_index);
+        view.lien = self.liens.get(&lockup_index);
+        view.cliff_escrow_id = self.cliff_escrows.get(&lockup_index);
+        view.created_by = self.lockup_creators.get(&lockup_index);
+        view.note = self
+            .lockup_notes
+            .get(&lockup_index)
+            .and_then(|history| history.last().cloned())
+            .filter(|note| !note.note.is_empty());
+        view.burn_on_termination = self.burn_on_termination.contains(&lockup_index);
+        view.lockup_id = self.internal_lockup_id(lockup_index);
+        view.in_flight_claim_balance = self.claims_in_flight.get(&lockup_index).unwrap_or_default();
+        view.status = self.internal_lockup_status(lockup_index);
+        view.claim_stats = self.claim_stats.get(&lockup_index).unwrap_or_default();
+        if humanized.unwrap_or(false) {
+            view.humanize(
+                self.token_decimals
+                    .expect("Token decimals are unknown, call refresh_token_metadata first"),
+            )
+        } else {
+            view
+        }
+    }
+
+    /// Loads the lockup with its unlock curve, which isn't a part of the stored lockup.
+    pub(crate) fn internal_get_lockup(&self, lockup_index: LockupIndex) -> Option<Lockup> {
+        self.lockups.get(u64::from(lockup_index)).map(|mut lockup| {
+            lockup.curve = self.lockup_curves.get(&lockup_index);
+            lockup.vesting = self.lockup_vesting_schedules.get(&lockup_index);
+            lockup
+        })
+    }
+
+    /// The indices of the page of `limit` lockups from `from_index`, the remaining lockups by default.
+    pub(crate) fn internal_lockup_range(
+        &self,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> std::ops::Range<LockupIndex> {
+        let num_lockups = LockupIndex::try_from(self.lockups.len()).unwrap();
+        let from_index = std::cmp::min(from_index.unwrap_or(0), num_lockups);
+        let to_index = limit.map_or(num_lockups, |limit| {
+            std::cmp::min(num_lockups, from_index.saturating_add(limit))
+        });
+        from_index..to_index
+    }
+
+    /// The unclaimed balance of the page of lockups, including the pending ones.
+    pub(crate) fn internal_unclaimed_balance(
+        &self,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> Balance {
+        self.internal_lockup_range(from_index, limit)
+            .map(|index| {
+                let lockup = self.internal_get_lockup(index).unwrap();
+                lockup.schedule.total_balance() - lockup.claimed_balance
+            })
+            .sum()
+    }
+
+    pub(crate) fn internal_add_lockup(&mut self, lockup: &Lockup, tags: &[String]) -> LockupIndex {
         let index = LockupIndex::try_from(self.lockups.len()).unwrap();
         self.lockups.push(lockup);
+        self.lockup_unlock_starts.insert(&index, &lockup.unlock_start());
         let mut indices = self.account_lockups.get(&lockup.account_id).unwrap_or_default();
         indices.insert(index);
         self.internal_save_account_lockups(&lockup.account_id, indices);
+        if !tags.is_empty() {
+            for tag in tags {
+                let mut indices = self.tag_lockups.get(tag).unwrap_or_else(|| {
+                    UnorderedSet::new(StorageKey::TagLockupIndices {
+                        tag_hash: env::sha256_array(tag.as_bytes()),
+                    })
+                });
+                indices.insert(&index);
+                self.tag_lockups.insert(tag, &indices);
+            }
+            self.lockup_tags.insert(&index, &tags.to_vec());
+        }
         index
     }
 
-    pub(crate) fn internal_save_account_lockups(&mut self, account_id: &AccountId, indices: HashSet<LockupIndex>) {
+    /// Lockups are only appended to `lockups` and never moved, so the index is stable and serves as the id.
+    pub(crate) fn internal_lockup_id(&self, lockup_index: LockupIndex) -> LockupId {
+        LockupId::from(lockup_index)
+    }
+
+    pub(crate) fn internal_lockup_index(&self, lockup_id: LockupId) -> Option<LockupIndex> {
+        LockupIndex::try_from(lockup_id)
+            .ok()
+            .filter(|index| u64::from(*index) < self.lockups.len())
+    }
+
+    /// Checks the share unlocked at creation against `config.max_unlocked_at_creation_bps`.
+    pub(crate) fn assert_unlocked_at_creation(&self, lockup_create: &LockupCreate) {
+        if let Some(max_unlocked_bps) = self.config.max_unlocked_at_creation_bps {
+            lockup_create
+                .validate_unlocked_at_creation(max_unlocked_bps, current_timestamp_sec())
+                .unwrap_or_else(|error| panic!("{error}"));
+        }
+    }
+
+    pub(crate) fn internal_add_created_lockup(
+        &mut self,
+        lockup: &Lockup,
+        lockup_create: &LockupCreate,
+        payer_id: &AccountId,
+        created_by: &AccountId,
+    ) -> LockupIndex {
+        let index = self.internal_add_lockup(lockup, &lockup_create.tags);
+        self.lockup_creators.insert(&index, created_by);
+        if let Some(owner_key) = &lockup_create.owner_key {
+            self.pending_lockups.insert(&index, owner_key);
+        }
+        if let Some(owner_email_hash) = lockup_create.owner_email_hash {
+            self.pending_email_lockups.insert(&index, &owner_email_hash.into());
+        }
+        if let Some(agreement_hash) = lockup_create.agreement_hash {
+            self.lockup_agreements.insert(&index, &agreement_hash.into());
+        }
+        if let Some(keeper_incentive) = &lockup_create.keeper_incentive {
+            self.keeper_incentives.insert(&index, keeper_incentive);
+        }
+        if lockup_create.burn_on_termination {
+            self.burn_on_termination.insert(&index);
+        }
+        if let Some(curve) = &lockup_create.curve {
+            self.lockup_curves.insert(&index, curve);
+        }
+        if let Some(vesting) = &lockup.vesting {
+            self.lockup_vesting_schedules.insert(&index, vesting);
+        }
+        if let Some(escrow_id) = &lockup_create.cliff_escrow_id {
+            self.cliff_escrows.insert(&index, escrow_id);
+        }
+        if let Some(after_sec) = lockup_create.unclaimed_revocation_after_sec {
+            self.unclaimed_revocations
+                .insert(&index, &UnclaimedRevocation::new(payer_id.clone(), after_sec));
+        }
+        index
+    }
+
+    pub(crate) fn internal_record_deposit(&mut self, sender_id: &AccountId, amount: Balance) -> DepositIndex {
+        let deposit_id = DepositIndex::try_from(self.deposits.len()).unwrap();
+        self.deposits.push(&Deposit {
+            sender_id: sender_id.clone(),
+            amount,
+            timestamp: current_timestamp_sec(),
+        });
+        deposit_id
+    }
+
+    pub(crate) fn internal_record_funding(
+        &mut self,
+        lockup_index: LockupIndex,
+        deposit_id: DepositIndex,
+        amount: Balance,
+    ) {
+        let mut funding = self.lockup_funding.get(&lockup_index).unwrap_or_default();
+        funding.push(FundingShare { deposit_id, amount });
+        self.lockup_funding.insert(&lockup_index, &funding);
+    }
+
+    /// The protocol fee withheld from claims at the moment.
+    pub(crate) fn internal_claim_fee(&self) -> Option<ClaimFee> {
+        self.config.fee_config.claim_fee(current_timestamp_sec()).cloned()
+    }
+
+    /// The burn of the unvested balance upon termination, `None` if it's returned to the payer.
+    pub(crate) fn internal_termination_burn(&self, lockup_index: LockupIndex) -> Option<TerminationBurn> {
+        self.burn_on_termination.contains(&lockup_index).then(|| {
+            self.config
+                .termination_burn
+                .clone()
+                .expect("Termination burn is not configured")
+        })
+    }
+
+    pub(crate) fn internal_create_lockup_event(
+        &self,
+        lockup_index: LockupIndex,
+        lockup: Lockup,
+        draft_id: Option<DraftIndex>,
+    ) -> FtLockupCreateLockup {
+        let stage = self.internal_lockup_stage(lockup_index, &lockup, current_timestamp_sec());
+        let mut event: FtLockupCreateLockup =
+            (lockup_index, self.internal_lockup_id(lockup_index), lockup, draft_id).into();
+        event.agreement_hash = self.lockup_agreements.get(&lockup_index).map(Into::into);
+        event.stage = stage;
+        event
+    }
+
+    /// Reserves the claimed amount of the lockup until its transfer callback resolves,
+    /// so overlapping claims can't rely on the state of an unresolved transfer.
+    pub(crate) fn internal_start_claim(&mut self, lockup_index: LockupIndex, claim_amount: Balance) {
+        self.assert_no_termination_in_flight(lockup_index);
+        assert!(
+            self.claims_in_flight.insert(&lockup_index, &claim_amount).is_none(),
+            "Claim of lockup #{lockup_index} is in flight, try later"
+        );
+    }
+
+    /// Releases the reservation of the claimed amount, committed on success or refunded on failure by the caller.
+    /// Runs inside resolve callbacks, so a mismatching reservation is logged and still cleared instead of panicking.
+    pub(crate) fn internal_finish_claim(&mut self, lockup_index: LockupIndex, claim_amount: Balance) {
+        // claims started before the upgrade introducing the reservations resolve without them
+        if let Some(in_flight_amount) = self.claims_in_flight.remove(&lockup_index) {
+            if in_flight_amount != claim_amount {
+                log!(
+                    "Claim of lockup #{} resolved {} while {} was reserved",
+                    lockup_index,
+                    claim_amount,
+                    in_flight_amount
+                );
+            }
+        }
+    }
+
+    pub(crate) fn assert_no_claim_in_flight(&self, lockup_index: LockupIndex) {
+        assert!(
+            !self.claims_in_flight.contains_key(&lockup_index),
+            "Claim of lockup #{lockup_index} is in flight, try later"
+        );
+    }
+
+    pub(crate) fn internal_emit_pause_flags(&self) {
+        let pause_flags = self.config.pause_flags;
+        emit(EventKind::FtLockupSetPauseFlags(FtLockupSetPauseFlags {
+            drafts: pause_flags.drafts,
+            claims: pause_flags.claims,
+            terminations: pause_flags.terminations,
+        }));
+    }
+
+    pub(crate) fn assert_drafts_not_paused(&self) {
+        assert!(!self.config.pause_flags.drafts, "Drafts are paused");
+    }
+
+    pub(crate) fn assert_claims_not_paused(&self) {
+        assert!(!self.config.pause_flags.claims, "Claims are paused");
+    }
+
+    pub(crate) fn assert_terminations_not_paused(&self) {
+        assert!(!self.config.pause_flags.terminations, "Terminations are paused");
+    }
+
+    pub(crate) fn internal_start_termination(&mut self, lockup_index: LockupIndex) {
+        self.assert_no_claim_in_flight(lockup_index);
+        assert!(
+            self.terminations_in_flight.insert(&lockup_index),
+            "Termination of lockup #{lockup_index} is in flight, try later"
+        );
+    }
+
+    /// Whether a claim or a termination of the lockup waits for its callback.
+    pub(crate) fn internal_is_in_flight(&self, lockup_index: LockupIndex) -> bool {
+        self.claims_in_flight.contains_key(&lockup_index) || self.terminations_in_flight.contains(&lockup_index)
+    }
+
+    pub(crate) fn assert_no_termination_in_flight(&self, lockup_index: LockupIndex) {
+        assert!(
+            !self.terminations_in_flight.contains(&lockup_index),
+            "Termination of lockup #{lockup_index} is in flight, try later"
+        );
+    }
+
+    /// Whether the lockup is held by the contract account until its owner key or email holder claims it.
+    pub(crate) fn internal_is_pending(&self, lockup_index: LockupIndex) -> bool {
+        self.pending_lockups.contains_key(&lockup_index) || self.pending_email_lockups.contains_key(&lockup_index)
+    }
+
+    pub(crate) fn internal_lockup_stage(
+        &self,
+        lockup_index: LockupIndex,
+        lockup: &Lockup,
+        timestamp: TimestampSec,
+    ) -> LockupStage {
+        if self.internal_is_pending(lockup_index) {
+            LockupStage::PendingAcceptance
+        } else if self.terminated_lockups.contains(&lockup_index) {
+            LockupStage::Terminated
+        } else if !lockup.is_fully_claimed() && !self.internal_is_attested(&lockup.account_id) {
+            LockupStage::Paused
+        } else {
+            lockup.stage(timestamp)
+        }
+    }
+
+    pub(crate) fn internal_lockup_status(&self, lockup_index: LockupIndex) -> LockupStatus {
+        if self.terminations_in_flight.contains(&lockup_index) {
+            LockupStatus::TerminationInFlight
+        } else if self.claims_in_flight.contains_key(&lockup_index) {
+            LockupStatus::ClaimInFlight
+        } else {
+            LockupStatus::Active
+        }
+    }
+
+    /// Charges the gas used by the claim so far and its callback gas to the claimed lockups,
+    /// see `LockupClaimStats::gas_shares`.
+    pub(crate) fn internal_charge_claim_gas(&mut self, lockup_claims: &[LockupClaim], callback_gas: Gas) {
+        let gas = env::used_gas().saturating_add(callback_gas);
+        let amounts: Vec<Balance> = lockup_claims
+            .iter()
+            .map(|lockup_claim| lockup_claim.claim_amount.0)
+            .collect();
+        for (lockup_claim, gas_share) in lockup_claims.iter().zip(LockupClaimStats::gas_shares(gas, &amounts)) {
+            let mut stats = self.claim_stats.get(&lockup_claim.index).unwrap_or_default();
+            stats.record(gas_share);
+            self.claim_stats.insert(&lockup_claim.index, &stats);
+        }
+    }
+
+    pub(crate) fn internal_record_claim(&mut self, lockup_index: LockupIndex) {
+        if let Some(mut revocation) = self.unclaimed_revocations.get(&lockup_index) {
+            revocation.on_claim(current_timestamp_sec());
+            self.unclaimed_revocations.insert(&lockup_index, &revocation);
+        }
+    }
+
+    /// The balance the owner can claim from the lockup, excluding the balance reserved by a lien.
+    /// Nothing is claimable until the balance is swept to the cliff escrow.
+    pub(crate) fn internal_claimable_balance(&self, lockup_index: LockupIndex, lockup: &Lockup) -> Balance {
+        if self.cliff_escrows.contains_key(&lockup_index) {
+            return 0;
+        }
+        self.internal_unreserved_balance(lockup_index, lockup)
+    }
+
+    /// The unclaimed unlocked balance of the lockup not reserved by a lien.
+    pub(crate) fn internal_unreserved_balance(&self, lockup_index: LockupIndex, lockup: &Lockup) -> Balance {
+        match self.liens.get(&lockup_index) {
+            Some(lien) => self.internal_owner_claimable(&lien, lockup),
+            None => lockup.unlocked_balance(current_timestamp_sec()) - lockup.claimed_balance,
+        }
+    }
+
+    pub(crate) fn internal_owner_claimable(&self, lien: &Lien, lockup: &Lockup) -> Balance {
+        lien.owner_claimable(lockup.unlocked_balance(current_timestamp_sec()) - lockup.claimed_balance)
+    }
+
+    /// The account receiving the claims of the lockup owner.
+    pub(crate) fn internal_payout_id(&self, account_id: &AccountId) -> AccountId {
+        self.payout_addresses
+            .get(account_id)
+            .and_then(|payout_address| payout_address.payout_id(current_timestamp_sec()).cloned())
+            .unwrap_or_else(|| account_id.clone())
+    }
+
+    pub(crate) fn internal_remaining_claim_quota(&self) -> Option<Balance> {
+        self.config.claim_throttle.as_ref().map(|throttle| {
+            let period_number = throttle.period_number(env::block_height(), current_timestamp_sec());
+            self.claim_quota.remaining(throttle, period_number)
+        })
+    }
+
+    pub(crate) fn internal_consume_claim_quota(&mut self, amount: Balance) {
+        if let Some(throttle) = &self.config.claim_throttle {
+            let period_number = throttle.period_number(env::block_height(), current_timestamp_sec());
+            self.claim_quota.consume(throttle, period_number, amount);
+        }
+    }
+
+    pub(crate) fn internal_save_account_lockups(&mut self, account_id: &AccountId, indices: BTreeSet<LockupIndex>) {
         if indices.is_empty() {
             self.account_lockups.remove(account_id);
         } else {
@@ -33,12 +521,82 @@ impl Contract {
         }
     }
 
+    /// Removes the finalized lockup from the sets of its tags, the tags stay in the lockup view.
+    pub(crate) fn internal_prune_lockup_tags(&mut self, lockup_index: LockupIndex) {
+        for tag in self.lockup_tags.get(&lockup_index).unwrap_or_default() {
+            let Some(mut indices) = self.tag_lockups.get(&tag) else {
+                continue;
+            };
+            indices.remove(&lockup_index);
+            if indices.is_empty() {
+                self.tag_lockups.remove(&tag);
+            } else {
+                self.tag_lockups.insert(&tag, &indices);
+            }
+        }
+    }
+
+    /// Moves the emptied lockup from the account lockups to its terminated lockups.
+    pub(crate) fn internal_remove_terminated_lockup(&mut self, account_id: &AccountId, lockup_index: LockupIndex) {
+        let mut indices = self.account_lockups.get(account_id).unwrap_or_default();
+        indices.remove(&lockup_index);
+        self.internal_save_account_lockups(account_id, indices);
+        self.internal_prune_lockup_tags(lockup_index);
+        let mut terminated_indices = self.account_terminated_lockups.get(account_id).unwrap_or_default();
+        terminated_indices.insert(lockup_index);
+        self.account_terminated_lockups.insert(account_id, &terminated_indices);
+    }
+
     pub(crate) fn internal_get_account_lockups(&self, account_id: &AccountId) -> Vec<(LockupIndex, Lockup)> {
         self.account_lockups
             .get(account_id)
             .unwrap_or_default()
             .into_iter()
-            .map(|lockup_index| (lockup_index, self.lockups.get(u64::from(lockup_index)).unwrap()))
+            .map(|lockup_index| (lockup_index, self.internal_get_lockup(lockup_index).unwrap()))
+            .collect()
+    }
+
+    pub(crate) fn internal_get_account_lockup_views(
+        &self,
+        account_id: &AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.internal_get_account_lockups(account_id)
+            .into_iter()
+            .map(|(lockup_index, lockup)| {
+                (
+                    lockup_index,
+                    self.internal_lockup_view(lockup_index, lockup, humanized, at_timestamp),
+                )
+            })
+            .collect()
+    }
+
+    /// The account lockups with a positive claimable balance, with the balance.
+    /// Fully claimed lockups are already removed from the account lockups and the lockups not unlocking yet
+    /// are skipped by their unlock start without being read. The lockups with nothing to claim are dropped
+    /// as they're read instead of being kept for the claim.
+    pub(crate) fn internal_get_claimable_account_lockups(
+        &self,
+        account_id: &AccountId,
+    ) -> Vec<(LockupIndex, Balance, Lockup)> {
+        let timestamp = current_timestamp_sec();
+        self.account_lockups
+            .get(account_id)
+            .unwrap_or_default()
+            .into_iter()
+            // the lockups created before the upgrade have no unlock start and are always read
+            .filter(|lockup_index| {
+                self.lockup_unlock_starts
+                    .get(lockup_index)
+                    .map_or(true, |unlock_start| unlock_start < timestamp)
+            })
+            .filter_map(|lockup_index| {
+                let lockup = self.internal_get_lockup(lockup_index).unwrap();
+                let claimable_balance = self.internal_claimable_balance(lockup_index, &lockup);
+                (claimable_balance > 0).then_some((lockup_index, claimable_balance, lockup))
+            })
             .collect()
     }
 
@@ -56,7 +614,7 @@ impl Contract {
                     account_lockup_ids.contains(&lockup_index),
                     "lockup not found for account: {lockup_index}",
                 );
-                let lockup = self.lockups.get(u64::from(lockup_index)).unwrap();
+                let lockup = self.internal_get_lockup(lockup_index).unwrap();
                 (lockup_index, lockup)
             })
             .collect()
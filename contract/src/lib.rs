@@ -1,63 +1,213 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     convert::Into,
 };
 
 use hodl_model::{
-    draft::{Draft, DraftGroup, DraftGroupIndex, DraftIndex},
-    lockup::{Lockup, LockupIndex},
+    airdrop::{Airdrop, AirdropIndex},
+    config::{Config, FtOnTransferPolicy, TerminationPolicy},
+    draft::{Draft, DraftGroup, DraftGroupCreations, DraftGroupIndex, DraftIndex},
+    extension::ScheduleExtension,
+    fee::ClaimFee,
+    gas::GasConfig,
+    grant::GrantIndex,
+    keeper::KeeperIncentive,
+    lien::Lien,
+    lockup::{allocate_claim_amount, validate_claim_memo, ClaimResult, Lockup, LockupCreate, LockupIndex},
     lockup_api::LockupApi,
+    memo::MemoTemplates,
+    merkle::{LockupLeaf, LockupsMerkleComputation, LockupsMerkleRoot, MerkleFrontier},
+    note::{push_note, validate_note, LockupNote},
+    payout::PayoutAddress,
+    pending::{pending_lockup_message, PendingLockupProof},
+    pool::LockupPool,
+    provenance::{Deposit, DepositIndex, FundingShare},
+    remap::AccountRemap,
+    revocation::UnclaimedRevocation,
+    schedule::{Schedule, UnlockCurve},
+    stats::{LockupClaimStats, TokenFlows},
+    termination::{TerminationBurn, TerminationRecord},
+    throttle::{ClaimQuota, ClaimThrottle},
+    u256::U256,
     util::current_timestamp_sec,
-    TimestampSec, TokenAccountId, WrappedBalance,
+    Balance, TimestampSec, TokenAccountId, WrappedBalance,
 };
 // use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
 use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
 use near_sdk::{
-    assert_one_yocto,
-    collections::{LookupMap, UnorderedMap, UnorderedSet, Vector},
+    assert_one_yocto, borsh,
+    collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector},
     env, ext_contract, is_promise_success,
     json_types::{Base58CryptoHash, U128},
     log, near, near_bindgen,
     serde::Serialize,
-    serde_json, AccountId, BorshStorageKey, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+    serde_json, AccountId, BorshStorageKey, CryptoHash, CurveType, Gas, IntoStorageKey, NearToken, PanicOnDefault,
+    Promise, PromiseOrValue, PublicKey,
 };
-use near_self_update_proc::SelfUpdate;
 
+pub mod airdrop;
 pub mod callbacks;
+pub mod escrow;
 pub mod event;
 pub mod ft_token_receiver;
 pub mod internal;
 
-mod migration;
+pub mod lien;
+mod measure;
+pub mod migration;
+pub mod privacy;
+pub mod signed;
+pub mod update;
 pub mod view;
+pub mod web4;
 
 use crate::{
     callbacks::{ext_self, SelfCallbacks},
     event::{
-        emit, EventKind, FtLockupAddToDepositWhitelist, FtLockupAddToDraftOperatorsWhitelist, FtLockupClaimLockup,
+        emit, emit_batch, EventKind, EventLog, FtLockupAddToDepositWhitelist, FtLockupAddToDraftOperatorsWhitelist,
+        FtLockupAddToPayerWhitelist, FtLockupApproveDraftGroup, FtLockupCancelAccountRemap,
+        FtLockupCancelClaimFeeChange, FtLockupCancelPayoutAddressChange, FtLockupCancelScheduleExtension,
+        FtLockupChangeClaimFee, FtLockupChangePayoutAddress, FtLockupClaimLockup, FtLockupComputeMerkleRoot,
         FtLockupCreateDraft, FtLockupCreateDraftGroup, FtLockupCreateLockup, FtLockupDeleteDraft,
-        FtLockupDiscardDraftGroup, FtLockupFundDraftGroup, FtLockupNew, FtLockupRemoveFromDepositWhitelist,
-        FtLockupRemoveFromDraftOperatorsWhitelist, FtLockupTerminateLockup,
+        FtLockupDiscardDraftGroup, FtLockupExtendSchedules, FtLockupFreezeDraftGroup, FtLockupFundDraftGroup,
+        FtLockupNew, FtLockupRelockLockup, FtLockupRemapLockup, FtLockupRemoveFromDepositWhitelist,
+        FtLockupRemoveFromDraftOperatorsWhitelist, FtLockupRemoveFromPayerWhitelist, FtLockupRenounceLockup,
+        FtLockupResign, FtLockupRevokeUnclaimed, FtLockupScheduleAccountRemap, FtLockupScheduleExtension,
+        FtLockupSetAttestation, FtLockupSetBlocked, FtLockupTerminateLockup, FtLockupUnfreezeDraftGroup,
+        FtLockupVoidDraftGroup, FtLockupWarnUnclaimedRevocation, FtLockupWithdrawLockupPool,
     },
+    measure::GasMeter,
+    migration::{DraftGroupV1, DraftV1},
     serde_json::json,
 };
 
 pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The version of the event schema, set in the `version` field of every event. It is bumped only
+/// when the shape of an event changes, independently of the contract version.
+pub const EVENT_STANDARD_VERSION: &str = "1.1.0";
 
-const GAS_FOR_FT_TRANSFER: Gas = Gas::from_gas(15_000_000_000_000);
-const GAS_FOR_AFTER_FT_TRANSFER: Gas = Gas::from_gas(20_000_000_000_000);
-const GAS_EXT_CALL_COST: Gas = Gas::from_gas(10_000_000_000_000);
-const GAS_MIN_FOR_CONVERT: Gas = Gas::from_gas(15_000_000_000_000);
+/// Maximum allowed deviation of the actual unvested balance from the expected one on termination, in basis points.
+/// The caller computes the expected balance off-chain before the transaction lands, so the vesting keeps going
+/// for a few blocks (or until a multisig approval) and the exact value can't be known. 1% absorbs that drift
+/// while still catching a wrong lockup or a wrong termination timestamp.
+pub const UNVESTED_BALANCE_TOLERANCE_BPS: u128 = 100;
+
+/// Raw key of the manager kept outside of the contract state, so a wiped state can only be
+/// initialized again by the manager with `reinit_after_state_wipe`.
+const INIT_MANAGER_KEY: &[u8] = b"INIT_MANAGER";
 
 #[near(contract_state)]
-#[derive(PanicOnDefault, SelfUpdate)]
+#[derive(PanicOnDefault)]
 pub struct Contract {
     pub token_account_id: TokenAccountId,
 
     pub lockups: Vector<Lockup>,
 
-    pub account_lockups: LookupMap<AccountId, HashSet<LockupIndex>>,
+    /// Indices of the lockups of each account, ordered so claims and events follow the lockup order.
+    pub account_lockups: LookupMap<AccountId, BTreeSet<LockupIndex>>,
+
+    /// Owner keys of lockups held by the contract until the key holder claims them.
+    pub pending_lockups: LookupMap<LockupIndex, PublicKey>,
+    /// Owner email hashes of lockups held by the contract until the email attestor signs their claims.
+    pub pending_email_lockups: LookupMap<LockupIndex, CryptoHash>,
+
+    /// Tags of lockups created with tags.
+    pub lockup_tags: LookupMap<LockupIndex, Vec<String>>,
+    /// Lockups of every tag, a lockup is removed from the sets of its tags once it's finalized.
+    pub tag_lockups: LookupMap<String, UnorderedSet<LockupIndex>>,
+
+    /// Lockup indices of every grant stage in order.
+    pub grants: Vector<Vec<LockupIndex>>,
+    /// The grant of every lockup created as a grant stage.
+    pub lockup_grants: LookupMap<LockupIndex, GrantIndex>,
+
+    /// Revocation state of lockups created with `unclaimed_revocation_after_sec`.
+    pub unclaimed_revocations: LookupMap<LockupIndex, UnclaimedRevocation>,
+
+    /// Hashes of the off-chain agreements bound to lockups at creation.
+    pub lockup_agreements: LookupMap<LockupIndex, CryptoHash>,
+
+    /// The accounts that created lockups: the sender of the deposit or the creator of the draft group.
+    pub lockup_creators: LookupMap<LockupIndex, AccountId>,
+    /// The operators that created draft groups.
+    pub draft_group_creators: LookupMap<DraftGroupIndex, AccountId>,
+
+    /// Liens of lien holders on lockups used as collateral.
+    pub liens: LookupMap<LockupIndex, Lien>,
+    /// The lien holder each lockup owner allowed to place a lien on the lockup.
+    pub lien_approvals: LookupMap<LockupIndex, AccountId>,
+    /// Accounts allowed to place liens, e.g. lending protocols.
+    pub lien_holders_whitelist: UnorderedSet<AccountId>,
+
+    /// Notes of the operators on lockups, the latest `MAX_NOTE_HISTORY` edits per lockup.
+    pub lockup_notes: LookupMap<LockupIndex, Vec<LockupNote>>,
+
+    /// The keys accounts sign `ViewAuth` with while `config.privacy_mode` is set.
+    pub viewing_keys: LookupMap<AccountId, PublicKey>,
+    /// Accounts allowed to view the details of all lockups while `config.privacy_mode` is set.
+    pub privacy_viewers_whitelist: UnorderedSet<AccountId>,
+
+    /// Contracts allowed to report the vested balance of `VestingConditions::External` at termination.
+    pub vesting_oracles_whitelist: UnorderedSet<AccountId>,
+
+    /// The unallocated deposits of whitelisted accounts, debited by `create_lockups`.
+    pub lockup_pools: UnorderedMap<AccountId, LockupPool>,
+
+    /// The offline key of the manager signing the actions of `execute_signed`.
+    pub manager_signing_key: Option<PublicKey>,
+    /// The nonce of the latest signed action executed per manager account.
+    pub signed_action_nonces: LookupMap<AccountId, u64>,
+
+    /// Keeper incentives of lockups created with `keeper_incentive`.
+    pub keeper_incentives: LookupMap<LockupIndex, KeeperIncentive>,
+
+    /// Accounts receiving the claims of lockup owners who registered a payout address.
+    pub payout_addresses: LookupMap<AccountId, PayoutAddress>,
+
+    /// Reassignments of all lockups of lost accounts scheduled by the manager, by the old account.
+    pub account_remaps: LookupMap<AccountId, AccountRemap>,
+
+    /// Merkle airdrops posted by the manager, their lockups are registered by the owners.
+    pub airdrops: Vector<Airdrop>,
+    /// The registered leaves of every airdrop.
+    pub airdrop_registrations: LookupSet<(AirdropIndex, u32)>,
+
+    /// The computation of the merkle root of all lockups in progress, see `compute_lockups_merkle_root`.
+    pub lockups_merkle_computation: Option<LockupsMerkleComputation>,
+    /// The last merkle root of all lockups computed.
+    pub lockups_merkle_root: Option<LockupsMerkleRoot>,
+
+    /// The extension of all schedules scheduled by the manager, until it's applied to all lockups.
+    pub schedule_extension: Option<ScheduleExtension>,
+
+    /// Claim amounts of lockups whose transfer awaits its callback, committed or returned to the lockup by it.
+    /// The lockups can't be claimed or terminated meanwhile.
+    pub claims_in_flight: LookupMap<LockupIndex, Balance>,
+
+    /// Lockups whose termination or revocation transfer awaits its callback.
+    pub terminations_in_flight: LookupSet<LockupIndex>,
+
+    /// Lockups whose unvested balance was returned by a termination or an unclaimed revocation.
+    pub terminated_lockups: LookupSet<LockupIndex>,
+    /// Terminated lockups removed from `account_lockups` once emptied, kept so the owner's history stays queryable.
+    pub account_terminated_lockups: LookupMap<AccountId, BTreeSet<LockupIndex>>,
+
+    /// Lockups created with `burn_on_termination`.
+    pub burn_on_termination: LookupSet<LockupIndex>,
+
+    /// Unlock curves of lockups created with `curve`, see `Lockup::curve`.
+    pub lockup_curves: LookupMap<LockupIndex, UnlockCurve>,
+    /// Vesting schedules of lockups created with `vesting`, see `Lockup::vesting`.
+    pub lockup_vesting_schedules: LookupMap<LockupIndex, Schedule>,
+    /// The timestamp lockups start unlocking after, see `Lockup::unlock_start`. Claims skip the lockups
+    /// not unlocking yet without reading them. Schedules are only postponed, so a stale value is only earlier.
+    pub lockup_unlock_starts: LookupMap<LockupIndex, TimestampSec>,
+    /// The claims of every claimed lockup, see `LockupView::claim_stats`.
+    pub claim_stats: LookupMap<LockupIndex, LockupClaimStats>,
+
+    /// Escrows the first claim of lockups created with `cliff_escrow_id` is swept to, see `sweep_to_escrow`.
+    pub cliff_escrows: LookupMap<LockupIndex, AccountId>,
 
     /// account ids that can perform all actions:
     /// - manage deposit_whitelist
@@ -69,13 +219,62 @@ pub struct Contract {
     /// - manage drafts, draft_groups
     pub draft_operators_whitelist: UnorderedSet<AccountId>,
 
+    /// account ids that can fund draft_groups if allowed by the `ft_on_transfer` policy
+    pub payer_whitelist: UnorderedSet<AccountId>,
+
+    /// account ids allowed to claim by the attestor, checked only if the attestor is configured
+    pub attested_accounts: LookupSet<AccountId>,
+
+    /// account ids that can't receive new lockups, managed by `config.blocklist_manager_id`
+    pub blocked_accounts: LookupSet<AccountId>,
+
     pub next_draft_id: DraftIndex,
     pub drafts: LookupMap<DraftIndex, Draft>,
+    /// Number of existing drafts, since `drafts` can't be counted
+    pub num_drafts: u32,
+    /// Drafts whose owner account creation has failed, they can still be converted the usual way.
+    pub drafts_pending_account: UnorderedSet<DraftIndex>,
     pub next_draft_group_id: DraftGroupIndex,
     pub draft_groups: UnorderedMap<DraftGroupIndex, DraftGroup>,
+    /// Draft groups removed once all their drafts are converted or deleted, kept for `get_draft_group_status`.
+    pub closed_draft_groups: LookupMap<DraftGroupIndex, DraftGroup>,
+    /// Draft groups of the pre-upgrade layout left to migrate by `continue_migration`.
+    pub legacy_draft_groups: UnorderedMap<DraftGroupIndex, DraftGroupV1>,
+    /// Drafts of the pre-upgrade layout, migrated along with their draft groups.
+    pub legacy_drafts: LookupMap<DraftIndex, DraftV1>,
+    /// Draft groups created per account today, tracked while `config.max_draft_groups_per_day` is set.
+    pub draft_group_creations: LookupMap<AccountId, DraftGroupCreations>,
 
     /// The account ID authorized to perform sensitive operations on the contract.
     pub manager: AccountId,
+
+    /// The account allowed to call `update_contract`, set by the manager.
+    pub multisig: Option<AccountId>,
+    /// Accounts approving the code hashes accepted by `update_contract`.
+    pub update_guardians: UnorderedSet<AccountId>,
+    /// Code hashes approved by the guardians, each approval is consumed by an update.
+    pub approved_code_hashes: UnorderedSet<CryptoHash>,
+
+    /// History of all lockup terminations.
+    pub terminations: Vector<TerminationRecord>,
+
+    /// Log of all accepted `ft_on_transfer` deposits.
+    pub deposits: Vector<Deposit>,
+    /// The deposits funding lockups, kept after the conversion of drafts.
+    pub lockup_funding: LookupMap<LockupIndex, Vec<FundingShare>>,
+    /// The deposit funding every funded draft group, kept after the group is removed.
+    pub draft_group_deposits: LookupMap<DraftGroupIndex, DepositIndex>,
+
+    /// Cached `decimals` of the token metadata, used to humanize amounts in views.
+    pub token_decimals: Option<u8>,
+
+    /// Lifetime counters of deposited, claimed, refunded and burned tokens.
+    pub token_flows: TokenFlows,
+
+    pub config: Config,
+
+    /// The amount claimed within the current period of `config.claim_throttle`.
+    pub claim_quota: ClaimQuota,
 }
 
 #[near(serializers=[borsh, json])]
@@ -87,6 +286,58 @@ pub(crate) enum StorageKey {
     DraftOperatorsWhitelist,
     Drafts,
     DraftGroups,
+    Terminations,
+    DraftGroupsV2,
+    LockupTags,
+    TagLockups,
+    DraftsV2,
+    PayerWhitelist,
+    DraftsPendingAccount,
+    PendingLockups,
+    EventSeq,
+    EventLog,
+    AttestedAccounts,
+    Grants,
+    LockupGrants,
+    UnclaimedRevocations,
+    LockupAgreements,
+    KeeperIncentives,
+    PayoutAddresses,
+    ClaimsInFlight,
+    TerminationsInFlight,
+    TerminatedLockups,
+    DraftGroupCreations,
+    BurnOnTermination,
+    Deposits,
+    LockupFunding,
+    DraftGroupDeposits,
+    UpdateGuardians,
+    ApprovedCodeHashes,
+    LockupCreators,
+    DraftGroupCreators,
+    Liens,
+    LienApprovals,
+    LienHoldersWhitelist,
+    LockupCurves,
+    CliffEscrows,
+    AccountTerminatedLockups,
+    AccountRemaps,
+    Airdrops,
+    AirdropRegistrations,
+    LockupNotes,
+    ViewingKeys,
+    PrivacyViewersWhitelist,
+    LockupPools,
+    SignedActionNonces,
+    BlockedAccounts,
+    ClosedDraftGroups,
+    VestingOraclesWhitelist,
+    LockupVestingSchedules,
+    TagLockupIndices { tag_hash: CryptoHash },
+    LockupUnlockStarts,
+    ClaimStats,
+    PendingEmailLockups,
+    EventLogDisabled,
 }
 
 impl Contract {
@@ -97,12 +348,241 @@ impl Contract {
             "Only the manager can update the code"
         );
     }
-}
 
-#[near_bindgen]
-impl LockupApi for Contract {
-    #[init]
-    fn new(
+    /// The manager of the last initialization, kept even if the state is wiped.
+    pub(crate) fn internal_init_manager() -> Option<AccountId> {
+        env::storage_read(INIT_MANAGER_KEY).map(|raw| borsh::from_slice(&raw).expect("Failed to read init manager"))
+    }
+
+    pub(crate) fn internal_set_init_manager(manager: &AccountId) {
+        env::storage_write(INIT_MANAGER_KEY, &borsh::to_vec(manager).unwrap());
+    }
+
+    /// Whether a wiped state left lockups or drafts behind, the new collections would overwrite them.
+    fn internal_has_leftover_records() -> bool {
+        let mut first_lockup_key = StorageKey::Lockups.into_storage_key();
+        first_lockup_key.extend(0u64.to_le_bytes());
+        let mut first_draft_key = StorageKey::DraftsV2.into_storage_key();
+        first_draft_key.extend(borsh::to_vec(&DraftIndex::default()).unwrap());
+        env::storage_has_key(&first_lockup_key) || env::storage_has_key(&first_draft_key)
+    }
+
+    pub(crate) fn internal_add_to_deposit_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        for account_id in &account_ids {
+            self.deposit_whitelist.insert(account_id);
+        }
+        emit(EventKind::FtLockupAddToDepositWhitelist(
+            FtLockupAddToDepositWhitelist {
+                account_ids: account_ids.into_iter().map(Into::into).collect(),
+            },
+        ));
+    }
+
+    pub(crate) fn internal_remove_from_deposit_whitelist(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    ) {
+        self.assert_admin_reason(reason.as_deref());
+        for account_id in &account_ids {
+            self.deposit_whitelist.remove(account_id);
+        }
+        assert!(
+            !self.deposit_whitelist.is_empty(),
+            "cannot remove all accounts from deposit whitelist",
+        );
+        emit(EventKind::FtLockupRemoveFromDepositWhitelist(
+            FtLockupRemoveFromDepositWhitelist {
+                account_ids: account_ids.into_iter().map(Into::into).collect(),
+                reason,
+            },
+        ));
+    }
+
+    pub(crate) fn internal_add_to_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        for account_id in &account_ids {
+            self.draft_operators_whitelist.insert(account_id);
+        }
+        emit(EventKind::FtLockupAddToDraftOperatorsWhitelist(
+            FtLockupAddToDraftOperatorsWhitelist {
+                account_ids: account_ids.into_iter().map(Into::into).collect(),
+            },
+        ));
+    }
+
+    pub(crate) fn internal_remove_from_draft_operators_whitelist(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    ) {
+        self.assert_admin_reason(reason.as_deref());
+        for account_id in &account_ids {
+            self.draft_operators_whitelist.remove(account_id);
+        }
+        emit(EventKind::FtLockupRemoveFromDraftOperatorsWhitelist(
+            FtLockupRemoveFromDraftOperatorsWhitelist {
+                account_ids: account_ids.into_iter().map(Into::into).collect(),
+                reason,
+            },
+        ));
+    }
+
+    /// Terminates the lockup once the caller is authorized, `terminated_by` and `reason` are recorded in the termination.
+    pub(crate) fn internal_terminate(
+        &mut self,
+        lockup_index: LockupIndex,
+        termination_timestamp: Option<TimestampSec>,
+        expected_unvested: Option<WrappedBalance>,
+        terminated_by: AccountId,
+        reason: Option<String>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        self.assert_admin_reason(reason.as_deref());
+        self.assert_no_claim_in_flight(lockup_index);
+        self.assert_no_termination_in_flight(lockup_index);
+        self.assert_no_lien(lockup_index);
+        let lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        let current_timestamp = current_timestamp_sec();
+        let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
+        assert!(
+            termination_timestamp >= current_timestamp,
+            "expected termination_timestamp >= now",
+        );
+        if let Some((oracle_id, method)) = lockup.vesting_oracle() {
+            assert!(
+                self.vesting_oracles_whitelist.contains(oracle_id),
+                "{oracle_id} is not a whitelisted vesting oracle"
+            );
+            // claims and other terminations of the lockup wait for the vested balance
+            self.internal_start_termination(lockup_index);
+            let args = serde_json::to_vec(&json!({
+                "lockup_index": lockup_index,
+                "account_id": lockup.account_id,
+                "termination_timestamp": termination_timestamp,
+            }))
+            .expect("Failed to serialize arguments");
+            return Promise::new(oracle_id.clone())
+                .function_call(
+                    method.to_string(),
+                    args,
+                    NearToken::from_yoctonear(0),
+                    self.config.gas.vesting_oracle,
+                )
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(self.config.gas.after_vesting_oracle)
+                        .after_vesting_oracle(
+                            lockup_index,
+                            termination_timestamp,
+                            expected_unvested,
+                            terminated_by,
+                            reason,
+                        ),
+                )
+                .into();
+        }
+        let vested_balance = lockup.vested_balance(termination_timestamp);
+        self.internal_complete_termination(
+            lockup_index,
+            lockup,
+            termination_timestamp,
+            vested_balance,
+            expected_unvested,
+            terminated_by,
+            reason,
+        )
+    }
+
+    /// Terminates the lockup at the vested balance, transferring or burning the unvested balance.
+    pub(crate) fn internal_complete_termination(
+        &mut self,
+        lockup_index: LockupIndex,
+        mut lockup: Lockup,
+        termination_timestamp: TimestampSec,
+        vested_balance: Balance,
+        expected_unvested: Option<WrappedBalance>,
+        terminated_by: AccountId,
+        reason: Option<String>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        let termination_burn = self.internal_termination_burn(lockup_index);
+        let (unvested_balance, payer_id) = lockup.terminate(termination_timestamp, vested_balance);
+        let beneficiary_id = match &termination_burn {
+            Some(TerminationBurn::Address(burn_id)) => burn_id.clone(),
+            Some(TerminationBurn::FtBurn) => self.token_account_id.clone(),
+            None => payer_id.clone(),
+        };
+        if let Some(expected_unvested) = expected_unvested {
+            assert_unvested_balance_within_tolerance(unvested_balance, expected_unvested.0);
+        }
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.terminations.push(&TerminationRecord {
+            lockup_index,
+            termination_timestamp,
+            unvested_balance,
+            beneficiary_id: beneficiary_id.clone(),
+            terminated_by,
+            reason: reason.clone(),
+        });
+        self.terminated_lockups.insert(&lockup_index);
+
+        // no need to store empty lockup
+        if lockup.schedule.total_balance() == 0 {
+            self.internal_remove_terminated_lockup(&lockup.account_id, lockup_index);
+        }
+
+        let event = FtLockupTerminateLockup {
+            id: lockup_index,
+            account_id: lockup.account_id.clone(),
+            termination_timestamp,
+            unvested_balance: unvested_balance.into(),
+            beneficiary_id: beneficiary_id.clone(),
+            stage: self.internal_lockup_stage(lockup_index, &lockup, current_timestamp_sec()),
+            reason,
+        };
+        emit(EventKind::FtLockupTerminateLockup(vec![event]));
+
+        if unvested_balance > 0 {
+            self.internal_start_termination(lockup_index);
+            let memo = Some(
+                self.config
+                    .memo_templates
+                    .termination_memo(unvested_balance, lockup_index),
+            );
+            let token = Promise::new(self.token_account_id.clone());
+            match termination_burn {
+                Some(termination_burn) => {
+                    let burn = match termination_burn {
+                        TerminationBurn::Address(burn_id) => {
+                            token.ft_transfer(&burn_id, unvested_balance, memo, self.config.gas.ft_transfer)
+                        }
+                        TerminationBurn::FtBurn => token.ft_burn(unvested_balance, memo, self.config.gas.ft_transfer),
+                    };
+                    burn.then(
+                        ext_self::ext(env::current_account_id())
+                            .with_static_gas(self.config.gas.after_ft_transfer)
+                            .after_termination_burn(payer_id, unvested_balance.into(), lockup_index),
+                    )
+                    .into()
+                }
+                None => token
+                    .ft_transfer(
+                        &beneficiary_id.clone(),
+                        unvested_balance,
+                        memo,
+                        self.config.gas.ft_transfer,
+                    )
+                    .then(
+                        ext_self::ext(env::current_account_id())
+                            .with_static_gas(self.config.gas.after_ft_transfer)
+                            .after_lockup_termination(beneficiary_id, unvested_balance.into(), Some(lockup_index)),
+                    )
+                    .into(),
+            }
+        } else {
+            PromiseOrValue::Value(0.into())
+        }
+    }
+
+    fn internal_init(
         token_account_id: AccountId,
         deposit_whitelist: Vec<AccountId>,
         draft_operators_whitelist: Option<Vec<AccountId>>,
@@ -135,57 +615,208 @@ impl LockupApi for Contract {
                     .collect(),
             },
         ));
-        Self {
+        let contract = Self {
             lockups: Vector::new(StorageKey::Lockups),
             account_lockups: LookupMap::new(StorageKey::AccountLockups),
+            pending_lockups: LookupMap::new(StorageKey::PendingLockups),
+            pending_email_lockups: LookupMap::new(StorageKey::PendingEmailLockups),
+            lockup_tags: LookupMap::new(StorageKey::LockupTags),
+            tag_lockups: LookupMap::new(StorageKey::TagLockups),
+            grants: Vector::new(StorageKey::Grants),
+            lockup_grants: LookupMap::new(StorageKey::LockupGrants),
+            unclaimed_revocations: LookupMap::new(StorageKey::UnclaimedRevocations),
+            lockup_agreements: LookupMap::new(StorageKey::LockupAgreements),
+            lockup_creators: LookupMap::new(StorageKey::LockupCreators),
+            draft_group_creators: LookupMap::new(StorageKey::DraftGroupCreators),
+            liens: LookupMap::new(StorageKey::Liens),
+            lien_approvals: LookupMap::new(StorageKey::LienApprovals),
+            lien_holders_whitelist: UnorderedSet::new(StorageKey::LienHoldersWhitelist),
+            lockup_notes: LookupMap::new(StorageKey::LockupNotes),
+            viewing_keys: LookupMap::new(StorageKey::ViewingKeys),
+            privacy_viewers_whitelist: UnorderedSet::new(StorageKey::PrivacyViewersWhitelist),
+            vesting_oracles_whitelist: UnorderedSet::new(StorageKey::VestingOraclesWhitelist),
+            lockup_pools: UnorderedMap::new(StorageKey::LockupPools),
+            manager_signing_key: None,
+            signed_action_nonces: LookupMap::new(StorageKey::SignedActionNonces),
+            keeper_incentives: LookupMap::new(StorageKey::KeeperIncentives),
+            payout_addresses: LookupMap::new(StorageKey::PayoutAddresses),
+            claims_in_flight: LookupMap::new(StorageKey::ClaimsInFlight),
+            terminations_in_flight: LookupSet::new(StorageKey::TerminationsInFlight),
+            terminated_lockups: LookupSet::new(StorageKey::TerminatedLockups),
+            account_terminated_lockups: LookupMap::new(StorageKey::AccountTerminatedLockups),
+            account_remaps: LookupMap::new(StorageKey::AccountRemaps),
+            schedule_extension: None,
+            airdrops: Vector::new(StorageKey::Airdrops),
+            airdrop_registrations: LookupSet::new(StorageKey::AirdropRegistrations),
+            lockups_merkle_computation: None,
+            lockups_merkle_root: None,
+            burn_on_termination: LookupSet::new(StorageKey::BurnOnTermination),
+            lockup_curves: LookupMap::new(StorageKey::LockupCurves),
+            lockup_vesting_schedules: LookupMap::new(StorageKey::LockupVestingSchedules),
+            lockup_unlock_starts: LookupMap::new(StorageKey::LockupUnlockStarts),
+            claim_stats: LookupMap::new(StorageKey::ClaimStats),
+            cliff_escrows: LookupMap::new(StorageKey::CliffEscrows),
+            draft_group_creations: LookupMap::new(StorageKey::DraftGroupCreations),
             token_account_id,
             deposit_whitelist: deposit_whitelist_set,
             draft_operators_whitelist: draft_operators_whitelist_set,
+            payer_whitelist: UnorderedSet::new(StorageKey::PayerWhitelist),
+            attested_accounts: LookupSet::new(StorageKey::AttestedAccounts),
+            blocked_accounts: LookupSet::new(StorageKey::BlockedAccounts),
             next_draft_id: 0,
-            drafts: LookupMap::new(StorageKey::Drafts),
+            drafts: LookupMap::new(StorageKey::DraftsV2),
+            num_drafts: 0,
+            drafts_pending_account: UnorderedSet::new(StorageKey::DraftsPendingAccount),
             next_draft_group_id: 0,
-            draft_groups: UnorderedMap::new(StorageKey::DraftGroups),
+            draft_groups: UnorderedMap::new(StorageKey::DraftGroupsV2),
+            closed_draft_groups: LookupMap::new(StorageKey::ClosedDraftGroups),
+            legacy_draft_groups: UnorderedMap::new(StorageKey::DraftGroups),
+            legacy_drafts: LookupMap::new(StorageKey::Drafts),
             manager,
-        }
+            multisig: None,
+            update_guardians: UnorderedSet::new(StorageKey::UpdateGuardians),
+            approved_code_hashes: UnorderedSet::new(StorageKey::ApprovedCodeHashes),
+            terminations: Vector::new(StorageKey::Terminations),
+            deposits: Vector::new(StorageKey::Deposits),
+            lockup_funding: LookupMap::new(StorageKey::LockupFunding),
+            draft_group_deposits: LookupMap::new(StorageKey::DraftGroupDeposits),
+            token_decimals: None,
+            token_flows: TokenFlows::default(),
+            config: Config::default(),
+            claim_quota: ClaimQuota::default(),
+        };
+        Self::internal_set_init_manager(&contract.manager);
+        contract.internal_refresh_token_metadata();
+
+        contract
+    }
+}
+
+#[near_bindgen]
+impl LockupApi for Contract {
+    #[init]
+    fn new(
+        token_account_id: AccountId,
+        deposit_whitelist: Vec<AccountId>,
+        draft_operators_whitelist: Option<Vec<AccountId>>,
+        manager: AccountId,
+    ) -> Self {
+        // `#[init]` only checks the state in the exported method, the checks also hold for direct calls
+        assert!(!env::state_exists(), "The contract is already initialized");
+        assert!(
+            Self::internal_init_manager().is_none(),
+            "The contract state was wiped, the manager can initialize it with reinit_after_state_wipe"
+        );
+        Self::internal_init(token_account_id, deposit_whitelist, draft_operators_whitelist, manager)
+    }
+
+    #[init]
+    fn reinit_after_state_wipe(
+        token_account_id: AccountId,
+        deposit_whitelist: Vec<AccountId>,
+        draft_operators_whitelist: Option<Vec<AccountId>>,
+    ) -> Self {
+        assert!(!env::state_exists(), "The contract is already initialized");
+        let manager = Self::internal_init_manager().expect("The contract was never initialized, use new");
+        assert_eq!(
+            env::predecessor_account_id(),
+            manager,
+            "Only the manager can reinitialize the contract"
+        );
+        assert!(
+            !Self::internal_has_leftover_records(),
+            "The wiped state left lockups or drafts, they would be overwritten"
+        );
+        Self::internal_init(token_account_id, deposit_whitelist, draft_operators_whitelist, manager)
     }
 
-    fn claim(&mut self, amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>) -> PromiseOrValue<WrappedBalance> {
+    fn claim(
+        &mut self,
+        amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+        memo: Option<String>,
+        max_amounts: Option<bool>,
+    ) -> PromiseOrValue<ClaimResult> {
+        self.assert_claims_not_paused();
+        let mut meter = GasMeter::start();
         let account_id = env::predecessor_account_id();
+        self.assert_attested(&account_id);
+        if let Some(memo) = &memo {
+            validate_claim_memo(memo).unwrap_or_else(|error| panic!("{error}"));
+        }
 
-        let (claim_amounts, mut lockups_by_id) = if let Some(amounts) = amounts {
+        let mut clamped_lockups = vec![];
+        let mut in_flight_lockups = vec![];
+        let (claim_amounts, mut lockups_by_id, has_more) = if let Some(amounts) = amounts {
             let lockups_by_id: HashMap<LockupIndex, Lockup> = self
                 .internal_get_account_lockups_by_id(&account_id, &amounts.iter().map(|x| x.0).collect())
                 .into_iter()
                 .collect();
-            let amounts: HashMap<LockupIndex, WrappedBalance> = amounts
+            meter.phase("storage read");
+            assert!(
+                lockups_by_id.len() <= max_claimed_lockups(&self.config.gas),
+                "Not enough gas to claim {} lockups",
+                lockups_by_id.len()
+            );
+            let amounts: BTreeMap<LockupIndex, WrappedBalance> = amounts
                 .into_iter()
                 .map(|(lockup_id, amount)| {
-                    (
-                        lockup_id,
-                        if let Some(amount) = amount {
+                    let amount = match amount {
+                        // the claimable balance may have grown since the amount was computed, but not shrunk
+                        Some(amount) if max_amounts.unwrap_or(false) => {
+                            let lockup = lockups_by_id.get(&lockup_id).expect("lockup not found");
+                            let claimable_balance = self.internal_claimable_balance(lockup_id, lockup);
+                            if amount.0 > claimable_balance {
+                                clamped_lockups.push((lockup_id, amount));
+                            }
+                            std::cmp::min(amount.0, claimable_balance).into()
+                        }
+                        Some(amount) => {
+                            assert!(
+                                !self.cliff_escrows.contains_key(&lockup_id),
+                                "lockup {lockup_id} awaits the sweep to its cliff escrow"
+                            );
+                            if let Some(lien) = self.liens.get(&lockup_id) {
+                                let lockup = lockups_by_id.get(&lockup_id).expect("lockup not found");
+                                assert!(
+                                    amount.0 <= self.internal_owner_claimable(&lien, lockup),
+                                    "claim amount exceeds the balance not reserved by the lien of lockup {lockup_id}"
+                                );
+                            }
                             amount
-                        } else {
+                        }
+                        None => {
                             let lockup = lockups_by_id.get(&lockup_id).expect("lockup not found");
-                            let unlocked_balance = lockup.schedule.unlocked_balance(current_timestamp_sec());
-                            (unlocked_balance - lockup.claimed_balance).into()
-                        },
-                    )
+                            self.internal_claimable_balance(lockup_id, lockup).into()
+                        }
+                    };
+                    (lockup_id, amount)
                 })
                 .collect();
-            (amounts, lockups_by_id)
+            (amounts, lockups_by_id, false)
         } else {
-            let lockups_by_id: HashMap<LockupIndex, Lockup> =
-                self.internal_get_account_lockups(&account_id).into_iter().collect();
-            let amounts: HashMap<LockupIndex, WrappedBalance> = lockups_by_id
-                .iter()
-                .map(|(lockup_id, lockup)| {
-                    let unlocked_balance = lockup.schedule.unlocked_balance(current_timestamp_sec());
-                    let amount: WrappedBalance = (unlocked_balance - lockup.claimed_balance).into();
-
-                    (*lockup_id, amount)
-                })
+            let (mut claimable_lockups, skipped_lockups): (Vec<_>, Vec<_>) = self
+                .internal_get_claimable_account_lockups(&account_id)
+                .into_iter()
+                .partition(|(lockup_index, _, _)| !self.internal_is_in_flight(*lockup_index));
+            in_flight_lockups = skipped_lockups
+                .into_iter()
+                .map(|(lockup_index, _, _)| lockup_index)
                 .collect();
-            (amounts, lockups_by_id)
+            meter.phase("storage read");
+            // claiming the largest amounts first if not all lockups fit into a single call
+            claimable_lockups.sort_unstable_by(|(a_index, a_amount, _), (b_index, b_amount, _)| {
+                b_amount.cmp(a_amount).then(a_index.cmp(b_index))
+            });
+            let max_lockups = max_claimed_lockups(&self.config.gas);
+            let has_more = claimable_lockups.len() > max_lockups;
+            claimable_lockups.truncate(max_lockups);
+            let mut amounts = BTreeMap::new();
+            let mut lockups_by_id = HashMap::with_capacity(claimable_lockups.len());
+            for (lockup_index, amount, lockup) in claimable_lockups {
+                amounts.insert(lockup_index, WrappedBalance::from(amount));
+                lockups_by_id.insert(lockup_index, lockup);
+            }
+            (amounts, lockups_by_id, has_more)
         };
 
         let account_id = env::predecessor_account_id();
@@ -198,158 +829,1138 @@ impl LockupApi for Contract {
             if lockup_claim.claim_amount.0 > 0 {
                 log!("Claiming {} form lockup #{}", lockup_claim.claim_amount.0, lockup_index);
                 total_claim_amount += lockup_claim.claim_amount.0;
-                self.lockups.replace(u64::from(lockup_index), lockup);
                 lockup_claims.push(lockup_claim);
             }
         }
         log!("Total claim {}", total_claim_amount);
+        self.internal_consume_claim_quota(total_claim_amount);
+        meter.phase("schedule math");
+
+        for lockup_claim in &lockup_claims {
+            self.lockups
+                .replace(u64::from(lockup_claim.index), &lockups_by_id[&lockup_claim.index]);
+            self.internal_record_claim(lockup_claim.index);
+            self.internal_start_claim(lockup_claim.index, lockup_claim.claim_amount.0);
+        }
+        meter.phase("serialization");
 
         if total_claim_amount > 0 {
-            Promise::new(self.token_account_id.clone())
-                .ft_transfer(
-                    &account_id,
-                    total_claim_amount,
-                    Some(format!(
-                        "Claiming unlocked {} balance from {}",
-                        total_claim_amount,
-                        env::current_account_id()
-                    )),
-                )
+            let callback_gas = self.config.gas.after_ft_transfer_gas(lockup_claims.len());
+            self.internal_charge_claim_gas(&lockup_claims, callback_gas);
+            let claim_fee = self.internal_claim_fee();
+            let claim_fee_amount: Balance = claim_fee.as_ref().map_or(0, |claim_fee| {
+                lockup_claims
+                    .iter()
+                    .map(|lockup_claim| claim_fee.fee(lockup_claim.claim_amount.0))
+                    .sum()
+            });
+            let transfer = Promise::new(self.token_account_id.clone()).ft_transfer(
+                &self.internal_payout_id(&account_id),
+                total_claim_amount - claim_fee_amount,
+                Some(memo.unwrap_or_else(|| {
+                    self.config
+                        .memo_templates
+                        .claim_memo(total_claim_amount, &env::current_account_id())
+                })),
+                self.config.gas.ft_transfer,
+            );
+            let transfer = match &claim_fee {
+                Some(claim_fee) if claim_fee_amount > 0 => {
+                    transfer.and(Promise::new(self.token_account_id.clone()).ft_transfer(
+                        &claim_fee.collector_id,
+                        claim_fee_amount,
+                        Some(self.config.memo_templates.claim_fee_memo(claim_fee_amount, &account_id)),
+                        self.config.gas.ft_transfer,
+                    ))
+                }
+                _ => transfer,
+            };
+            transfer
                 .then(
                     ext_self::ext(env::current_account_id())
-                        .with_static_gas(GAS_FOR_AFTER_FT_TRANSFER)
-                        .after_ft_transfer(account_id, lockup_claims),
+                        .with_static_gas(callback_gas)
+                        .after_ft_transfer(
+                            account_id,
+                            lockup_claims,
+                            has_more,
+                            claim_fee,
+                            Some(clamped_lockups),
+                            Some(in_flight_lockups),
+                        ),
                 )
                 .into()
         } else {
-            PromiseOrValue::Value(0.into())
+            PromiseOrValue::Value(ClaimResult {
+                claimed_balance: 0.into(),
+                has_more,
+                claimed_lockups: vec![],
+                clamped_lockups,
+                in_flight_lockups,
+                escrowed_lockups: vec![],
+            })
         }
     }
 
+    fn claim_amount(&mut self, total: WrappedBalance, memo: Option<String>) -> PromiseOrValue<ClaimResult> {
+        assert!(total.0 > 0, "claim amount should be positive");
+        let account_id = env::predecessor_account_id();
+        let claimable_lockups = self
+            .internal_get_claimable_account_lockups(&account_id)
+            .into_iter()
+            .filter(|(lockup_index, _, _)| !self.internal_is_in_flight(*lockup_index))
+            .map(|(lockup_index, amount, lockup)| (lockup_index, amount, lockup.schedule.0[0].timestamp))
+            .collect();
+        let amounts = allocate_claim_amount(claimable_lockups, total.0).unwrap_or_else(|error| panic!("{error}"));
+        let amounts = amounts
+            .into_iter()
+            .map(|(lockup_index, amount)| (lockup_index, Some(amount.into())))
+            .collect();
+        self.claim(Some(amounts), memo, None)
+    }
+
+    #[payable]
+    fn claim_for_keeper(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance> {
+        self.assert_claims_not_paused();
+        let keeper_id = env::predecessor_account_id();
+        let keeper_incentive = self
+            .keeper_incentives
+            .get(&lockup_index)
+            .expect("lockup has no keeper incentive");
+        assert!(
+            !self.internal_is_pending(lockup_index),
+            "pending lockup can't be claimed by a keeper"
+        );
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        let account_id = lockup.account_id.clone();
+        self.assert_attested(&account_id);
+        let claim_amount = self.internal_claimable_balance(lockup_index, &lockup);
+        assert!(
+            claim_amount > 0 && claim_amount >= keeper_incentive.min_claim_amount.0,
+            "claimable balance is below the keeper threshold"
+        );
+        let keeper_fee = keeper_incentive.fee(claim_amount);
+        let claim_fee = self.internal_claim_fee();
+        let claim_fee_amount = claim_fee.as_ref().map_or(0, |claim_fee| claim_fee.fee(claim_amount));
+        let owner_amount = claim_amount.saturating_sub(keeper_fee).saturating_sub(claim_fee_amount);
+        assert!(
+            owner_amount > 0,
+            "claimable balance doesn't cover the keeper and claim fees"
+        );
+        self.internal_consume_claim_quota(claim_amount);
+        // not recording the claim for unclaimed revocations, a keeper claim doesn't prove the owner is active
+        let lockup_claim = lockup.claim(lockup_index, claim_amount);
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.internal_start_claim(lockup_index, claim_amount);
+        self.internal_charge_claim_gas(
+            std::slice::from_ref(&lockup_claim),
+            self.config.gas.after_ft_transfer_gas(1),
+        );
+
+        // the owner receives the claim net of the fees
+        let memo = self
+            .config
+            .memo_templates
+            .claim_memo(owner_amount, &env::current_account_id());
+        let transfer = Promise::new(self.token_account_id.clone()).ft_transfer(
+            &self.internal_payout_id(&account_id),
+            owner_amount,
+            Some(memo),
+            self.config.gas.ft_transfer,
+        );
+        let transfer = if keeper_fee > 0 {
+            transfer.and(Promise::new(self.token_account_id.clone()).ft_transfer(
+                &keeper_id,
+                keeper_fee,
+                Some(self.config.memo_templates.keeper_fee_memo(keeper_fee, lockup_index)),
+                self.config.gas.ft_transfer,
+            ))
+        } else {
+            transfer
+        };
+        let transfer = match &claim_fee {
+            Some(claim_fee) if claim_fee_amount > 0 => {
+                transfer.and(Promise::new(self.token_account_id.clone()).ft_transfer(
+                    &claim_fee.collector_id,
+                    claim_fee_amount,
+                    Some(self.config.memo_templates.claim_fee_memo(claim_fee_amount, &account_id)),
+                    self.config.gas.ft_transfer,
+                ))
+            }
+            _ => transfer,
+        };
+        transfer
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer_gas(1))
+                    .after_keeper_claim(account_id, lockup_claim, keeper_id, keeper_fee.into(), claim_fee),
+            )
+            .into()
+    }
+
+    #[payable]
+    fn set_payout_address(&mut self, payout_id: Option<AccountId>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut payout_address = self.payout_addresses.get(&account_id).unwrap_or_default();
+        let effective_at = payout_address.change(payout_id.clone(), current_timestamp_sec());
+        self.payout_addresses.insert(&account_id, &payout_address);
+
+        emit(EventKind::FtLockupChangePayoutAddress(FtLockupChangePayoutAddress {
+            account_id,
+            payout_id,
+            effective_at,
+        }));
+    }
+
+    #[payable]
+    fn cancel_payout_address_change(&mut self) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut payout_address = self
+            .payout_addresses
+            .get(&account_id)
+            .expect("no pending payout address change");
+        payout_address.cancel_change(current_timestamp_sec());
+        self.payout_addresses.insert(&account_id, &payout_address);
+
+        emit(EventKind::FtLockupCancelPayoutAddressChange(
+            FtLockupCancelPayoutAddressChange { account_id },
+        ));
+    }
+
     #[payable]
     fn terminate(
         &mut self,
         lockup_index: LockupIndex,
         termination_timestamp: Option<TimestampSec>,
+        expected_unvested: Option<WrappedBalance>,
+        reason: Option<String>,
     ) -> PromiseOrValue<WrappedBalance> {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        let mut lockup = self.lockups.get(u64::from(lockup_index)).expect("Lockup not found");
-        let current_timestamp = current_timestamp_sec();
-        let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
-        assert!(
-            termination_timestamp >= current_timestamp,
-            "expected termination_timestamp >= now",
-        );
-        let (unvested_balance, beneficiary_id) = lockup.terminate(termination_timestamp);
+        self.assert_terminations_not_paused();
+        self.assert_can_terminate(lockup_index);
+        self.internal_terminate(
+            lockup_index,
+            termination_timestamp,
+            expected_unvested,
+            env::predecessor_account_id(),
+            reason,
+        )
+    }
+
+    #[payable]
+    fn reveal_vesting_schedule(&mut self, lockup_index: LockupIndex, schedule: Schedule) {
+        assert_one_yocto();
+        self.assert_no_termination_in_flight(lockup_index);
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        lockup
+            .reveal_vesting_schedule(schedule)
+            .unwrap_or_else(|error| panic!("{error}"));
         self.lockups.replace(u64::from(lockup_index), &lockup);
+    }
 
-        // no need to store empty lockup
-        if lockup.schedule.total_balance() == 0 {
-            let mut indices = self.account_lockups.get(&lockup.account_id).unwrap_or_default();
-            indices.remove(&lockup_index);
-            self.internal_save_account_lockups(&lockup.account_id, indices);
-        }
+    #[payable]
+    fn warn_unclaimed_revocation(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        self.assert_terminations_not_paused();
+        self.assert_can_terminate(lockup_index);
+        let lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        assert!(!lockup.is_fully_claimed(), "lockup is fully claimed");
+        let mut revocation = self
+            .unclaimed_revocations
+            .get(&lockup_index)
+            .expect("lockup is not revocable");
+        let unlock_finish_timestamp = lockup.unlock_finish_timestamp();
+        revocation.warn(current_timestamp_sec(), unlock_finish_timestamp);
+        self.unclaimed_revocations.insert(&lockup_index, &revocation);
 
-        let event = FtLockupTerminateLockup {
-            id: lockup_index,
-            termination_timestamp,
-            unvested_balance: unvested_balance.into(),
-        };
-        emit(EventKind::FtLockupTerminateLockup(vec![event]));
+        emit(EventKind::FtLockupWarnUnclaimedRevocation(vec![
+            FtLockupWarnUnclaimedRevocation {
+                id: lockup_index,
+                account_id: lockup.account_id,
+                revocable_at: revocation.revocable_at(unlock_finish_timestamp).unwrap(),
+            },
+        ]));
+    }
 
-        if unvested_balance > 0 {
-            Promise::new(self.token_account_id.clone())
-                .ft_transfer(
-                    &beneficiary_id.clone(),
-                    unvested_balance,
-                    Some(format!("Terminated lockup #{lockup_index}")),
-                )
-                .then(
-                    ext_self::ext(env::current_account_id())
-                        .with_static_gas(GAS_FOR_AFTER_FT_TRANSFER)
-                        .after_lockup_termination(beneficiary_id, unvested_balance.into()),
-                )
-                .into()
+    #[payable]
+    fn revoke_unclaimed(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.assert_terminations_not_paused();
+        self.assert_can_terminate(lockup_index);
+        self.assert_no_lien(lockup_index);
+        self.internal_start_termination(lockup_index);
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        let revocation = self
+            .unclaimed_revocations
+            .get(&lockup_index)
+            .expect("lockup is not revocable");
+        let current_timestamp = current_timestamp_sec();
+        revocation.assert_revocable(current_timestamp, lockup.unlock_finish_timestamp());
+
+        let revoked_balance = lockup.revoke_unclaimed(current_timestamp);
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.unclaimed_revocations.remove(&lockup_index);
+        self.terminated_lockups.insert(&lockup_index);
+        self.internal_remove_terminated_lockup(&lockup.account_id, lockup_index);
+
+        emit(EventKind::FtLockupRevokeUnclaimed(vec![FtLockupRevokeUnclaimed {
+            id: lockup_index,
+            account_id: lockup.account_id.clone(),
+            amount: revoked_balance.into(),
+            beneficiary_id: revocation.beneficiary_id.clone(),
+        }]));
+
+        Promise::new(self.token_account_id.clone())
+            .ft_transfer(
+                &revocation.beneficiary_id,
+                revoked_balance,
+                Some(
+                    self.config
+                        .memo_templates
+                        .revocation_memo(revoked_balance, lockup_index),
+                ),
+                self.config.gas.ft_transfer,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer)
+                    .after_unclaimed_revocation(revocation.beneficiary_id, revoked_balance.into(), lockup_index),
+            )
+            .into()
+    }
+
+    #[payable]
+    fn renounce_lockup(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.assert_terminations_not_paused();
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        assert_eq!(
+            lockup.account_id,
+            env::predecessor_account_id(),
+            "Only the lockup owner can renounce it"
+        );
+        self.assert_no_lien(lockup_index);
+        self.internal_start_termination(lockup_index);
+        let beneficiary_id = lockup
+            .termination_config
+            .as_ref()
+            .map(|termination_config| termination_config.beneficiary_id.clone())
+            .or_else(|| self.lockup_creators.get(&lockup_index))
+            .expect("lockup has no beneficiary");
+
+        let forfeited_balance = lockup.renounce(current_timestamp_sec());
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.lockup_curves.remove(&lockup_index);
+        self.unclaimed_revocations.remove(&lockup_index);
+        self.terminated_lockups.insert(&lockup_index);
+        self.internal_remove_terminated_lockup(&lockup.account_id, lockup_index);
+
+        emit(EventKind::FtLockupRenounceLockup(vec![FtLockupRenounceLockup {
+            id: lockup_index,
+            account_id: lockup.account_id.clone(),
+            amount: forfeited_balance.into(),
+            beneficiary_id: beneficiary_id.clone(),
+        }]));
+
+        Promise::new(self.token_account_id.clone())
+            .ft_transfer(
+                &beneficiary_id,
+                forfeited_balance,
+                Some(
+                    self.config
+                        .memo_templates
+                        .renunciation_memo(forfeited_balance, lockup_index),
+                ),
+                self.config.gas.ft_transfer,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer)
+                    .after_lockup_termination(beneficiary_id, forfeited_balance.into(), Some(lockup_index)),
+            )
+            .into()
+    }
+
+    fn claim_pending_lockup(&mut self, proof: PendingLockupProof) {
+        let account_id = env::predecessor_account_id();
+        self.assert_not_blocked(&account_id);
+        let lockup_index = proof.lockup_index;
+        let owner_key = match self.pending_lockups.get(&lockup_index) {
+            Some(owner_key) => owner_key,
+            None => {
+                assert!(
+                    self.pending_email_lockups.contains_key(&lockup_index),
+                    "pending lockup not found"
+                );
+                self.config
+                    .email_attestor_key
+                    .clone()
+                    .expect("email attestor key is not set")
+            }
+        };
+
+        let signature: [u8; 64] = proof.signature.0.try_into().expect("invalid signature length");
+        let public_key: [u8; 32] = owner_key.as_bytes()[1..].try_into().unwrap();
+        let message = pending_lockup_message(&env::current_account_id(), lockup_index, &account_id);
+        assert!(
+            env::ed25519_verify(&signature, &message, &public_key),
+            "invalid signature"
+        );
+
+        let mut lockup = self.internal_get_lockup(lockup_index).unwrap();
+        let mut indices = self.account_lockups.get(&lockup.account_id).unwrap_or_default();
+        indices.remove(&lockup_index);
+        self.internal_save_account_lockups(&lockup.account_id, indices);
+
+        lockup.account_id = account_id.clone();
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
+        indices.insert(lockup_index);
+        self.internal_save_account_lockups(&account_id, indices);
+        self.pending_lockups.remove(&lockup_index);
+        self.pending_email_lockups.remove(&lockup_index);
+
+        emit(EventKind::FtLockupClaimPendingLockup(vec![
+            FtLockupClaimPendingLockup {
+                id: lockup_index,
+                account_id,
+            },
+        ]));
+    }
+
+    #[payable]
+    fn relock(&mut self, lockup_index: LockupIndex, extend_by_sec: TimestampSec) {
+        assert_one_yocto();
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        assert_eq!(
+            lockup.account_id,
+            env::predecessor_account_id(),
+            "Only the lockup owner can relock it"
+        );
+        let (old_finish, new_finish) = lockup.relock(extend_by_sec);
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+
+        emit(EventKind::FtLockupRelockLockup(vec![FtLockupRelockLockup {
+            id: lockup_index,
+            old_finish,
+            new_finish,
+        }]));
+    }
+
+    #[payable]
+    fn set_lockup_note(&mut self, lockup_index: LockupIndex, note: String) {
+        assert_one_yocto();
+        let edited_by = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&edited_by);
+        validate_note(&note).unwrap_or_else(|error| panic!("{error}"));
+        assert!(u64::from(lockup_index) < self.lockups.len(), "Lockup not found");
+
+        let mut history = self.lockup_notes.get(&lockup_index).unwrap_or_default();
+        push_note(
+            &mut history,
+            LockupNote {
+                note,
+                edited_by,
+                edited_at: current_timestamp_sec(),
+            },
+        );
+        // notes may hold internal references, so they're deliberately not emitted
+        self.lockup_notes.insert(&lockup_index, &history);
+    }
+
+    fn refresh_token_metadata(&mut self) -> PromiseOrValue<Option<u8>> {
+        self.internal_refresh_token_metadata().into()
+    }
+
+    #[payable]
+    fn reindex_account_lockups(&mut self, from_index: LockupIndex, limit: u32) -> Option<LockupIndex> {
+        assert_one_yocto();
+        self.assert_manager();
+
+        let num_lockups = LockupIndex::try_from(self.lockups.len()).unwrap();
+        let to_index = std::cmp::min(num_lockups, from_index.saturating_add(limit));
+        for index in from_index..to_index {
+            let lockup = self.internal_get_lockup(index).unwrap();
+            let mut indices = self.account_lockups.get(&lockup.account_id).unwrap_or_default();
+            let modified = if lockup.is_fully_claimed() {
+                indices.remove(&index)
+            } else {
+                indices.insert(index)
+            };
+            if modified {
+                log!(
+                    "Fixed account lockups index of lockup #{} for {}",
+                    index,
+                    lockup.account_id
+                );
+                self.internal_save_account_lockups(&lockup.account_id, indices);
+            }
+        }
+
+        (to_index < num_lockups).then_some(to_index)
+    }
+
+    #[payable]
+    fn set_event_batch_threshold(&mut self, event_batch_threshold: Option<u32>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.event_batch_threshold = event_batch_threshold;
+    }
+
+    #[payable]
+    fn set_event_log_enabled(&mut self, enabled: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        EventLog::new().set_enabled(enabled);
+    }
+
+    #[payable]
+    fn set_account_factory(&mut self, account_factory_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.account_factory_id = account_factory_id;
+    }
+
+    #[payable]
+    fn set_claim_escrow(&mut self, claim_escrow_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        assert_ne!(
+            claim_escrow_id.as_ref(),
+            Some(&env::current_account_id()),
+            "claim escrow can't be the lockup contract"
+        );
+        self.config.claim_escrow_id = claim_escrow_id;
+    }
+
+    #[payable]
+    fn set_ft_on_transfer_policy(&mut self, ft_on_transfer_policy: FtOnTransferPolicy) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.ft_on_transfer_policy = ft_on_transfer_policy;
+    }
+
+    #[payable]
+    fn set_termination_policy(&mut self, termination_policy: TerminationPolicy) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.termination_policy = termination_policy;
+    }
+
+    #[payable]
+    fn set_draft_group_approval_threshold(&mut self, draft_group_approval_threshold: Option<WrappedBalance>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.draft_group_approval_threshold = draft_group_approval_threshold;
+    }
+
+    #[payable]
+    fn pause_drafts(&mut self, paused: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.pause_flags.drafts = paused;
+        self.internal_emit_pause_flags();
+    }
+
+    #[payable]
+    fn pause_claims(&mut self, paused: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.pause_flags.claims = paused;
+        self.internal_emit_pause_flags();
+    }
+
+    #[payable]
+    fn pause_terminations(&mut self, paused: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.pause_flags.terminations = paused;
+        self.internal_emit_pause_flags();
+    }
+
+    #[payable]
+    fn set_gas_config(&mut self, gas_config: GasConfig) {
+        assert_one_yocto();
+        self.assert_manager();
+        gas_config.assert_valid();
+        self.config.gas = gas_config;
+    }
+
+    #[payable]
+    fn set_max_managed_balance(&mut self, max_managed_balance: Option<WrappedBalance>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.max_managed_balance = max_managed_balance;
+    }
+
+    #[payable]
+    fn set_anonymize_upcoming_unlocks(&mut self, anonymize: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.anonymize_upcoming_unlocks = anonymize;
+    }
+
+    #[payable]
+    fn set_privacy_mode(&mut self, enabled: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.privacy_mode = enabled;
+    }
+
+    #[payable]
+    fn set_max_unlocked_at_creation(&mut self, max_unlocked_bps: Option<u16>) {
+        assert_one_yocto();
+        self.assert_manager();
+        if let Some(max_unlocked_bps) = max_unlocked_bps {
+            assert!(
+                max_unlocked_bps <= 10_000,
+                "max unlocked share should be at most 10000 bps"
+            );
+        }
+        self.config.max_unlocked_at_creation_bps = max_unlocked_bps;
+    }
+
+    #[payable]
+    fn set_require_admin_reason(&mut self, required: bool) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.require_admin_reason = required;
+    }
+
+    #[payable]
+    fn set_draft_group_conversion_deadline(&mut self, conversion_deadline_sec: Option<TimestampSec>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.draft_group_conversion_deadline_sec = conversion_deadline_sec;
+    }
+
+    #[payable]
+    fn set_max_draft_groups_per_day(&mut self, max_draft_groups_per_day: Option<u32>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.max_draft_groups_per_day = max_draft_groups_per_day;
+    }
+
+    #[payable]
+    fn set_memo_templates(&mut self, memo_templates: MemoTemplates) {
+        assert_one_yocto();
+        self.assert_manager();
+        memo_templates.validate().unwrap_or_else(|error| panic!("{error}"));
+        self.config.memo_templates = memo_templates;
+    }
+
+    #[payable]
+    fn set_termination_burn(&mut self, termination_burn: Option<TerminationBurn>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.termination_burn = termination_burn;
+    }
+
+    #[payable]
+    fn set_claim_fee(&mut self, claim_fee: Option<ClaimFee>) {
+        assert_one_yocto();
+        self.assert_manager();
+        let effective_at = self
+            .config
+            .fee_config
+            .change(claim_fee.clone(), current_timestamp_sec());
+
+        emit(EventKind::FtLockupChangeClaimFee(FtLockupChangeClaimFee {
+            claim_fee,
+            effective_at,
+        }));
+    }
+
+    #[payable]
+    fn cancel_claim_fee_change(&mut self) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.fee_config.cancel_change(current_timestamp_sec());
+
+        emit(EventKind::FtLockupCancelClaimFeeChange(FtLockupCancelClaimFeeChange {}));
+    }
+
+    #[payable]
+    fn schedule_account_remap(&mut self, old_account_id: AccountId, new_account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_manager();
+        assert_ne!(
+            old_account_id, new_account_id,
+            "the new account should differ from the old one"
+        );
+        // pending lockups are held by the contract account until claimed with the owner key
+        assert_ne!(
+            old_account_id,
+            env::current_account_id(),
+            "lockups of the contract account can't be remapped"
+        );
+        self.assert_not_blocked(&new_account_id);
+        let remap = AccountRemap::new(new_account_id.clone(), current_timestamp_sec());
+        self.account_remaps.insert(&old_account_id, &remap);
+
+        emit(EventKind::FtLockupScheduleAccountRemap(FtLockupScheduleAccountRemap {
+            old_account_id,
+            new_account_id,
+            executable_at: remap.executable_at,
+        }));
+    }
+
+    #[payable]
+    fn cancel_account_remap(&mut self, old_account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_manager();
+        assert!(
+            self.account_remaps.remove(&old_account_id).is_some(),
+            "no scheduled account remap"
+        );
+
+        emit(EventKind::FtLockupCancelAccountRemap(FtLockupCancelAccountRemap {
+            old_account_id,
+        }));
+    }
+
+    #[payable]
+    fn remap_account(&mut self, old_account_id: AccountId, new_account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_manager();
+        let remap = self
+            .account_remaps
+            .remove(&old_account_id)
+            .expect("no scheduled account remap");
+        remap.assert_executable(&new_account_id, current_timestamp_sec());
+        // the account may have been blocked since the remap was scheduled
+        self.assert_not_blocked(&new_account_id);
+
+        let indices = self.account_lockups.remove(&old_account_id).unwrap_or_default();
+        let terminated_indices = self
+            .account_terminated_lockups
+            .remove(&old_account_id)
+            .unwrap_or_default();
+        let mut lockup_indices: Vec<LockupIndex> = indices.iter().chain(&terminated_indices).copied().collect();
+        lockup_indices.sort_unstable();
+        let events: Vec<FtLockupRemapLockup> = lockup_indices
+            .into_iter()
+            .map(|lockup_index| {
+                self.assert_no_claim_in_flight(lockup_index);
+                self.assert_no_termination_in_flight(lockup_index);
+                // the lien approval was given by the owner of the old account
+                self.assert_no_lien(lockup_index);
+                let mut lockup = self.internal_get_lockup(lockup_index).unwrap();
+                lockup.account_id = new_account_id.clone();
+                self.lockups.replace(u64::from(lockup_index), &lockup);
+                FtLockupRemapLockup {
+                    id: lockup_index,
+                    old_account_id: old_account_id.clone(),
+                    new_account_id: new_account_id.clone(),
+                }
+            })
+            .collect();
+
+        let mut new_indices = self.account_lockups.get(&new_account_id).unwrap_or_default();
+        new_indices.extend(indices);
+        self.internal_save_account_lockups(&new_account_id, new_indices);
+        if !terminated_indices.is_empty() {
+            let mut new_terminated_indices = self.account_terminated_lockups.get(&new_account_id).unwrap_or_default();
+            new_terminated_indices.extend(terminated_indices);
+            self.account_terminated_lockups
+                .insert(&new_account_id, &new_terminated_indices);
+        }
+        // the payout address may have been set by whoever controls the lost account
+        self.payout_addresses.remove(&old_account_id);
+
+        emit_batch(
+            EventKind::FtLockupRemapLockup,
+            events,
+            self.config.event_batch_threshold,
+        );
+    }
+
+    #[payable]
+    fn extend_all_schedules(&mut self, by_sec: TimestampSec) {
+        assert_one_yocto();
+        self.assert_manager();
+        assert!(
+            self.schedule_extension.is_none(),
+            "a schedule extension is already scheduled"
+        );
+        let extension = ScheduleExtension::new(by_sec, current_timestamp_sec());
+
+        emit(EventKind::FtLockupScheduleExtension(FtLockupScheduleExtension {
+            by_sec,
+            executable_at: extension.executable_at,
+        }));
+        self.schedule_extension = Some(extension);
+    }
+
+    #[payable]
+    fn cancel_schedule_extension(&mut self) {
+        assert_one_yocto();
+        self.assert_manager();
+        let extension = self.schedule_extension.take().expect("no scheduled schedule extension");
+        assert!(
+            !extension.is_started(),
+            "the schedule extension is already being applied"
+        );
+
+        emit(EventKind::FtLockupCancelScheduleExtension(
+            FtLockupCancelScheduleExtension {},
+        ));
+    }
+
+    #[payable]
+    fn apply_schedule_extension(&mut self, limit: u32) -> Option<LockupIndex> {
+        assert_one_yocto();
+        self.assert_manager();
+        let mut extension = self.schedule_extension.take().expect("no scheduled schedule extension");
+        extension.assert_executable(current_timestamp_sec());
+
+        let num_lockups = LockupIndex::try_from(self.lockups.len()).unwrap();
+        let from_index = extension.next_index;
+        let to_index = std::cmp::min(num_lockups, from_index.saturating_add(limit));
+        let mut extended_ids = vec![];
+        let mut skipped_ids = vec![];
+        for index in from_index..to_index {
+            let mut lockup = self.internal_get_lockup(index).unwrap();
+            match lockup.extend_schedules(extension.timestamp, extension.by_sec) {
+                Ok(true) => {
+                    self.lockups.replace(u64::from(index), &lockup);
+                    if let Some(vesting) = &lockup.vesting {
+                        self.lockup_vesting_schedules.insert(&index, vesting);
+                    }
+                    extended_ids.push(index);
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    log!("Lockup #{} is not extended: {}", index, error);
+                    skipped_ids.push(index);
+                }
+            }
+        }
+
+        emit(EventKind::FtLockupExtendSchedules(FtLockupExtendSchedules {
+            from_index,
+            to_index,
+            by_sec: extension.by_sec,
+            timestamp: extension.timestamp,
+            extended_ids,
+            skipped_ids,
+        }));
+        if to_index < num_lockups {
+            extension.next_index = to_index;
+            self.schedule_extension = Some(extension);
+            Some(to_index)
         } else {
-            PromiseOrValue::Value(0.into())
+            None
+        }
+    }
+
+    fn compute_lockups_merkle_root(&mut self, limit: u32) -> Option<LockupIndex> {
+        let mut computation = self
+            .lockups_merkle_computation
+            .take()
+            .unwrap_or_else(|| LockupsMerkleComputation {
+                frontier: MerkleFrontier::default(),
+                started_at: current_timestamp_sec(),
+            });
+
+        let num_lockups = LockupIndex::try_from(self.lockups.len()).unwrap();
+        let from_index = computation.frontier.num_leaves;
+        let to_index = std::cmp::min(num_lockups, from_index.saturating_add(limit));
+        for index in from_index..to_index {
+            let lockup = self.internal_get_lockup(index).unwrap();
+            computation.frontier.append(LockupLeaf::new(index, &lockup).hash());
+        }
+
+        // the lockups created meanwhile are hashed as well
+        if to_index < num_lockups {
+            self.lockups_merkle_computation = Some(computation);
+            return Some(to_index);
+        }
+        let merkle_root = LockupsMerkleRoot {
+            root: computation.frontier.root().into(),
+            height: computation.frontier.height(),
+            num_lockups,
+            started_at: computation.started_at,
+            finished_at: current_timestamp_sec(),
+        };
+        emit(EventKind::FtLockupComputeMerkleRoot(FtLockupComputeMerkleRoot {
+            root: merkle_root.root,
+            height: merkle_root.height,
+            num_lockups,
+        }));
+        self.lockups_merkle_root = Some(merkle_root);
+        None
+    }
+
+    #[payable]
+    fn set_claim_throttle(&mut self, claim_throttle: Option<ClaimThrottle>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.claim_throttle = claim_throttle;
+    }
+
+    // preserving both options for API compatibility
+    #[payable]
+    fn add_to_deposit_whitelist(&mut self, account_id: Option<AccountId>, account_ids: Option<Vec<AccountId>>) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        let account_ids = if let Some(account_ids) = account_ids {
+            account_ids
+        } else {
+            vec![account_id.expect("expected either account_id or account_ids")]
+        };
+        self.internal_add_to_deposit_whitelist(account_ids);
+    }
+
+    // preserving both options for API compatibility
+    #[payable]
+    fn remove_from_deposit_whitelist(
+        &mut self,
+        account_id: Option<AccountId>,
+        account_ids: Option<Vec<AccountId>>,
+        reason: Option<String>,
+    ) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        let account_ids = if let Some(account_ids) = account_ids {
+            account_ids
+        } else {
+            vec![account_id.expect("expected either account_id or account_ids")]
+        };
+        self.internal_remove_from_deposit_whitelist(account_ids, reason);
+    }
+
+    #[payable]
+    fn add_to_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.internal_add_to_draft_operators_whitelist(account_ids);
+    }
+
+    #[payable]
+    fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>, reason: Option<String>) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.internal_remove_from_draft_operators_whitelist(account_ids, reason);
+    }
+
+    #[payable]
+    fn add_to_payer_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        for account_id in &account_ids {
+            self.payer_whitelist.insert(account_id);
+        }
+        emit(EventKind::FtLockupAddToPayerWhitelist(FtLockupAddToPayerWhitelist {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
+        }));
+    }
+
+    #[payable]
+    fn remove_from_payer_whitelist(&mut self, account_ids: Vec<AccountId>, reason: Option<String>) {
+        assert_one_yocto();
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.assert_admin_reason(reason.as_deref());
+        for account_id in &account_ids {
+            self.payer_whitelist.remove(account_id);
+        }
+        emit(EventKind::FtLockupRemoveFromPayerWhitelist(
+            FtLockupRemoveFromPayerWhitelist {
+                account_ids: account_ids.into_iter().map(Into::into).collect(),
+                reason,
+            },
+        ));
+    }
+
+    #[payable]
+    fn set_attestor(&mut self, attestor_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.attestor_id = attestor_id;
+    }
+
+    #[payable]
+    fn set_attestation(&mut self, account_ids: Vec<AccountId>, attested: bool) {
+        assert_one_yocto();
+        self.assert_attestor(&env::predecessor_account_id());
+        for account_id in &account_ids {
+            if attested {
+                self.attested_accounts.insert(account_id);
+            } else {
+                self.attested_accounts.remove(account_id);
+            }
+        }
+        emit(EventKind::FtLockupSetAttestation(FtLockupSetAttestation {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
+            attested,
+        }));
+    }
+
+    #[payable]
+    fn set_email_attestor_key(&mut self, email_attestor_key: Option<PublicKey>) {
+        assert_one_yocto();
+        self.assert_manager();
+        if let Some(email_attestor_key) = &email_attestor_key {
+            assert_eq!(
+                email_attestor_key.curve_type(),
+                CurveType::ED25519,
+                "email attestor key must be an ed25519 key"
+            );
+        }
+        self.config.email_attestor_key = email_attestor_key;
+    }
+
+    #[payable]
+    fn set_blocklist_manager(&mut self, blocklist_manager_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        self.config.blocklist_manager_id = blocklist_manager_id;
+    }
+
+    #[payable]
+    fn set_blocked(&mut self, account_ids: Vec<AccountId>, blocked: bool) {
+        assert_one_yocto();
+        self.assert_blocklist_manager(&env::predecessor_account_id());
+        for account_id in &account_ids {
+            if blocked {
+                self.blocked_accounts.insert(account_id);
+            } else {
+                self.blocked_accounts.remove(account_id);
+            }
         }
+        emit(EventKind::FtLockupSetBlocked(FtLockupSetBlocked {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
+            blocked,
+        }));
     }
 
-    // preserving both options for API compatibility
     #[payable]
-    fn add_to_deposit_whitelist(&mut self, account_id: Option<AccountId>, account_ids: Option<Vec<AccountId>>) {
+    fn add_to_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>) {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        let account_ids = if let Some(account_ids) = account_ids {
-            account_ids
-        } else {
-            vec![account_id.expect("expected either account_id or account_ids")]
-        };
+        self.assert_manager();
         for account_id in &account_ids {
-            self.deposit_whitelist.insert(account_id);
+            self.vesting_oracles_whitelist.insert(account_id);
         }
-        emit(EventKind::FtLockupAddToDepositWhitelist(
-            FtLockupAddToDepositWhitelist {
-                account_ids: account_ids.into_iter().map(Into::into).collect(),
-            },
-        ));
     }
 
-    // preserving both options for API compatibility
     #[payable]
-    fn remove_from_deposit_whitelist(&mut self, account_id: Option<AccountId>, account_ids: Option<Vec<AccountId>>) {
+    fn remove_from_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>) {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        let account_ids = if let Some(account_ids) = account_ids {
-            account_ids
-        } else {
-            vec![account_id.expect("expected either account_id or account_ids")]
-        };
+        self.assert_manager();
+        // lockups vesting by a removed oracle can't be terminated until it's whitelisted again
         for account_id in &account_ids {
-            self.deposit_whitelist.remove(account_id);
+            self.vesting_oracles_whitelist.remove(account_id);
         }
+    }
+
+    #[payable]
+    fn resign(&mut self) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let from_deposit_whitelist = self.deposit_whitelist.remove(&account_id);
+        let from_draft_operators_whitelist = self.draft_operators_whitelist.remove(&account_id);
+        let from_payer_whitelist = self.payer_whitelist.remove(&account_id);
+        assert!(
+            from_deposit_whitelist || from_draft_operators_whitelist || from_payer_whitelist,
+            "Not in deposit whitelist, draft operators whitelist or payer whitelist"
+        );
         assert!(
             !self.deposit_whitelist.is_empty(),
             "cannot remove all accounts from deposit whitelist",
         );
-        emit(EventKind::FtLockupRemoveFromDepositWhitelist(
-            FtLockupRemoveFromDepositWhitelist {
-                account_ids: account_ids.into_iter().map(Into::into).collect(),
-            },
-        ));
+        emit(EventKind::FtLockupResign(FtLockupResign {
+            account_id,
+            from_deposit_whitelist,
+            from_draft_operators_whitelist,
+            from_payer_whitelist,
+        }));
     }
 
     #[payable]
-    fn add_to_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>) {
+    fn create_lockups(&mut self, lockups: Vec<LockupCreate>) -> Vec<LockupIndex> {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        for account_id in &account_ids {
-            self.draft_operators_whitelist.insert(account_id);
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        assert!(!lockups.is_empty(), "no lockups to create");
+        let mut pool = self.lockup_pools.get(&account_id).unwrap_or_default();
+
+        let mut events = vec![];
+        let indices = lockups
+            .iter()
+            .map(|lockup_create| {
+                lockup_create.assert_valid();
+                self.assert_not_blocked(&lockup_create.account_id);
+                self.assert_unlocked_at_creation(lockup_create);
+                let total_balance = lockup_create.schedule.total_balance();
+                let lockup = lockup_create.into_lockup(&account_id);
+                lockup.assert_new_valid(total_balance);
+                let funding = pool.debit(total_balance);
+                let index = self.internal_add_created_lockup(&lockup, lockup_create, &account_id, &account_id);
+                for share in funding {
+                    self.internal_record_funding(index, share.deposit_id, share.amount);
+                }
+                events.push(self.internal_create_lockup_event(index, lockup, None));
+                index
+            })
+            .collect();
+
+        if pool.is_empty() {
+            self.lockup_pools.remove(&account_id);
+        } else {
+            self.lockup_pools.insert(&account_id, &pool);
         }
-        emit(EventKind::FtLockupAddToDraftOperatorsWhitelist(
-            FtLockupAddToDraftOperatorsWhitelist {
-                account_ids: account_ids.into_iter().map(Into::into).collect(),
-            },
-        ));
+        log!(
+            "Created {} lockups from the lockup pool of {}",
+            events.len(),
+            account_id
+        );
+        emit_batch(
+            EventKind::FtLockupCreateLockup,
+            events,
+            self.config.event_batch_threshold,
+        );
+        indices
     }
 
     #[payable]
-    fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>) {
+    fn withdraw_lockup_pool(&mut self) -> PromiseOrValue<WrappedBalance> {
         assert_one_yocto();
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        for account_id in &account_ids {
-            self.draft_operators_whitelist.remove(account_id);
-        }
-        emit(EventKind::FtLockupRemoveFromDraftOperatorsWhitelist(
-            FtLockupRemoveFromDraftOperatorsWhitelist {
-                account_ids: account_ids.into_iter().map(Into::into).collect(),
-            },
-        ));
+        // also available after leaving the deposit whitelist
+        let account_id = env::predecessor_account_id();
+        let pool = self.lockup_pools.remove(&account_id).expect("lockup pool not found");
+        let amount = pool.balance();
+
+        emit(EventKind::FtLockupWithdrawLockupPool(FtLockupWithdrawLockupPool {
+            account_id: account_id.clone(),
+            amount: amount.into(),
+        }));
+
+        Promise::new(self.token_account_id.clone())
+            .ft_transfer(
+                &account_id,
+                amount,
+                Some(self.config.memo_templates.lockup_pool_withdrawal_memo(amount)),
+                self.config.gas.ft_transfer,
+            )
+            .then(
+                // same as a termination refund, the account gets an unlocked lockup if the transfer fails
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer)
+                    .after_lockup_termination(account_id, amount.into(), None),
+            )
+            .into()
     }
 
     fn create_draft_group(&mut self) -> DraftGroupIndex {
-        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+        self.assert_drafts_not_paused();
+        let account_id = env::predecessor_account_id();
+        self.assert_draft_operators_whitelist(&account_id);
+        if let Some(max_per_day) = self.config.max_draft_groups_per_day {
+            let mut creations = self.draft_group_creations.get(&account_id).unwrap_or_default();
+            creations.record(max_per_day, current_timestamp_sec());
+            self.draft_group_creations.insert(&account_id, &creations);
+        }
 
         let index = self.next_draft_group_id;
         self.next_draft_group_id += 1;
@@ -357,6 +1968,7 @@ impl LockupApi for Contract {
             self.draft_groups.insert(&index, &DraftGroup::default()).is_none(),
             "Invariant"
         );
+        self.draft_group_creators.insert(&index, &account_id);
         emit(EventKind::FtLockupCreateDraftGroup(vec![FtLockupCreateDraftGroup {
             id: index,
         }]));
@@ -364,11 +1976,60 @@ impl LockupApi for Contract {
         index
     }
 
+    #[payable]
+    fn reclaim_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        let conversion_deadline_sec = self
+            .config
+            .draft_group_conversion_deadline_sec
+            .expect("draft group conversion deadline is not configured");
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        let payer_id = draft_group.payer_id.clone().expect("draft group is not funded");
+        assert_eq!(env::predecessor_account_id(), payer_id, "Not the draft group payer");
+
+        let (draft_ids, amount) = draft_group.void(conversion_deadline_sec, current_timestamp_sec());
+        for draft_id in &draft_ids {
+            self.drafts.remove(draft_id);
+            self.num_drafts -= 1;
+            self.drafts_pending_account.remove(draft_id);
+        }
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+
+        emit(EventKind::FtLockupVoidDraftGroup(vec![FtLockupVoidDraftGroup {
+            id: draft_group_id,
+            draft_ids,
+            amount: amount.into(),
+        }]));
+
+        if amount > 0 {
+            Promise::new(self.token_account_id.clone())
+                .ft_transfer(
+                    &payer_id,
+                    amount,
+                    Some(self.config.memo_templates.reclaim_memo(amount, draft_group_id)),
+                    self.config.gas.ft_transfer,
+                )
+                .then(
+                    // same as a termination refund, the payer gets an unlocked lockup if the transfer fails
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(self.config.gas.after_ft_transfer)
+                        .after_lockup_termination(payer_id, amount.into(), None),
+                )
+                .into()
+        } else {
+            PromiseOrValue::Value(0.into())
+        }
+    }
+
     fn create_draft(&mut self, draft: Draft) -> DraftIndex {
         self.create_drafts(vec![draft])[0]
     }
 
     fn create_drafts(&mut self, drafts: Vec<Draft>) -> Vec<DraftIndex> {
+        self.assert_drafts_not_paused();
         self.assert_draft_operators_whitelist(&env::predecessor_account_id());
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let mut events: Vec<FtLockupCreateDraft> = vec![];
@@ -382,10 +2043,15 @@ impl LockupApi for Contract {
                 });
                 draft_group.assert_can_add_draft();
                 draft.assert_new_valid();
+                for account_id in draft.account_ids() {
+                    self.assert_not_blocked(account_id);
+                }
+                self.assert_unlocked_at_creation(&draft.lockup_create);
 
                 let index = self.next_draft_id;
                 self.next_draft_id += 1;
                 assert!(self.drafts.insert(&index, &draft).is_none(), "Invariant");
+                self.num_drafts += 1;
                 draft_group.total_amount = draft_group
                     .total_amount
                     .checked_add(draft.total_balance())
@@ -398,7 +2064,7 @@ impl LockupApi for Contract {
             })
             .collect();
 
-        emit(EventKind::FtLockupCreateDraft(events));
+        emit_batch(EventKind::FtLockupCreateDraft, events, self.config.event_batch_threshold);
         for (draft_group_id, draft_group) in draft_group_lookup {
             self.draft_groups.insert(&draft_group_id as _, &draft_group);
         }
@@ -407,10 +2073,109 @@ impl LockupApi for Contract {
     }
 
     fn convert_draft(&mut self, draft_id: DraftIndex) -> LockupIndex {
+        let draft = self.drafts.get(&draft_id as _).expect("draft not found");
+        for account_id in draft.account_ids() {
+            self.assert_not_blocked(account_id);
+        }
+        draft.assert_new_valid();
         self.convert_drafts(vec![draft_id])[0]
     }
 
+    fn freeze_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        self.assert_drafts_not_paused();
+        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.freeze();
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+
+        emit(EventKind::FtLockupFreezeDraftGroup(vec![FtLockupFreezeDraftGroup {
+            id: draft_group_id,
+        }]));
+    }
+
+    fn unfreeze_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        self.assert_drafts_not_paused();
+        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.unfreeze();
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+
+        emit(EventKind::FtLockupUnfreezeDraftGroup(vec![FtLockupUnfreezeDraftGroup {
+            id: draft_group_id,
+        }]));
+    }
+
+    #[payable]
+    fn approve_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        assert_one_yocto();
+        self.assert_drafts_not_paused();
+        self.assert_manager();
+
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.approve();
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+
+        emit(EventKind::FtLockupApproveDraftGroup(vec![FtLockupApproveDraftGroup {
+            id: draft_group_id,
+        }]));
+    }
+
+    #[payable]
+    fn convert_draft_with_account_creation(
+        &mut self,
+        draft_id: DraftIndex,
+        public_key: PublicKey,
+    ) -> PromiseOrValue<Option<LockupIndex>> {
+        self.assert_drafts_not_paused();
+        // the caller chooses the key of the created account, so it must be trusted
+        self.assert_draft_operators_whitelist(&env::predecessor_account_id());
+        let account_factory_id = self
+            .config
+            .account_factory_id
+            .clone()
+            .expect("account factory is not configured");
+        let deposit = env::attached_deposit();
+        assert!(!deposit.is_zero(), "deposit is required to fund the account");
+
+        let draft = self.drafts.get(&draft_id as _).expect("draft not found");
+        assert!(
+            draft.recipients.is_empty(),
+            "cannot create the account of a draft with multiple recipients"
+        );
+        self.assert_not_blocked(&draft.lockup_create.account_id);
+        self.draft_groups
+            .get(&draft.draft_group_id as _)
+            .expect("draft group not found")
+            .assert_can_convert_draft();
+
+        Promise::new(account_factory_id)
+            .create_account(
+                &draft.lockup_create.account_id,
+                &public_key,
+                deposit,
+                self.config.gas.create_account,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_create_account)
+                    .after_account_creation(draft_id, env::predecessor_account_id(), deposit),
+            )
+            .into()
+    }
+
     fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        self.assert_drafts_not_paused();
         self.assert_draft_operators_whitelist(&env::predecessor_account_id());
 
         let mut draft_group = self
@@ -420,7 +2185,7 @@ impl LockupApi for Contract {
         draft_group.discard();
 
         if draft_group.draft_indices.is_empty() {
-            self.draft_groups.remove(&draft_group_id as _);
+            self.internal_remove_draft_group(draft_group_id, &draft_group);
         } else {
             self.draft_groups.insert(&draft_group_id as _, &draft_group);
         }
@@ -431,48 +2196,138 @@ impl LockupApi for Contract {
     }
 
     fn delete_drafts(&mut self, draft_ids: Vec<DraftIndex>) {
+        self.assert_drafts_not_paused();
         // no authorization required here since the draft group discard has been authorized
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let mut events: Vec<FtLockupDeleteDraft> = vec![];
         for draft_id in draft_ids {
             let draft = self.drafts.remove(&draft_id as _).expect("draft not found");
+            self.num_drafts -= 1;
+            self.drafts_pending_account.remove(&draft_id);
             let draft_group = draft_group_lookup.entry(draft.draft_group_id).or_insert_with(|| {
                 self.draft_groups
                     .get(&draft.draft_group_id as _)
                     .expect("draft group not found")
             });
 
-            draft_group.assert_can_delete_draft();
-            let amount = draft.total_balance();
-            assert!(draft_group.total_amount >= amount, "Invariant");
-            draft_group.total_amount -= amount;
-
-            assert!(draft_group.draft_indices.remove(&draft_id), "Invariant");
+            draft_group.delete_draft(draft_id, draft.total_balance());
 
             let event = FtLockupDeleteDraft { id: draft_id };
             events.push(event);
         }
 
-        emit(EventKind::FtLockupDeleteDraft(events));
+        emit_batch(EventKind::FtLockupDeleteDraft, events, self.config.event_batch_threshold);
 
         for (draft_group_id, draft_group) in draft_group_lookup {
             if draft_group.draft_indices.is_empty() {
-                self.draft_groups.remove(&draft_group_id as _);
+                self.internal_remove_draft_group(draft_group_id, &draft_group);
             } else {
                 self.draft_groups.insert(&draft_group_id as _, &draft_group);
             }
         }
     }
+
+    fn delete_drafts_in_group(&mut self, draft_group_id: DraftGroupIndex, limit: u32) -> u32 {
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_can_delete_draft();
+        let mut draft_ids: Vec<DraftIndex> = draft_group.draft_indices.into_iter().collect();
+        draft_ids.sort_unstable();
+        let num_left = draft_ids.len().saturating_sub(limit as usize);
+        draft_ids.truncate(limit as usize);
+        self.delete_drafts(draft_ids);
+
+        u32::try_from(num_left).unwrap()
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Same as `create_drafts` with the drafts passed as the borsh-serialized input instead of JSON,
+    /// for batches exceeding the transaction size limit as JSON.
+    pub fn create_drafts_packed(&mut self, #[serializer(borsh)] drafts: Vec<Draft>) -> Vec<DraftIndex> {
+        self.create_drafts(drafts)
+    }
+}
+
+fn assert_unvested_balance_within_tolerance(unvested_balance: Balance, expected_unvested: Balance) {
+    validate_unvested_balance_within_tolerance(unvested_balance, expected_unvested)
+        .unwrap_or_else(|error| panic!("{error}"));
+}
+
+pub(crate) fn validate_unvested_balance_within_tolerance(
+    unvested_balance: Balance,
+    expected_unvested: Balance,
+) -> Result<(), String> {
+    let difference = unvested_balance.abs_diff(expected_unvested);
+    let tolerance = U256::from(expected_unvested) * U256::from(UNVESTED_BALANCE_TOLERANCE_BPS) / U256::from(10_000);
+    if U256::from(difference) > tolerance {
+        return Err(format!(
+            "The unvested balance {unvested_balance} differs from the expected {expected_unvested} by more than the tolerance"
+        ));
+    }
+    Ok(())
+}
+
+/// Number of lockups that can be claimed with the remaining gas, including the `after_ft_transfer` callback.
+fn max_claimed_lockups(gas_config: &GasConfig) -> usize {
+    let remaining_gas = env::prepaid_gas()
+        .as_gas()
+        .saturating_sub(env::used_gas().as_gas() + gas_config.claim_reserved_gas().as_gas());
+    usize::try_from(remaining_gas / gas_config.claim_gas_per_lockup().as_gas()).unwrap_or(usize::MAX)
 }
 
 /// Amount of fungible tokens
 pub type TokenAmount = u128;
+trait AccountFactoryPromise {
+    fn create_account(
+        self,
+        new_account_id: &AccountId,
+        new_public_key: &PublicKey,
+        deposit: NearToken,
+        gas: Gas,
+    ) -> Promise;
+}
+
+impl AccountFactoryPromise for Promise {
+    fn create_account(
+        self,
+        new_account_id: &AccountId,
+        new_public_key: &PublicKey,
+        deposit: NearToken,
+        gas: Gas,
+    ) -> Promise {
+        let args = serde_json::to_vec(&json!({
+            "new_account_id": new_account_id,
+            "new_public_key": new_public_key,
+        }))
+        .expect("Failed to serialize arguments");
+
+        self.function_call("create_account".to_string(), args, deposit, gas)
+    }
+}
+
 trait FtTransferPromise {
-    fn ft_transfer(self, receiver_id: &AccountId, amount: TokenAmount, memo: Option<String>) -> Promise;
+    fn ft_transfer(self, receiver_id: &AccountId, amount: TokenAmount, memo: Option<String>, gas: Gas) -> Promise;
+
+    fn ft_transfer_call(
+        self,
+        receiver_id: &AccountId,
+        amount: TokenAmount,
+        memo: Option<String>,
+        msg: String,
+        gas: Gas,
+    ) -> Promise;
+
+    fn ft_burn(self, amount: TokenAmount, memo: Option<String>, gas: Gas) -> Promise;
+
+    fn ft_metadata(self, gas: Gas) -> Promise;
 }
 
 impl FtTransferPromise for Promise {
-    fn ft_transfer(self, receiver_id: &AccountId, amount: TokenAmount, memo: Option<String>) -> Promise {
+    fn ft_transfer(self, receiver_id: &AccountId, amount: TokenAmount, memo: Option<String>, gas: Gas) -> Promise {
         let args = serde_json::to_vec(&json!({
             "receiver_id": receiver_id,
             "amount": amount.to_string(),
@@ -480,11 +2335,235 @@ impl FtTransferPromise for Promise {
         }))
         .expect("Failed to serialize arguments");
 
-        self.function_call(
-            "ft_transfer".to_string(),
-            args,
-            NearToken::from_yoctonear(1),
-            GAS_FOR_FT_TRANSFER,
-        )
+        self.function_call("ft_transfer".to_string(), args, NearToken::from_yoctonear(1), gas)
+    }
+
+    fn ft_transfer_call(
+        self,
+        receiver_id: &AccountId,
+        amount: TokenAmount,
+        memo: Option<String>,
+        msg: String,
+        gas: Gas,
+    ) -> Promise {
+        let args = serde_json::to_vec(&json!({
+            "receiver_id": receiver_id,
+            "amount": amount.to_string(),
+            "memo": memo.unwrap_or_default(),
+            "msg": msg,
+        }))
+        .expect("Failed to serialize arguments");
+
+        self.function_call("ft_transfer_call".to_string(), args, NearToken::from_yoctonear(1), gas)
+    }
+
+    fn ft_burn(self, amount: TokenAmount, memo: Option<String>, gas: Gas) -> Promise {
+        let args = serde_json::to_vec(&json!({
+            "amount": amount.to_string(),
+            "memo": memo.unwrap_or_default(),
+        }))
+        .expect("Failed to serialize arguments");
+
+        self.function_call("ft_burn".to_string(), args, NearToken::from_yoctonear(1), gas)
+    }
+
+    fn ft_metadata(self, gas: Gas) -> Promise {
+        self.function_call("ft_metadata".to_string(), vec![], NearToken::from_yoctonear(0), gas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, str::FromStr};
+
+    use hodl_model::remap::ACCOUNT_REMAP_DELAY_SEC;
+    use near_sdk::{test_utils::VMContextBuilder, testing_env};
+
+    use super::*;
+
+    fn manager() -> AccountId {
+        AccountId::from_str("manager.near").unwrap()
+    }
+
+    fn token() -> AccountId {
+        AccountId::from_str("token.near").unwrap()
+    }
+
+    fn set_predecessor(account_id: AccountId) {
+        testing_env!(VMContextBuilder::new().predecessor_account_id(account_id).build());
+    }
+
+    fn wipe_state(contract: &Contract) {
+        env::state_write(contract);
+        assert!(env::storage_remove(b"STATE"));
+    }
+
+    fn alice() -> AccountId {
+        AccountId::from_str("alice.near").unwrap()
+    }
+
+    fn set_manager_call(timestamp: TimestampSec) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(manager())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .block_timestamp(u64::from(timestamp) * 1_000_000_000)
+            .build());
+    }
+
+    fn contract_with_lien() -> Contract {
+        set_predecessor(manager());
+        let mut contract = Contract::new(token(), vec![manager()], None, manager());
+        contract.lockups.push(&Lockup::new_unlocked(alice(), 100));
+        contract.account_lockups.insert(&alice(), &BTreeSet::from([0]));
+        contract.liens.insert(
+            &0,
+            &Lien {
+                holder_id: AccountId::from_str("lender.near").unwrap(),
+                amount: 50,
+                redirect_claims: false,
+            },
+        );
+        contract
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract is not initialized")]
+    fn test_default_panics() {
+        let _contract = Contract::default();
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract is already initialized")]
+    fn test_new_over_existing_state() {
+        set_predecessor(manager());
+        let contract = Contract::new(token(), vec![], None, manager());
+        env::state_write(&contract);
+
+        Contract::new(token(), vec![], None, manager());
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract state was wiped")]
+    fn test_new_after_state_wipe() {
+        set_predecessor(manager());
+        let contract = Contract::new(token(), vec![], None, manager());
+        wipe_state(&contract);
+
+        Contract::new(token(), vec![], None, manager());
+    }
+
+    #[test]
+    fn test_reinit_after_state_wipe() {
+        set_predecessor(manager());
+        let mut contract = Contract::new(token(), vec![], None, manager());
+        contract.config.event_batch_threshold = Some(10);
+        wipe_state(&contract);
+
+        let contract = Contract::reinit_after_state_wipe(token(), vec![manager()], None);
+        assert_eq!(contract.manager, manager());
+        assert!(contract.deposit_whitelist.contains(&manager()));
+        assert_eq!(contract.config, Config::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract was never initialized")]
+    fn test_reinit_never_initialized() {
+        set_predecessor(manager());
+
+        Contract::reinit_after_state_wipe(token(), vec![], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the manager can reinitialize the contract")]
+    fn test_reinit_not_by_manager() {
+        set_predecessor(AccountId::from_str("alice.near").unwrap());
+        let contract = Contract::new(token(), vec![], None, manager());
+        wipe_state(&contract);
+
+        Contract::reinit_after_state_wipe(token(), vec![], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The wiped state left lockups or drafts")]
+    fn test_reinit_with_leftover_lockups() {
+        set_predecessor(manager());
+        let mut contract = Contract::new(token(), vec![], None, manager());
+        contract
+            .lockups
+            .push(&Lockup::new_unlocked(AccountId::from_str("alice.near").unwrap(), 100));
+        wipe_state(&contract);
+
+        Contract::reinit_after_state_wipe(token(), vec![], None);
+    }
+
+    #[test]
+    fn test_account_lockups_stored_as_hash_set() {
+        set_predecessor(manager());
+        let contract = Contract::new(token(), vec![], None, manager());
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut stored: LookupMap<AccountId, HashSet<LockupIndex>> = LookupMap::new(StorageKey::AccountLockups);
+        stored.insert(&account_id, &HashSet::from([5, 1, 3]));
+
+        let indices = contract.account_lockups.get(&account_id).unwrap();
+        assert_eq!(indices.into_iter().collect::<Vec<_>>(), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_claim_all_skips_lockups_in_flight() {
+        set_predecessor(manager());
+        let mut contract = Contract::new(token(), vec![manager()], None, manager());
+        contract.lockups.push(&Lockup::new_unlocked(alice(), 100));
+        contract.lockups.push(&Lockup::new_unlocked(alice(), 200));
+        contract.account_lockups.insert(&alice(), &BTreeSet::from([0, 1]));
+        contract.claims_in_flight.insert(&0, &10);
+        contract.terminations_in_flight.insert(&1);
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .block_timestamp(10 * 1_000_000_000)
+            .build());
+
+        let PromiseOrValue::Value(result) = contract.claim(None, None, None) else {
+            panic!("Nothing should be claimed");
+        };
+        assert_eq!(result.claimed_balance.0, 0);
+        assert_eq!(result.in_flight_lockups, vec![0, 1]);
+
+        contract.terminations_in_flight.remove(&1);
+        let PromiseOrValue::Promise(_) = contract.claim(None, None, None) else {
+            panic!("The lockup not in flight should be claimed");
+        };
+        assert_eq!(contract.claims_in_flight.get(&0), Some(10));
+        assert_eq!(contract.claims_in_flight.get(&1), Some(200));
+    }
+
+    #[test]
+    #[should_panic(expected = "lockup has a lien")]
+    fn test_terminate_with_lien() {
+        let mut contract = contract_with_lien();
+        set_manager_call(0);
+
+        contract.terminate(0, None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "lockup has a lien")]
+    fn test_revoke_unclaimed_with_lien() {
+        let mut contract = contract_with_lien();
+        set_manager_call(0);
+
+        contract.revoke_unclaimed(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "lockup has a lien")]
+    fn test_remap_account_with_lien() {
+        let mut contract = contract_with_lien();
+        let new_account_id = AccountId::from_str("alice-recovered.near").unwrap();
+        contract
+            .account_remaps
+            .insert(&alice(), &AccountRemap::new(new_account_id.clone(), 0));
+        set_manager_call(ACCOUNT_REMAP_DELAY_SEC);
+
+        contract.remap_account(alice(), new_account_id);
     }
 }
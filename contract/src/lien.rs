@@ -0,0 +1,151 @@
+use hodl_model::{
+    lien::{Lien, LienApi},
+    lockup::LockupIndex,
+    util::current_timestamp_sec,
+    WrappedBalance,
+};
+use near_sdk::{assert_one_yocto, env, near_bindgen, AccountId, Promise, PromiseOrValue};
+
+use crate::{
+    callbacks::ext_self,
+    event::{emit, EventKind, FtLockupPlaceLien, FtLockupReleaseLien},
+    Contract, ContractExt, FtTransferPromise,
+};
+
+impl Contract {
+    pub(crate) fn assert_lien_holder(&self, lockup_index: LockupIndex) -> Lien {
+        let lien = self.liens.get(&lockup_index).expect("lockup has no lien");
+        assert_eq!(
+            lien.holder_id,
+            env::predecessor_account_id(),
+            "Only the lien holder can call it"
+        );
+        lien
+    }
+
+    /// The reserved balance of a lien can't leave the lockup before the lien holder releases it.
+    pub(crate) fn assert_no_lien(&self, lockup_index: LockupIndex) {
+        assert!(!self.liens.contains_key(&lockup_index), "lockup has a lien");
+    }
+}
+
+#[near_bindgen]
+impl LienApi for Contract {
+    #[payable]
+    fn approve_lien(&mut self, lockup_index: LockupIndex, holder_id: Option<AccountId>) {
+        assert_one_yocto();
+        let lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        assert_eq!(
+            lockup.account_id,
+            env::predecessor_account_id(),
+            "Only the lockup owner can approve a lien"
+        );
+        if let Some(holder_id) = holder_id {
+            self.lien_approvals.insert(&lockup_index, &holder_id);
+        } else {
+            self.lien_approvals.remove(&lockup_index);
+        }
+    }
+
+    #[payable]
+    fn place_lien(&mut self, lockup_index: LockupIndex, amount: WrappedBalance, redirect_claims: bool) {
+        assert_one_yocto();
+        let holder_id = env::predecessor_account_id();
+        assert!(
+            self.lien_holders_whitelist.contains(&holder_id),
+            "Not in lien holders whitelist"
+        );
+        assert_eq!(
+            self.lien_approvals.remove(&lockup_index),
+            Some(holder_id.clone()),
+            "The lien is not approved by the lockup owner"
+        );
+        assert!(!self.liens.contains_key(&lockup_index), "lockup already has a lien");
+        assert!(
+            !self.internal_is_pending(lockup_index),
+            "pending lockup can't have a lien"
+        );
+        let lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        let remaining_balance = lockup.schedule.total_balance() - lockup.claimed_balance;
+        assert!(
+            amount.0 > 0 && amount.0 <= remaining_balance,
+            "lien amount should be positive and not exceed the unclaimed balance"
+        );
+
+        self.liens.insert(
+            &lockup_index,
+            &Lien {
+                holder_id: holder_id.clone(),
+                amount: amount.0,
+                redirect_claims,
+            },
+        );
+        emit(EventKind::FtLockupPlaceLien(vec![FtLockupPlaceLien {
+            id: lockup_index,
+            holder_id,
+            amount,
+            redirect_claims,
+        }]));
+    }
+
+    #[payable]
+    fn release_lien(&mut self, lockup_index: LockupIndex) {
+        assert_one_yocto();
+        let lien = self.assert_lien_holder(lockup_index);
+        self.liens.remove(&lockup_index);
+        emit(EventKind::FtLockupReleaseLien(vec![FtLockupReleaseLien {
+            id: lockup_index,
+            holder_id: lien.holder_id,
+        }]));
+    }
+
+    #[payable]
+    fn claim_lien(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.assert_claims_not_paused();
+        let lien = self.assert_lien_holder(lockup_index);
+        assert!(lien.redirect_claims, "the lien doesn't redirect claims");
+        let mut lockup = self.internal_get_lockup(lockup_index).expect("Lockup not found");
+        self.assert_attested(&lockup.account_id);
+        let claimable = lockup.unlocked_balance(current_timestamp_sec()) - lockup.claimed_balance;
+        let claim_amount = lien.holder_claimable(claimable);
+        assert!(claim_amount > 0, "nothing to claim");
+        self.internal_consume_claim_quota(claim_amount);
+        let lockup_claim = lockup.claim(lockup_index, claim_amount);
+        self.lockups.replace(u64::from(lockup_index), &lockup);
+        self.internal_start_claim(lockup_index, claim_amount);
+
+        Promise::new(self.token_account_id.clone())
+            .ft_transfer(
+                &lien.holder_id,
+                claim_amount,
+                Some(self.config.memo_templates.lien_claim_memo(claim_amount, lockup_index)),
+                self.config.gas.ft_transfer,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(self.config.gas.after_ft_transfer_gas(1))
+                    .after_lien_claim(lockup.account_id, lockup_claim, lien.holder_id),
+            )
+            .into()
+    }
+
+    #[payable]
+    fn add_to_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        for account_id in &account_ids {
+            self.lien_holders_whitelist.insert(account_id);
+        }
+    }
+
+    #[payable]
+    fn remove_from_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        // the liens already placed stay in force until released by their holders
+        for account_id in &account_ids {
+            self.lien_holders_whitelist.remove(account_id);
+        }
+    }
+}
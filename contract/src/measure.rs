@@ -0,0 +1,40 @@
+//! Gas profiling of internal phases of a call, enabled by the `measure` feature.
+//! Without the feature the meter is a no-op and gets optimized out.
+
+#[cfg(feature = "measure")]
+use crate::{env, log};
+
+pub(crate) struct GasMeter {
+    #[cfg(feature = "measure")]
+    used_gas: u64,
+}
+
+#[cfg(feature = "measure")]
+impl GasMeter {
+    /// Starts measuring, the gas used before the call body (input and state deserialization) is logged
+    /// as the `deserialize` phase.
+    pub(crate) fn start() -> Self {
+        let used_gas = env::used_gas().as_gas();
+        log!("measure: deserialize {} gas", used_gas);
+        Self { used_gas }
+    }
+
+    /// Logs the gas used since the previous phase.
+    pub(crate) fn phase(&mut self, name: &str) {
+        let used_gas = env::used_gas().as_gas();
+        log!("measure: {} {} gas", name, used_gas - self.used_gas);
+        self.used_gas = used_gas;
+    }
+}
+
+#[cfg(not(feature = "measure"))]
+impl GasMeter {
+    #[inline]
+    pub(crate) fn start() -> Self {
+        Self {}
+    }
+
+    #[inline]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn phase(&mut self, _name: &str) {}
+}
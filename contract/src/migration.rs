@@ -1,7 +1,124 @@
-use near_sdk::{env, near_bindgen};
+use std::collections::{BTreeSet, HashSet};
 
-use crate::{Contract, ContractExt};
-use crate::event::{emit, EventKind, FtLockupUpdateContract};
+use hodl_model::{
+    config::Config,
+    draft::{Draft, DraftGroup, DraftGroupIndex, DraftGroupStatus, DraftIndex},
+    lockup::{Lockup, LockupCreate, LockupIndex},
+    schedule::Schedule,
+    stats::TokenFlows,
+    termination::VestingConditions,
+    throttle::ClaimQuota,
+    util::current_timestamp_sec,
+    Balance, TokenAccountId,
+};
+use near_sdk::{
+    assert_one_yocto,
+    borsh::{to_vec, BorshDeserialize},
+    collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector},
+    env, log, near, near_bindgen, AccountId, IntoStorageKey,
+};
+
+use crate::{
+    event::{emit, EventKind, FtLockupUpdateContract},
+    Contract, ContractExt, StorageKey,
+};
+
+#[cfg(feature = "migration-diff")]
+pub mod diff;
+
+/// The draft group layout of version 1.1.0.
+#[near(serializers=[borsh])]
+pub struct DraftGroupV1 {
+    pub total_amount: Balance,
+    pub payer_id: Option<AccountId>,
+    pub draft_indices: HashSet<DraftIndex>,
+    pub discarded: bool,
+}
+
+impl From<DraftGroupV1> for DraftGroup {
+    fn from(draft_group: DraftGroupV1) -> Self {
+        let status = if draft_group.discarded {
+            DraftGroupStatus::Discarded
+        } else if draft_group.payer_id.is_some() {
+            DraftGroupStatus::Funded
+        } else {
+            // groups were funded without being frozen before the upgrade, so they're frozen to remain fundable
+            DraftGroupStatus::Frozen
+        };
+        Self {
+            total_amount: draft_group.total_amount,
+            payer_id: draft_group.payer_id,
+            draft_indices: draft_group.draft_indices,
+            status,
+            approved: false,
+            // the conversion deadline of groups funded before the upgrade starts with their migration
+            funded_timestamp: (status == DraftGroupStatus::Funded).then(current_timestamp_sec),
+            // conversions are counted since the migration, against the balance left for them
+            funded_amount: if status == DraftGroupStatus::Funded {
+                draft_group.total_amount
+            } else {
+                0
+            },
+            converted_amount: 0,
+            deleted_amount: 0,
+        }
+    }
+}
+
+/// The draft layout of version 1.1.0.
+#[near(serializers=[borsh])]
+pub struct DraftV1 {
+    pub draft_group_id: DraftGroupIndex,
+    pub lockup_create: LockupCreateV1,
+}
+
+#[near(serializers=[borsh])]
+pub struct LockupCreateV1 {
+    pub account_id: AccountId,
+    pub schedule: Schedule,
+    pub vesting_schedule: Option<VestingConditions>,
+}
+
+impl From<DraftV1> for Draft {
+    fn from(draft: DraftV1) -> Self {
+        Self {
+            draft_group_id: draft.draft_group_id,
+            lockup_create: LockupCreate {
+                account_id: draft.lockup_create.account_id,
+                schedule: draft.lockup_create.schedule,
+                vesting_schedule: draft.lockup_create.vesting_schedule,
+                tags: vec![],
+                owner_key: None,
+                owner_email_hash: None,
+                unclaimed_revocation_after_sec: None,
+                smoothing_sec: None,
+                agreement_hash: None,
+                keeper_incentive: None,
+                burn_on_termination: false,
+                curve: None,
+                cliff_escrow_id: None,
+                vesting: None,
+            },
+            recipients: vec![],
+        }
+    }
+}
+
+/// The contract state layout of version 1.1.0.
+#[near(serializers=[borsh])]
+pub struct ContractV1 {
+    pub token_account_id: TokenAccountId,
+    pub lockups: Vector<Lockup>,
+    /// Stored as a `HashSet`, which has the same Borsh layout, the sets are sorted as they're saved again.
+    pub account_lockups: LookupMap<AccountId, BTreeSet<LockupIndex>>,
+    pub deposit_whitelist: UnorderedSet<AccountId>,
+    pub draft_operators_whitelist: UnorderedSet<AccountId>,
+    pub next_draft_id: DraftIndex,
+    pub drafts: LookupMap<DraftIndex, DraftV1>,
+    pub next_draft_group_id: DraftGroupIndex,
+    pub draft_groups: UnorderedMap<DraftGroupIndex, DraftGroupV1>,
+    pub manager: AccountId,
+}
 
 #[near_bindgen]
 impl Contract {
@@ -10,8 +127,219 @@ impl Contract {
     pub fn migrate() -> Self {
         emit(EventKind::FtLockupUpdateContract(FtLockupUpdateContract {}));
 
-        let old_state: Contract = env::state_read().expect("Failed to read old state");
+        let old_state: ContractV1 = env::state_read().expect("Failed to read old state");
+
+        let contract = Contract {
+            token_account_id: old_state.token_account_id,
+            lockups: old_state.lockups,
+            account_lockups: old_state.account_lockups,
+            pending_lockups: LookupMap::new(StorageKey::PendingLockups),
+            pending_email_lockups: LookupMap::new(StorageKey::PendingEmailLockups),
+            lockup_tags: LookupMap::new(StorageKey::LockupTags),
+            tag_lockups: LookupMap::new(StorageKey::TagLockups),
+            grants: Vector::new(StorageKey::Grants),
+            lockup_grants: LookupMap::new(StorageKey::LockupGrants),
+            unclaimed_revocations: LookupMap::new(StorageKey::UnclaimedRevocations),
+            lockup_agreements: LookupMap::new(StorageKey::LockupAgreements),
+            // the creators of existing lockups are unknown, only the manager can terminate them under a creator policy
+            lockup_creators: LookupMap::new(StorageKey::LockupCreators),
+            draft_group_creators: LookupMap::new(StorageKey::DraftGroupCreators),
+            liens: LookupMap::new(StorageKey::Liens),
+            lien_approvals: LookupMap::new(StorageKey::LienApprovals),
+            lien_holders_whitelist: UnorderedSet::new(StorageKey::LienHoldersWhitelist),
+            lockup_notes: LookupMap::new(StorageKey::LockupNotes),
+            viewing_keys: LookupMap::new(StorageKey::ViewingKeys),
+            privacy_viewers_whitelist: UnorderedSet::new(StorageKey::PrivacyViewersWhitelist),
+            vesting_oracles_whitelist: UnorderedSet::new(StorageKey::VestingOraclesWhitelist),
+            lockup_pools: UnorderedMap::new(StorageKey::LockupPools),
+            manager_signing_key: None,
+            signed_action_nonces: LookupMap::new(StorageKey::SignedActionNonces),
+            keeper_incentives: LookupMap::new(StorageKey::KeeperIncentives),
+            payout_addresses: LookupMap::new(StorageKey::PayoutAddresses),
+            claims_in_flight: LookupMap::new(StorageKey::ClaimsInFlight),
+            terminations_in_flight: LookupSet::new(StorageKey::TerminationsInFlight),
+            terminated_lockups: LookupSet::new(StorageKey::TerminatedLockups),
+            account_terminated_lockups: LookupMap::new(StorageKey::AccountTerminatedLockups),
+            account_remaps: LookupMap::new(StorageKey::AccountRemaps),
+            schedule_extension: None,
+            airdrops: Vector::new(StorageKey::Airdrops),
+            airdrop_registrations: LookupSet::new(StorageKey::AirdropRegistrations),
+            lockups_merkle_computation: None,
+            lockups_merkle_root: None,
+            burn_on_termination: LookupSet::new(StorageKey::BurnOnTermination),
+            lockup_curves: LookupMap::new(StorageKey::LockupCurves),
+            lockup_vesting_schedules: LookupMap::new(StorageKey::LockupVestingSchedules),
+            lockup_unlock_starts: LookupMap::new(StorageKey::LockupUnlockStarts),
+            claim_stats: LookupMap::new(StorageKey::ClaimStats),
+            cliff_escrows: LookupMap::new(StorageKey::CliffEscrows),
+            draft_group_creations: LookupMap::new(StorageKey::DraftGroupCreations),
+            deposit_whitelist: old_state.deposit_whitelist,
+            draft_operators_whitelist: old_state.draft_operators_whitelist,
+            payer_whitelist: UnorderedSet::new(StorageKey::PayerWhitelist),
+            attested_accounts: LookupSet::new(StorageKey::AttestedAccounts),
+            blocked_accounts: LookupSet::new(StorageKey::BlockedAccounts),
+            next_draft_id: old_state.next_draft_id,
+            drafts: LookupMap::new(StorageKey::DraftsV2),
+            // counting the drafts as they're migrated by `continue_migration`
+            num_drafts: 0,
+            drafts_pending_account: UnorderedSet::new(StorageKey::DraftsPendingAccount),
+            next_draft_group_id: old_state.next_draft_group_id,
+            draft_groups: UnorderedMap::new(StorageKey::DraftGroupsV2),
+            closed_draft_groups: LookupMap::new(StorageKey::ClosedDraftGroups),
+            // the drafts are migrated in batches, so the upgrade fits the gas of `migrate` for any number of drafts
+            legacy_draft_groups: old_state.draft_groups,
+            legacy_drafts: old_state.drafts,
+            manager: old_state.manager,
+            // the multisig of the previous version was stored by the `SelfUpdate` derive outside of the state,
+            // after the upgrade the manager sets it again with `set_multisig` and appoints the update guardians
+            // with `add_update_guardians`, no further update can be deployed before both are set
+            multisig: None,
+            update_guardians: UnorderedSet::new(StorageKey::UpdateGuardians),
+            approved_code_hashes: UnorderedSet::new(StorageKey::ApprovedCodeHashes),
+            terminations: Vector::new(StorageKey::Terminations),
+            // tracking provenance since the upgrade
+            deposits: Vector::new(StorageKey::Deposits),
+            lockup_funding: LookupMap::new(StorageKey::LockupFunding),
+            draft_group_deposits: LookupMap::new(StorageKey::DraftGroupDeposits),
+            token_decimals: None,
+            // counting flows since the upgrade
+            token_flows: TokenFlows::default(),
+            config: Config::default(),
+            claim_quota: ClaimQuota::default(),
+        };
+        // deployments initialized before the marker was introduced get it on the upgrade
+        Contract::internal_set_init_manager(&contract.manager);
+        contract.internal_refresh_token_metadata();
+
+        contract
+    }
+
+    /// Migrates the next draft groups left in the pre-upgrade layout with their drafts, up to `limit` records.
+    /// A draft group can't be used until it's migrated, so it's called right after the upgrade until it returns 0.
+    /// Anyone can call it. Returns the number of draft groups left to migrate.
+    pub fn continue_migration(&mut self, limit: u32) -> u32 {
+        let mut num_migrated: u32 = 0;
+        while num_migrated < limit {
+            let keys = self.legacy_draft_groups.keys_as_vector();
+            let Some(draft_group_id) = keys.len().checked_sub(1).and_then(|index| keys.get(index)) else {
+                break;
+            };
+            let draft_group = self
+                .legacy_draft_groups
+                .get(&draft_group_id)
+                .expect("draft group not found");
+
+            // drafts can't be iterated, but all of them are referenced by draft groups
+            let mut is_complete = true;
+            for draft_id in &draft_group.draft_indices {
+                if num_migrated == limit {
+                    is_complete = false;
+                    break;
+                }
+                // the drafts migrated by a previous call are already removed
+                if let Some(draft) = self.legacy_drafts.remove(draft_id) {
+                    self.drafts.insert(draft_id, &draft.into());
+                    self.num_drafts += 1;
+                    num_migrated += 1;
+                }
+            }
+            if !is_complete {
+                break;
+            }
+
+            self.legacy_draft_groups.remove(&draft_group_id);
+            self.draft_groups.insert(&draft_group_id, &draft_group.into());
+            num_migrated += 1;
+        }
+
+        let num_left = u32::try_from(self.legacy_draft_groups.len()).unwrap();
+        log!(
+            "Migrated {} drafts and draft groups, {} draft groups left",
+            num_migrated,
+            num_left
+        );
+        num_left
+    }
+
+    /// Marks the lockups terminated before the upgrade as terminated, since the pre-upgrade state doesn't record it.
+    /// The indices are taken from the `ft_lockup_terminate_lockup` events emitted before the upgrade.
+    /// Only the manager can call it, and only lockups without a termination config can be marked.
+    #[payable]
+    pub fn backfill_terminated_lockups(&mut self, lockup_indices: Vec<LockupIndex>) {
+        assert_one_yocto();
+        self.assert_manager();
+        for lockup_index in lockup_indices {
+            let lockup = self.lockups.get(u64::from(lockup_index)).expect("lockup not found");
+            assert!(
+                lockup.termination_config.is_none(),
+                "lockup #{lockup_index} is still terminatable"
+            );
+            self.terminated_lockups.insert(&lockup_index);
+        }
+    }
+
+    /// Runs the migration over the first `limit` lockups and draft groups without persisting anything,
+    /// logging the records failing to deserialize, and returns the number of failures.
+    /// It reads the pre-upgrade layout, so it's called with the new code in place of `migrate`,
+    /// e.g. on a sandbox patched with the mainnet state. Only the manager can call it.
+    pub fn simulate_migration(limit: u32) -> u32 {
+        let old_state: ContractV1 = env::state_read().expect("Failed to read old state");
+        assert_eq!(env::predecessor_account_id(), old_state.manager, "Not a manager");
+        let limit = u64::from(limit);
+        let mut num_failures: u32 = 0;
+
+        let num_lockups = old_state.lockups.len().min(limit);
+        for index in 0..num_lockups {
+            let raw_lockup = old_state.lockups.get_raw(index).expect("lockup not found");
+            if let Err(error) = Lockup::try_from_slice(&raw_lockup) {
+                log!("Failed to deserialize lockup #{}: {}", index, error);
+                num_failures += 1;
+            }
+        }
+
+        let draft_group_ids = old_state.draft_groups.keys_as_vector();
+        let draft_groups = old_state.draft_groups.values_as_vector();
+        let drafts_prefix = StorageKey::Drafts.into_storage_key();
+        let num_draft_groups = draft_groups.len().min(limit);
+        for index in 0..num_draft_groups {
+            let draft_group_id = draft_group_ids.get(index).expect("draft group not found");
+            let raw_draft_group = draft_groups.get_raw(index).expect("draft group not found");
+            let draft_group = match DraftGroupV1::try_from_slice(&raw_draft_group) {
+                Ok(draft_group) => draft_group,
+                Err(error) => {
+                    log!("Failed to deserialize draft group {}: {}", draft_group_id, error);
+                    num_failures += 1;
+                    continue;
+                }
+            };
+            for draft_id in &draft_group.draft_indices {
+                let key = [drafts_prefix.as_slice(), &to_vec(draft_id).unwrap()].concat();
+                match env::storage_read(&key).map(|raw_draft| DraftV1::try_from_slice(&raw_draft)) {
+                    Some(Ok(draft)) => {
+                        if let Err(error) = Draft::from(draft).validate_new() {
+                            log!("Draft {} is invalid after the migration: {}", draft_id, error);
+                            num_failures += 1;
+                        }
+                    }
+                    Some(Err(error)) => {
+                        log!("Failed to deserialize draft {}: {}", draft_id, error);
+                        num_failures += 1;
+                    }
+                    None => {
+                        log!("Draft {} of draft group {} not found", draft_id, draft_group_id);
+                        num_failures += 1;
+                    }
+                }
+            }
+            let _: DraftGroup = draft_group.into();
+        }
 
-        old_state
+        log!(
+            "Simulated the migration of {} lockups and {} draft groups with {} failures",
+            num_lockups,
+            num_draft_groups,
+            num_failures
+        );
+        num_failures
     }
 }
@@ -0,0 +1,110 @@
+//! Compares the lockup balances of a pre-upgrade state snapshot with the state produced by `migrate`,
+//! so a migration can be reviewed against the mainnet state before it's applied.
+
+use hodl_model::{
+    lockup::{Lockup, LockupIndex},
+    TimestampSec, WrappedBalance,
+};
+use near_sdk::{
+    env,
+    json_types::Base64VecU8,
+    serde::{Deserialize, Serialize},
+    test_utils::VMContextBuilder,
+    test_vm_config, testing_env, AccountId, Gas, NearToken,
+};
+
+use crate::{migration::ContractV1, Contract};
+
+/// A contract storage record as returned by the `view_state` RPC query.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StateRecord {
+    pub key: Base64VecU8,
+    pub value: Base64VecU8,
+}
+
+/// The result of the `view_state` RPC query for the contract account.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StateSnapshot {
+    pub values: Vec<StateRecord>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockupBalances {
+    pub total_balance: WrappedBalance,
+    pub claimed_balance: WrappedBalance,
+    pub unclaimed_balance: WrappedBalance,
+}
+
+/// A lockup whose balances differ after the migration, `None` if the lockup is missing on that side.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockupBalancesChange {
+    pub index: LockupIndex,
+    pub before: Option<LockupBalances>,
+    pub after: Option<LockupBalances>,
+}
+
+/// Loads the snapshot into the mocked blockchain storage of the contract account, runs `migrate` on it
+/// and returns the lockups whose balances at the given timestamp changed.
+pub fn diff(snapshot: StateSnapshot, contract_id: AccountId, timestamp: TimestampSec) -> Vec<LockupBalancesChange> {
+    // the migration of the full state isn't limited by the gas of a single transaction here
+    let mut vm_config = test_vm_config();
+    vm_config.limit_config.max_gas_burnt = u64::MAX;
+    testing_env!(
+        VMContextBuilder::new()
+            .current_account_id(contract_id.clone())
+            .predecessor_account_id(contract_id)
+            .block_timestamp(u64::from(timestamp) * 1_000_000_000)
+            .prepaid_gas(Gas::from_gas(u64::MAX))
+            .account_balance(NearToken::from_near(1_000_000_000))
+            .build(),
+        vm_config
+    );
+
+    for record in snapshot.values {
+        env::storage_write(&record.key.0, &record.value.0);
+    }
+
+    let old_state: ContractV1 = env::state_read().expect("Failed to read old state");
+    let before: Vec<LockupBalances> = old_state
+        .lockups
+        .iter()
+        .map(|lockup| lockup_balances(&lockup, timestamp))
+        .collect();
+
+    let contract = Contract::migrate();
+    let after: Vec<LockupBalances> = contract
+        .lockups
+        .iter()
+        .map(|lockup| lockup_balances(&lockup, timestamp))
+        .collect();
+
+    let len = before.len().max(after.len());
+    let mut before = before.into_iter().map(Some).chain(std::iter::repeat_with(|| None));
+    let mut after = after.into_iter().map(Some).chain(std::iter::repeat_with(|| None));
+
+    (0..len)
+        .filter_map(|index| {
+            let (before, after) = (before.next().flatten(), after.next().flatten());
+            (before != after).then(|| LockupBalancesChange {
+                index: index.try_into().expect("lockup index overflow"),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+fn lockup_balances(lockup: &Lockup, timestamp: TimestampSec) -> LockupBalances {
+    LockupBalances {
+        total_balance: lockup.schedule.total_balance().into(),
+        claimed_balance: lockup.claimed_balance.into(),
+        unclaimed_balance: lockup
+            .unlocked_balance(timestamp)
+            .saturating_sub(lockup.claimed_balance)
+            .into(),
+    }
+}
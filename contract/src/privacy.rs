@@ -0,0 +1,80 @@
+use hodl_model::{
+    privacy::{view_auth_message, PrivacyApi, ViewAuth},
+    util::current_timestamp_sec,
+};
+use near_sdk::{assert_one_yocto, env, near_bindgen, AccountId, CurveType, PublicKey};
+
+use crate::{Contract, ContractExt};
+
+impl Contract {
+    /// Panics while the privacy mode hides the detailed views.
+    pub(crate) fn assert_public_details(&self) {
+        assert!(
+            !self.config.privacy_mode,
+            "Lockup details are private, use the signed views"
+        );
+    }
+
+    /// Checks that the authorization is signed by the viewing key of its viewer, and that the viewer
+    /// is the account itself, a whitelisted privacy viewer or the manager.
+    pub(crate) fn assert_view_auth(&self, auth: &ViewAuth, account_id: &AccountId) {
+        auth.validate(current_timestamp_sec())
+            .unwrap_or_else(|error| panic!("{error}"));
+        let viewing_key = self.viewing_keys.get(&auth.viewer_id).expect("viewing key not found");
+        let signature: [u8; 64] = auth
+            .signature
+            .0
+            .as_slice()
+            .try_into()
+            .expect("invalid signature length");
+        let public_key: [u8; 32] = viewing_key.as_bytes()[1..].try_into().unwrap();
+        let message = view_auth_message(&env::current_account_id(), &auth.viewer_id, auth.expires_at);
+        assert!(
+            env::ed25519_verify(&signature, &message, &public_key),
+            "invalid signature"
+        );
+        assert!(
+            &auth.viewer_id == account_id
+                || auth.viewer_id == self.manager
+                || self.privacy_viewers_whitelist.contains(&auth.viewer_id),
+            "Not allowed to view the lockups of {account_id}"
+        );
+    }
+}
+
+#[near_bindgen]
+impl PrivacyApi for Contract {
+    #[payable]
+    fn set_viewing_key(&mut self, public_key: Option<PublicKey>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        if let Some(public_key) = public_key {
+            assert_eq!(
+                public_key.curve_type(),
+                CurveType::ED25519,
+                "viewing key must be an ed25519 key"
+            );
+            self.viewing_keys.insert(&account_id, &public_key);
+        } else {
+            self.viewing_keys.remove(&account_id);
+        }
+    }
+
+    #[payable]
+    fn add_to_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        for account_id in &account_ids {
+            self.privacy_viewers_whitelist.insert(account_id);
+        }
+    }
+
+    #[payable]
+    fn remove_from_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_manager();
+        for account_id in &account_ids {
+            self.privacy_viewers_whitelist.remove(account_id);
+        }
+    }
+}
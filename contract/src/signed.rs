@@ -0,0 +1,94 @@
+use hodl_model::{
+    signed::{signed_action_message, AdminOperation, SignedAction, SignedApi},
+    util::current_timestamp_sec,
+    WrappedBalance,
+};
+use near_sdk::{assert_one_yocto, env, json_types::Base64VecU8, near_bindgen, CurveType, PromiseOrValue, PublicKey};
+
+use crate::{
+    event::{emit, EventKind, FtLockupExecuteSignedAction},
+    Contract, ContractExt,
+};
+
+impl Contract {
+    /// Checks the action is signed by the manager signing key, isn't expired and isn't replayed,
+    /// then advances the nonce of the manager.
+    fn internal_verify_signed_action(&mut self, action: &SignedAction, signature: &Base64VecU8) {
+        let signing_key = self
+            .manager_signing_key
+            .as_ref()
+            .expect("manager signing key is not set");
+        assert!(action.expires_at >= current_timestamp_sec(), "signed action expired");
+        let signature: [u8; 64] = signature.0.as_slice().try_into().expect("invalid signature length");
+        let public_key: [u8; 32] = signing_key.as_bytes()[1..].try_into().unwrap();
+        let message = signed_action_message(&env::current_account_id(), action);
+        assert!(
+            env::ed25519_verify(&signature, &message, &public_key),
+            "invalid signature"
+        );
+        let nonce = self.signed_action_nonces.get(&self.manager).unwrap_or(0);
+        assert!(
+            action.nonce > nonce,
+            "signed action nonce should be greater than {nonce}"
+        );
+        self.signed_action_nonces.insert(&self.manager, &action.nonce);
+    }
+}
+
+#[near_bindgen]
+impl SignedApi for Contract {
+    #[payable]
+    fn set_manager_signing_key(&mut self, public_key: Option<PublicKey>) {
+        assert_one_yocto();
+        self.assert_manager();
+        if let Some(public_key) = &public_key {
+            assert_eq!(
+                public_key.curve_type(),
+                CurveType::ED25519,
+                "signing key must be an ed25519 key"
+            );
+        }
+        self.manager_signing_key = public_key;
+    }
+
+    #[payable]
+    fn execute_signed(&mut self, action: SignedAction, signature: Base64VecU8) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        self.internal_verify_signed_action(&action, &signature);
+        emit(EventKind::FtLockupExecuteSignedAction(FtLockupExecuteSignedAction {
+            nonce: action.nonce,
+            relayer_id: env::predecessor_account_id(),
+        }));
+
+        match action.operation {
+            AdminOperation::AddToDepositWhitelist { account_ids } => {
+                self.internal_add_to_deposit_whitelist(account_ids);
+            }
+            AdminOperation::RemoveFromDepositWhitelist { account_ids, reason } => {
+                self.internal_remove_from_deposit_whitelist(account_ids, reason);
+            }
+            AdminOperation::AddToDraftOperatorsWhitelist { account_ids } => {
+                self.internal_add_to_draft_operators_whitelist(account_ids);
+            }
+            AdminOperation::RemoveFromDraftOperatorsWhitelist { account_ids, reason } => {
+                self.internal_remove_from_draft_operators_whitelist(account_ids, reason);
+            }
+            AdminOperation::Terminate {
+                lockup_index,
+                termination_timestamp,
+                expected_unvested,
+                reason,
+            } => {
+                self.assert_terminations_not_paused();
+                return self.internal_terminate(
+                    lockup_index,
+                    termination_timestamp,
+                    expected_unvested,
+                    self.manager.clone(),
+                    reason,
+                );
+            }
+        }
+        PromiseOrValue::Value(0.into())
+    }
+}
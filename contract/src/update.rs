@@ -0,0 +1,92 @@
+use hodl_model::update::UpdateApi;
+use near_sdk::{assert_one_yocto, env, json_types::Base58CryptoHash, near_bindgen, AccountId, Gas, NearToken, Promise};
+
+use crate::{Contract, ContractExt, PromiseOrValue};
+
+const GAS_FOR_MIGRATE: Gas = Gas::from_gas(100_000_000_000_000);
+
+impl Contract {
+    pub(crate) fn assert_update_guardian(&self) {
+        assert!(
+            self.update_guardians.contains(&env::predecessor_account_id()),
+            "Not an update guardian"
+        );
+    }
+}
+
+#[near_bindgen]
+impl UpdateApi for Contract {
+    fn update_contract(&mut self) -> PromiseOrValue<()> {
+        assert_eq!(
+            Some(env::predecessor_account_id()),
+            self.multisig,
+            "Only multisig account can update the contract"
+        );
+        let code = env::input().expect("Error: No input");
+        // failing closed, no code can be deployed until guardians are configured and approve it,
+        // an approval is consumed by the update
+        assert!(
+            self.approved_code_hashes.remove(&env::sha256_array(&code)),
+            "The code hash is not approved by the update guardians"
+        );
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                "migrate".to_string(),
+                vec![],
+                NearToken::from_yoctonear(0),
+                GAS_FOR_MIGRATE,
+            )
+            .into()
+    }
+
+    fn set_multisig(&mut self, multisig: AccountId) {
+        self.assert_account_can_update();
+        self.multisig = Some(multisig);
+    }
+
+    #[payable]
+    fn add_update_guardians(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        // the manager can only bootstrap the guardians, so a manager key can't approve its own code
+        if self.update_guardians.is_empty() {
+            self.assert_manager();
+        } else {
+            self.assert_update_guardian();
+        }
+        for account_id in &account_ids {
+            self.update_guardians.insert(account_id);
+        }
+    }
+
+    #[payable]
+    fn remove_update_guardians(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_update_guardian();
+        for account_id in &account_ids {
+            self.update_guardians.remove(account_id);
+        }
+        assert!(
+            !self.update_guardians.is_empty(),
+            "At least one update guardian is required"
+        );
+    }
+
+    #[payable]
+    fn approve_code_hash(&mut self, code_hash: Base58CryptoHash) {
+        assert_one_yocto();
+        self.assert_update_guardian();
+        self.approved_code_hashes.insert(&code_hash.into());
+    }
+
+    #[payable]
+    fn revoke_code_hash(&mut self, code_hash: Base58CryptoHash) {
+        assert_one_yocto();
+        self.assert_update_guardian();
+        assert!(
+            self.approved_code_hashes.remove(&code_hash.into()),
+            "The code hash is not approved"
+        );
+    }
+}
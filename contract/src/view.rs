@@ -1,12 +1,45 @@
+use std::collections::{HashMap, HashSet};
+
 use hodl_model::{
-    draft::{DraftGroupIndex, DraftGroupView, DraftIndex, DraftView},
-    lockup::{LockupIndex, LockupView},
-    schedule::Schedule,
+    airdrop::{Airdrop, AirdropIndex},
+    config::Config,
+    draft::{
+        DraftConversionIssue, DraftGroup, DraftGroupConversionReport, DraftGroupIndex, DraftGroupStatus,
+        DraftGroupStatusView, DraftGroupView, DraftIndex, DraftView, PendingOperatorActions,
+    },
+    extension::ScheduleExtension,
+    fee::FeeConfig,
+    ft_message::{DraftGroupFunding, FtMessage},
+    gas::GasConfig,
+    grant::{GrantIndex, GrantView},
+    lockup::{
+        AccountLockupsPage, LockupCreate, LockupId, LockupIndex, LockupSortBy, LockupStatus, LockupView, UnlockRate,
+    },
+    merkle::{LockupLeaf, LockupsMerkleRoot},
+    note::LockupNote,
+    payout::PayoutAddress,
+    privacy::ViewAuth,
+    provenance::{Deposit, DepositIndex, FundingSource},
+    remap::AccountRemap,
+    revocation::UnclaimedRevocation,
+    schedule::{CheckpointView, Schedule, ScheduleView},
+    signed::{signed_action_message, SignedAction},
+    stats::{
+        EmissionBucket, Health, NextIndices, SolvencyReport, TagStats, TokenFlows, UpcomingUnlock,
+        DEFAULT_UPCOMING_UNLOCKS_LIMIT,
+    },
+    termination::{TerminationConfig, TerminationRecord, VestingConditions},
+    util::current_timestamp_sec,
     view_api::LockupViewApi,
-    WrappedBalance,
+    Balance, TimestampSec, WrappedBalance,
 };
 
-use crate::{near_bindgen, AccountId, Base58CryptoHash, Contract, ContractExt, Into, VERSION};
+use crate::{
+    env,
+    event::{EventLog, EVENT_LOG_SIZE},
+    near_bindgen, AccountId, Base58CryptoHash, Base64VecU8, Contract, ContractExt, Into, PublicKey,
+    EVENT_STANDARD_VERSION, VERSION,
+};
 
 #[near_bindgen]
 impl LockupViewApi for Contract {
@@ -14,21 +47,197 @@ impl LockupViewApi for Contract {
         self.token_account_id.clone()
     }
 
-    fn get_account_lockups(&self, account_id: AccountId) -> Vec<(LockupIndex, LockupView)> {
-        self.internal_get_account_lockups(&account_id)
+    fn get_token_decimals(&self) -> Option<u8> {
+        self.token_decimals
+    }
+
+    fn get_account_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.assert_public_details();
+        self.internal_get_account_lockup_views(&account_id, humanized, at_timestamp)
+    }
+
+    fn get_account_lockups_paged(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        sort_by: Option<LockupSortBy>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> AccountLockupsPage {
+        self.assert_public_details();
+        let mut lockups = self.internal_get_account_lockups(&account_id);
+        let total = u32::try_from(lockups.len()).unwrap();
+        sort_by
+            .unwrap_or_default()
+            .sort(&mut lockups, at_timestamp.unwrap_or_else(current_timestamp_sec));
+        let lockups = lockups
             .into_iter()
-            .map(|(lockup_index, lockup)| (lockup_index, lockup.into()))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|(lockup_index, lockup)| {
+                (
+                    lockup_index,
+                    self.internal_lockup_view(lockup_index, lockup, humanized, at_timestamp),
+                )
+            })
+            .collect();
+        AccountLockupsPage { lockups, total }
+    }
+
+    fn get_account_terminated_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.assert_public_details();
+        self.account_terminated_lockups
+            .get(&account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|index| {
+                self.get_lockup(index, humanized, at_timestamp)
+                    .map(|lockup| (index, lockup))
+            })
+            .collect()
+    }
+
+    fn get_accounts_lockups(
+        &self,
+        account_ids: Vec<AccountId>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> HashMap<AccountId, Vec<(LockupIndex, LockupView)>> {
+        self.assert_public_details();
+        account_ids
+            .into_iter()
+            .map(|account_id| {
+                let lockups = self.get_account_lockups(account_id.clone(), humanized, at_timestamp);
+                (account_id, lockups)
+            })
             .collect()
     }
 
-    fn get_lockup(&self, index: LockupIndex) -> Option<LockupView> {
-        self.lockups.get(u64::from(index)).map(Into::into)
+    fn get_lockup(
+        &self,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<LockupView> {
+        self.assert_public_details();
+        self.internal_get_lockup(index)
+            .map(|lockup| self.internal_lockup_view(index, lockup, humanized, at_timestamp))
     }
 
-    fn get_lockups(&self, indices: Vec<LockupIndex>) -> Vec<(LockupIndex, LockupView)> {
+    fn get_account_lockups_signed(
+        &self,
+        auth: ViewAuth,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.assert_view_auth(&auth, &account_id);
+        self.internal_get_account_lockup_views(&account_id, humanized, at_timestamp)
+    }
+
+    fn get_lockup_signed(
+        &self,
+        auth: ViewAuth,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<LockupView> {
+        let lockup = self.internal_get_lockup(index)?;
+        self.assert_view_auth(&auth, &lockup.account_id);
+        Some(self.internal_lockup_view(index, lockup, humanized, at_timestamp))
+    }
+
+    fn get_lockup_id(&self, index: LockupIndex) -> Option<LockupId> {
+        (u64::from(index) < self.lockups.len()).then(|| self.internal_lockup_id(index))
+    }
+
+    fn get_lockup_note_history(&self, index: LockupIndex) -> Vec<LockupNote> {
+        self.assert_public_details();
+        self.lockup_notes.get(&index).unwrap_or_default()
+    }
+
+    fn get_lockup_index(&self, lockup_id: LockupId) -> Option<LockupIndex> {
+        self.internal_lockup_index(lockup_id)
+    }
+
+    fn get_lockup_status(&self, index: LockupIndex) -> Option<LockupStatus> {
+        self.assert_public_details();
+        (u64::from(index) < self.lockups.len()).then(|| self.internal_lockup_status(index))
+    }
+
+    fn get_lockup_by_id(
+        &self,
+        lockup_id: LockupId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<(LockupIndex, LockupView)> {
+        self.internal_lockup_index(lockup_id).and_then(|index| {
+            self.get_lockup(index, humanized, at_timestamp)
+                .map(|lockup| (index, lockup))
+        })
+    }
+
+    fn get_schedule(&self, lockup_index: LockupIndex, humanize: bool) -> Option<ScheduleView> {
+        self.assert_public_details();
+        let lockup = self.internal_get_lockup(lockup_index)?;
+        // raw units until the token metadata is refreshed
+        let decimals = humanize.then(|| self.token_decimals.unwrap_or(0));
+        let checkpoints = |schedule: Schedule| -> Vec<CheckpointView> {
+            schedule
+                .0
+                .into_iter()
+                .map(|checkpoint| {
+                    let view = CheckpointView::from(checkpoint);
+                    match decimals {
+                        Some(decimals) => view.humanize(decimals),
+                        None => view,
+                    }
+                })
+                .collect()
+        };
+        let vesting_schedule = match lockup.termination_config {
+            Some(TerminationConfig {
+                vesting_schedule: VestingConditions::Schedule(schedule),
+                ..
+            }) => Some(checkpoints(schedule)),
+            _ => None,
+        };
+        Some(ScheduleView {
+            schedule: checkpoints(lockup.schedule),
+            curve: lockup.curve,
+            vesting_schedule,
+        })
+    }
+
+    fn get_unlock_rate(&self, lockup_index: LockupIndex, at_timestamp: Option<TimestampSec>) -> Option<UnlockRate> {
+        self.assert_public_details();
+        self.internal_get_lockup(lockup_index)
+            .map(|lockup| lockup.unlock_rate(at_timestamp.unwrap_or_else(current_timestamp_sec)))
+    }
+
+    fn get_lockups(
+        &self,
+        indices: Vec<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
         indices
             .into_iter()
-            .filter_map(|index| self.get_lockup(index).map(|lockup| (index, lockup)))
+            .filter_map(|index| {
+                self.get_lockup(index, humanized, at_timestamp)
+                    .map(|lockup| (index, lockup))
+            })
             .collect()
     }
 
@@ -40,22 +249,227 @@ impl LockupViewApi for Contract {
         &self,
         from_index: Option<LockupIndex>,
         limit: Option<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
     ) -> Vec<(LockupIndex, LockupView)> {
         let from_index = from_index.unwrap_or(0);
         let limit = limit.unwrap_or(self.get_num_lockups());
         (from_index..std::cmp::min(self.get_num_lockups(), limit))
-            .filter_map(|index| self.get_lockup(index).map(|lockup| (index, lockup)))
+            .filter_map(|index| {
+                self.get_lockup(index, humanized, at_timestamp)
+                    .map(|lockup| (index, lockup))
+            })
             .collect()
     }
 
+    fn get_lockups_by_tag(
+        &self,
+        tag: String,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.assert_public_details();
+        let Some(tag_lockups) = self.tag_lockups.get(&tag) else {
+            return vec![];
+        };
+        let indices = tag_lockups.as_vector();
+        let from_index = u64::from(from_index.unwrap_or(0));
+        let to_index = limit.map_or(indices.len(), |limit| {
+            indices.len().min(from_index.saturating_add(u64::from(limit)))
+        });
+        let mut page: Vec<LockupIndex> = (from_index..to_index)
+            .filter_map(|position| indices.get(position))
+            .collect();
+        page.sort_unstable();
+        page.into_iter()
+            .filter_map(|index| {
+                self.get_lockup(index, humanized, at_timestamp)
+                    .map(|lockup| (index, lockup))
+            })
+            .collect()
+    }
+
+    fn get_num_grants(&self) -> u32 {
+        self.grants.len().try_into().unwrap()
+    }
+
+    fn get_grant(
+        &self,
+        grant_id: GrantIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<GrantView> {
+        self.grants.get(u64::from(grant_id)).map(|lockup_ids| {
+            lockup_ids
+                .into_iter()
+                .filter_map(|index| {
+                    self.get_lockup(index, humanized, at_timestamp)
+                        .map(|lockup| (index, lockup))
+                })
+                .collect::<Vec<_>>()
+                .into()
+        })
+    }
+
+    fn get_unclaimed_revocation(&self, lockup_index: LockupIndex) -> Option<UnclaimedRevocation> {
+        self.assert_public_details();
+        self.unclaimed_revocations.get(&lockup_index)
+    }
+
+    fn verify_agreement(&self, lockup_index: LockupIndex, preimage: Base64VecU8) -> bool {
+        self.lockup_agreements
+            .get(&lockup_index)
+            .is_some_and(|agreement_hash| env::sha256_array(&preimage.0) == agreement_hash)
+    }
+
+    fn get_tag_stats(&self, tag: String, at_timestamp: Option<TimestampSec>) -> TagStats {
+        self.assert_public_details();
+        let timestamp = at_timestamp.unwrap_or_else(current_timestamp_sec);
+        let mut stats = TagStats::default();
+        let Some(indices) = self.tag_lockups.get(&tag) else {
+            return stats;
+        };
+        for index in indices.iter() {
+            let lockup = LockupView::at_timestamp(self.internal_get_lockup(index).unwrap(), timestamp);
+            stats.num_lockups += 1;
+            stats.total_balance += lockup.total_balance;
+            stats.claimed_balance += lockup.claimed_balance;
+            stats.unclaimed_balance += lockup.unclaimed_balance;
+        }
+        stats
+    }
+
+    fn get_top_upcoming_unlocks(
+        &self,
+        window_sec: TimestampSec,
+        limit: Option<u32>,
+        from_index: Option<LockupIndex>,
+        lockups_limit: Option<LockupIndex>,
+    ) -> Vec<UpcomingUnlock> {
+        let timestamp = current_timestamp_sec();
+        let window_end = timestamp.saturating_add(window_sec);
+        let unlocks = self.internal_lockup_range(from_index, lockups_limit).map(|index| {
+            let lockup = self.internal_get_lockup(index).unwrap();
+            let amount = lockup.unlocked_balance(window_end) - lockup.unlocked_balance(timestamp);
+            (lockup.account_id, amount)
+        });
+        UpcomingUnlock::top(
+            unlocks,
+            limit.unwrap_or(DEFAULT_UPCOMING_UNLOCKS_LIMIT) as usize,
+            self.config.anonymize_upcoming_unlocks || self.config.privacy_mode,
+        )
+    }
+
+    fn get_emission_schedule(
+        &self,
+        bucket_sec: TimestampSec,
+        from_ts: TimestampSec,
+        to_ts: TimestampSec,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> Vec<EmissionBucket> {
+        let mut buckets = EmissionBucket::split(bucket_sec, from_ts, to_ts).unwrap_or_else(|error| panic!("{error}"));
+        for index in self.internal_lockup_range(from_index, limit) {
+            let lockup = self.internal_get_lockup(index).unwrap();
+            EmissionBucket::add_unlocks(&mut buckets, |timestamp| lockup.unlocked_balance(timestamp));
+        }
+        buckets
+    }
+
     fn get_deposit_whitelist(&self) -> Vec<AccountId> {
         self.deposit_whitelist.to_vec()
     }
 
+    fn get_update_guardians(&self) -> Vec<AccountId> {
+        self.update_guardians.to_vec()
+    }
+
+    fn get_approved_code_hashes(&self) -> Vec<Base58CryptoHash> {
+        self.approved_code_hashes.iter().map(Into::into).collect()
+    }
+
     fn get_draft_operators_whitelist(&self) -> Vec<AccountId> {
         self.draft_operators_whitelist.to_vec()
     }
 
+    fn get_payer_whitelist(&self) -> Vec<AccountId> {
+        self.payer_whitelist.to_vec()
+    }
+
+    fn get_lien_holders_whitelist(&self) -> Vec<AccountId> {
+        self.lien_holders_whitelist.to_vec()
+    }
+
+    fn get_privacy_viewers_whitelist(&self) -> Vec<AccountId> {
+        self.privacy_viewers_whitelist.to_vec()
+    }
+
+    fn get_vesting_oracles_whitelist(&self) -> Vec<AccountId> {
+        self.vesting_oracles_whitelist.to_vec()
+    }
+
+    fn get_viewing_key(&self, account_id: AccountId) -> Option<PublicKey> {
+        self.viewing_keys.get(&account_id)
+    }
+
+    fn get_manager_signing_key(&self) -> Option<PublicKey> {
+        self.manager_signing_key.clone()
+    }
+
+    fn get_signed_action_nonce(&self) -> u64 {
+        self.signed_action_nonces.get(&self.manager).unwrap_or(0)
+    }
+
+    fn get_signed_action_message(&self, action: SignedAction) -> Base64VecU8 {
+        signed_action_message(&env::current_account_id(), &action).into()
+    }
+
+    fn is_attested(&self, account_id: AccountId) -> bool {
+        self.attested_accounts.contains(&account_id)
+    }
+
+    fn is_blocked(&self, account_id: AccountId) -> bool {
+        self.blocked_accounts.contains(&account_id)
+    }
+
+    fn get_payout_address(&self, account_id: AccountId) -> Option<PayoutAddress> {
+        self.assert_public_details();
+        self.payout_addresses.get(&account_id)
+    }
+
+    fn get_account_remap(&self, old_account_id: AccountId) -> Option<AccountRemap> {
+        self.assert_public_details();
+        self.account_remaps.get(&old_account_id)
+    }
+
+    fn get_schedule_extension(&self) -> Option<ScheduleExtension> {
+        self.schedule_extension.clone()
+    }
+
+    fn get_num_airdrops(&self) -> u32 {
+        self.airdrops.len().try_into().unwrap()
+    }
+
+    fn get_airdrop(&self, airdrop_id: AirdropIndex) -> Option<Airdrop> {
+        self.airdrops.get(u64::from(airdrop_id))
+    }
+
+    fn is_airdrop_lockup_registered(&self, airdrop_id: AirdropIndex, leaf_index: u32) -> bool {
+        self.airdrop_registrations.contains(&(airdrop_id, leaf_index))
+    }
+
+    fn get_lockups_merkle_root(&self) -> Option<LockupsMerkleRoot> {
+        self.lockups_merkle_root.clone()
+    }
+
+    fn get_lockup_leaf(&self, index: LockupIndex) -> Option<LockupLeaf> {
+        self.assert_public_details();
+        self.internal_get_lockup(index)
+            .map(|lockup| LockupLeaf::new(index, &lockup))
+    }
+
     fn hash_schedule(&self, schedule: Schedule) -> Base58CryptoHash {
         schedule.hash().into()
     }
@@ -73,6 +487,23 @@ impl LockupViewApi for Contract {
         }
     }
 
+    fn build_schedule_with_cliff(
+        &self,
+        total_balance: WrappedBalance,
+        initial_balance: WrappedBalance,
+        start_timestamp: TimestampSec,
+        cliff_timestamp: TimestampSec,
+        finish_timestamp: TimestampSec,
+    ) -> Schedule {
+        Schedule::new_with_cliff(
+            total_balance.0,
+            initial_balance.0,
+            start_timestamp,
+            cliff_timestamp,
+            finish_timestamp,
+        )
+    }
+
     fn get_next_draft_group_id(&self) -> DraftGroupIndex {
         self.next_draft_group_id
     }
@@ -81,20 +512,75 @@ impl LockupViewApi for Contract {
         self.next_draft_id
     }
 
+    fn get_next_indices(&self) -> NextIndices {
+        NextIndices {
+            lockup_id: self.get_num_lockups(),
+            draft_id: self.next_draft_id,
+            draft_group_id: self.next_draft_group_id,
+        }
+    }
+
+    fn get_lockups_count(&self) -> u32 {
+        self.get_num_lockups()
+    }
+
+    fn get_drafts_count(&self) -> u32 {
+        self.num_drafts
+    }
+
+    fn get_draft_groups_count(&self) -> u32 {
+        self.get_num_draft_groups()
+    }
+
+    fn get_drafts_pending_account(&self) -> Vec<DraftIndex> {
+        self.drafts_pending_account.to_vec()
+    }
+
     fn get_num_draft_groups(&self) -> u32 {
         self.draft_groups.len().try_into().unwrap()
     }
 
     fn get_draft_group(&self, index: DraftGroupIndex) -> Option<DraftGroupView> {
+        self.assert_public_details();
         self.draft_groups.get(&index as _).map(Into::into)
     }
 
+    fn get_draft_group_status(&self, draft_group_id: DraftGroupIndex) -> Option<DraftGroupStatusView> {
+        self.assert_public_details();
+        self.draft_groups
+            .get(&draft_group_id as _)
+            .or_else(|| self.closed_draft_groups.get(&draft_group_id))
+            .map(Into::into)
+    }
+
+    fn encode_create_lockup_msg(&self, spec: LockupCreate) -> String {
+        spec.assert_valid();
+        // the payer isn't known in a view, it doesn't affect the validation
+        spec.into_lockup(&env::current_account_id())
+            .assert_new_valid(spec.schedule.total_balance());
+        self.assert_unlocked_at_creation(&spec);
+        FtMessage::LockupCreate(spec).to_msg()
+    }
+
+    fn encode_fund_draft_group_msg(&self, draft_group_id: DraftGroupIndex, try_convert: Option<bool>) -> String {
+        assert!(
+            self.draft_groups.get(&draft_group_id as _).is_some(),
+            "draft group not found"
+        );
+        FtMessage::DraftGroupFunding(DraftGroupFunding {
+            draft_group_id,
+            try_convert,
+        })
+        .to_msg()
+    }
+
     fn get_draft_groups_paged(
         &self,
         // not the draft_id, but internal index used inside the LookupMap struct
         from_index: Option<DraftGroupIndex>,
         to_index: Option<DraftGroupIndex>,
     ) -> Vec<(DraftGroupIndex, DraftGroupView)> {
+        self.assert_public_details();
         let from_index = from_index.unwrap_or(0);
         let to_index = to_index.unwrap_or(self.draft_groups.len().try_into().unwrap());
         let keys = self.draft_groups.keys_as_vector();
@@ -109,18 +595,219 @@ impl LockupViewApi for Contract {
             .collect()
     }
 
+    fn get_pending_operator_actions(&self) -> PendingOperatorActions {
+        self.assert_public_details();
+        let mut actions = PendingOperatorActions::default();
+        for (draft_group_id, draft_group) in self.draft_groups.iter() {
+            actions.add(draft_group_id, &draft_group);
+        }
+        actions
+    }
+
     fn get_draft(&self, index: DraftIndex) -> Option<DraftView> {
+        self.assert_public_details();
         self.drafts.get(&index as _).map(Into::into)
     }
 
     fn get_drafts(&self, indices: Vec<DraftIndex>) -> Vec<(DraftIndex, DraftView)> {
+        self.assert_public_details();
         indices
             .into_iter()
             .filter_map(|index| self.get_draft(index).map(|draft| (index, draft)))
             .collect()
     }
 
+    fn validate_draft_group_conversion(&self, draft_group_id: DraftGroupIndex) -> DraftGroupConversionReport {
+        let mut report = DraftGroupConversionReport {
+            draft_group_id,
+            group_issues: vec![],
+            draft_issues: vec![],
+            draft_warnings: vec![],
+        };
+        let Some(draft_group) = self.draft_groups.get(&draft_group_id as _) else {
+            report.group_issues.push("draft group not found".to_string());
+            return report;
+        };
+        if !matches!(draft_group.status, DraftGroupStatus::Funded | DraftGroupStatus::Converting) {
+            report
+                .group_issues
+                .push(format!("draft group is {:?}", draft_group.status));
+        }
+
+        let current_timestamp = current_timestamp_sec();
+        let mut draft_ids: Vec<DraftIndex> = draft_group.draft_indices.into_iter().collect();
+        draft_ids.sort_unstable();
+        let mut account_ids: HashSet<AccountId> = HashSet::new();
+        let mut total_amount: Balance = 0;
+        for draft_id in draft_ids {
+            let issue = |reason: String| DraftConversionIssue { draft_id, reason };
+            let Some(draft) = self.drafts.get(&draft_id as _) else {
+                report.draft_issues.push(issue("draft not found".to_string()));
+                continue;
+            };
+            if draft.draft_group_id != draft_group_id {
+                report
+                    .draft_issues
+                    .push(issue(format!("draft belongs to draft group {}", draft.draft_group_id)));
+            }
+            if let Err(error) = draft.validate_new() {
+                report.draft_issues.push(issue(error));
+            }
+            // backdated vesting is valid
+            if draft.lockup_create.schedule.0.first().unwrap().timestamp < current_timestamp {
+                report
+                    .draft_warnings
+                    .push(issue("schedule starts in the past".to_string()));
+            }
+            // several lockups for one account are allowed by the draft format
+            for account_id in draft.account_ids() {
+                if !account_ids.insert(account_id.clone()) {
+                    report.draft_warnings.push(issue(format!(
+                        "another lockup for account {account_id} in the draft group"
+                    )));
+                }
+            }
+            total_amount = total_amount.saturating_add(draft.total_balance());
+        }
+        if total_amount != draft_group.total_amount {
+            report.group_issues.push(format!(
+                "drafts total amount {total_amount} doesn't match the draft group total amount {}",
+                draft_group.total_amount
+            ));
+        }
+
+        report
+    }
+
+    fn get_num_terminations(&self) -> u32 {
+        self.terminations.len().try_into().unwrap()
+    }
+
+    fn get_num_deposits(&self) -> u32 {
+        self.deposits.len().try_into().unwrap()
+    }
+
+    fn get_deposit(&self, deposit_id: DepositIndex) -> Option<Deposit> {
+        self.assert_public_details();
+        self.deposits.get(u64::from(deposit_id))
+    }
+
+    fn get_lockup_pool_balance(&self, account_id: AccountId) -> WrappedBalance {
+        self.assert_public_details();
+        self.lockup_pools
+            .get(&account_id)
+            .map_or(0, |pool| pool.balance())
+            .into()
+    }
+
+    fn get_lockup_funding(&self, index: LockupIndex) -> Vec<FundingSource> {
+        self.assert_public_details();
+        self.lockup_funding
+            .get(&index)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|share| {
+                let deposit = self
+                    .deposits
+                    .get(u64::from(share.deposit_id))
+                    .expect("deposit not found");
+                FundingSource::new(share.deposit_id, deposit, share.amount)
+            })
+            .collect()
+    }
+
+    fn get_draft_group_funding(&self, draft_group_id: DraftGroupIndex) -> Option<FundingSource> {
+        self.assert_public_details();
+        self.draft_group_deposits.get(&draft_group_id).map(|deposit_id| {
+            let deposit = self.deposits.get(u64::from(deposit_id)).expect("deposit not found");
+            let amount = deposit.amount;
+            FundingSource::new(deposit_id, deposit, amount)
+        })
+    }
+
+    fn get_terminations(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<TerminationRecord> {
+        self.assert_public_details();
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(self.get_num_terminations());
+        let to_index = std::cmp::min(self.get_num_terminations(), from_index.saturating_add(limit));
+        (from_index..to_index)
+            .filter_map(|index| self.terminations.get(u64::from(index)))
+            .collect()
+    }
+
+    fn get_token_flows(&self) -> TokenFlows {
+        self.token_flows.clone()
+    }
+
+    fn check_solvency(
+        &self,
+        contract_balance: WrappedBalance,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> SolvencyReport {
+        let unconverted_balance = self
+            .draft_groups
+            .values()
+            .filter(DraftGroup::is_funded)
+            .map(|draft_group| draft_group.funded_amount - draft_group.converted_amount)
+            .chain(self.airdrops.iter().map(|airdrop| airdrop.unregistered_balance()))
+            .chain(self.lockup_pools.values().map(|pool| pool.balance()))
+            .sum();
+        SolvencyReport::new(
+            self.internal_unclaimed_balance(from_index, limit),
+            unconverted_balance,
+            contract_balance.0,
+        )
+    }
+
+    fn get_next_event_seq(&self) -> u64 {
+        EventLog::new().next_seq()
+    }
+
+    fn get_event_log_enabled(&self) -> bool {
+        EventLog::new().is_enabled()
+    }
+
+    fn get_events_since(&self, seq: u64, limit: Option<u32>) -> Vec<(u64, String)> {
+        // the events carry the lockup details, they're replayed only while the details are public
+        self.assert_public_details();
+        EventLog::new().events_since(seq, limit.map_or(EVENT_LOG_SIZE, u64::from))
+    }
+
+    fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+
+    fn get_gas_config(&self) -> GasConfig {
+        self.config.gas
+    }
+
+    fn get_fee_config(&self) -> FeeConfig {
+        self.config.fee_config.clone()
+    }
+
+    fn get_remaining_claim_quota(&self) -> Option<WrappedBalance> {
+        self.internal_remaining_claim_quota().map(Into::into)
+    }
+
     fn get_version(&self) -> String {
         VERSION.into()
     }
+
+    fn event_version(&self) -> String {
+        EVENT_STANDARD_VERSION.into()
+    }
+
+    fn health(&self) -> Health {
+        Health {
+            token_account_id: self.token_account_id.clone(),
+            paused: self.config.pause_flags.is_any(),
+            version: VERSION.into(),
+            num_lockups: self.get_num_lockups(),
+            num_grants: self.get_num_grants(),
+            num_drafts: self.num_drafts,
+            num_draft_groups: self.get_num_draft_groups(),
+            num_terminations: self.get_num_terminations(),
+        }
+    }
 }
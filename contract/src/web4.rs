@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use hodl_model::{
+    schedule::Schedule,
+    util::{format_balance, format_timestamp},
+    view_api::LockupViewApi,
+    web4::{escape_html, Web4Api, Web4Request, Web4Response},
+    Balance,
+};
+use near_sdk::{json_types::U128, near_bindgen, serde_json::json, AccountId};
+
+use crate::{Contract, ContractExt};
+
+/// The most lockups summed into the total locked balance of the summary, it's omitted for more lockups
+/// to fit into the view gas limit.
+const WEB4_MAX_SUMMED_LOCKUPS: u32 = 1_000;
+
+impl Contract {
+    fn web4_total_locked(&self) -> Option<Balance> {
+        (self.get_num_lockups() <= WEB4_MAX_SUMMED_LOCKUPS).then(|| self.internal_unclaimed_balance(None, None))
+    }
+
+    fn web4_format_balance(&self, balance: Balance) -> String {
+        match self.token_decimals {
+            Some(decimals) => format_balance(balance, decimals),
+            None => balance.to_string(),
+        }
+    }
+
+    fn web4_page(&self, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><head><meta charset=\"UTF-8\"><title>{} lockups</title></head><body>\
+             <h1>Lockups of <a href=\"/\">{}</a></h1>{body}</body></html>",
+            self.token_account_id, self.token_account_id
+        )
+    }
+
+    fn web4_summary(&self) -> Web4Response {
+        let health = self.health();
+        let token_flows = self.get_token_flows();
+        let body = format!(
+            "<p>Total locked: {}</p><p>Lockups: {}, draft groups: {}, terminations: {}</p>\
+             <p>Deposited: {}, claimed: {}, refunded: {}, burned: {}, revoked: {}</p>\
+             <form action=\"/account\"><input name=\"account_id\" placeholder=\"alice.near\">\
+             <button type=\"submit\">Show lockups</button></form>",
+            self.web4_total_locked().map_or_else(
+                || "see check_solvency".to_string(),
+                |balance| self.web4_format_balance(balance)
+            ),
+            health.num_lockups,
+            health.num_draft_groups,
+            health.num_terminations,
+            self.web4_format_balance(token_flows.total_deposited),
+            self.web4_format_balance(token_flows.total_claimed),
+            self.web4_format_balance(token_flows.total_refunded),
+            self.web4_format_balance(token_flows.total_burned),
+            self.web4_format_balance(token_flows.total_revoked),
+        );
+        Web4Response::html(self.web4_page(&body))
+    }
+
+    fn web4_summary_json(&self) -> Web4Response {
+        let summary = json!({
+            "health": self.health(),
+            "total_locked": self.web4_total_locked().map(U128),
+            "token_flows": self.get_token_flows(),
+        });
+        Web4Response::json(summary.to_string())
+    }
+
+    fn web4_schedule(&self, schedule: &Schedule) -> String {
+        schedule.0.iter().fold(String::new(), |mut list, checkpoint| {
+            write!(
+                list,
+                "<li>{}: {}</li>",
+                format_timestamp(checkpoint.timestamp),
+                self.web4_format_balance(checkpoint.balance)
+            )
+            .unwrap();
+            list
+        })
+    }
+
+    fn web4_account(&self, request: &Web4Request) -> Web4Response {
+        let Some(account_id) = request
+            .query_param("account_id")
+            .and_then(|account_id| account_id.parse::<AccountId>().ok())
+        else {
+            return Web4Response::html(self.web4_page("<p>Invalid account id</p>")).with_status(400);
+        };
+
+        if self.config.privacy_mode {
+            return Web4Response::html(self.web4_page("<p>Lockup details are private</p>")).with_status(403);
+        }
+
+        let mut body = format!("<h2>{}</h2>", escape_html(account_id.as_str()));
+        let lockups = self.internal_get_account_lockups(&account_id);
+        if lockups.is_empty() {
+            body.push_str("<p>No lockups</p>");
+        }
+        for (lockup_index, lockup) in lockups {
+            let view = self.internal_lockup_view(lockup_index, lockup, None, None);
+            write!(
+                body,
+                "<h3>Lockup #{lockup_index}</h3><p>Total: {}, claimed: {}, unclaimed: {}</p><ul>{}</ul>",
+                self.web4_format_balance(view.total_balance),
+                self.web4_format_balance(view.claimed_balance),
+                self.web4_format_balance(view.unclaimed_balance),
+                self.web4_schedule(&view.schedule),
+            )
+            .unwrap();
+        }
+        Web4Response::html(self.web4_page(&body))
+    }
+}
+
+#[near_bindgen]
+impl Web4Api for Contract {
+    fn web4_get(&self, request: Web4Request) -> Web4Response {
+        match request.path.as_str() {
+            "" | "/" => self.web4_summary(),
+            "/summary.json" => self.web4_summary_json(),
+            "/account" => self.web4_account(&request),
+            _ => Web4Response::html(self.web4_page("<p>Not found</p>")).with_status(404),
+        }
+    }
+}
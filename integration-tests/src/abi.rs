@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use near_sdk::serde_json::{from_slice, Value};
+
+use crate::utils::load_wasm;
+
+/// Written next to the wasm by `make build-with-abi`, which requires cargo-near.
+const ABI_PATH: &str = "../res/hodl_lockup_abi.json";
+
+#[test]
+fn abi_contains_api_methods() -> Result<()> {
+    if !Path::new(ABI_PATH).exists() {
+        println!("Skipping the ABI check, run `make build-with-abi` to generate {ABI_PATH}");
+        return Ok(());
+    }
+    let abi: Value = from_slice(&load_wasm(ABI_PATH)?)?;
+
+    assert!(abi["schema_version"].is_string());
+    assert_eq!(abi["metadata"]["name"], "hodl-lockup");
+
+    let functions: Vec<&str> = abi["body"]["functions"]
+        .as_array()
+        .context("functions are expected")?
+        .iter()
+        .filter_map(|function| function["name"].as_str())
+        .collect();
+    for name in [
+        "new",
+        "claim",
+        "terminate",
+        "reveal_vesting_schedule",
+        "set_claim_escrow",
+        "set_event_log_enabled",
+        "get_lockup",
+        "get_schedule",
+        "get_lockups_count",
+        "get_events_since",
+    ] {
+        assert!(functions.contains(&name), "{name} is missing in the ABI");
+    }
+
+    let definitions = abi["body"]["root_schema"]["definitions"]
+        .as_object()
+        .context("definitions are expected")?;
+    for model_type in ["LockupView", "ScheduleView", "ClaimResult", "Config"] {
+        assert!(
+            definitions.contains_key(model_type),
+            "{model_type} is missing in the ABI"
+        );
+    }
+
+    Ok(())
+}
@@ -1,7 +1,13 @@
+mod abi;
 pub mod context;
 mod helper_test;
 mod lockup_interface;
 mod migrate_to_multisig;
 mod migration;
+mod packed_drafts;
+mod pending_email_lockup;
+mod reentrancy;
+mod schedule_checkpoints;
+mod termination;
 mod update;
 mod utils;
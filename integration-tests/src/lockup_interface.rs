@@ -1,16 +1,51 @@
 #![cfg(test)]
 
+use std::collections::HashMap;
+
 use integration_utils::{contract_call::ContractCall, integration_contract::IntegrationContract, misc::ToNear};
 use model::{
-    draft::{Draft, DraftGroupIndex, DraftGroupView, DraftIndex, DraftView},
-    lockup::{LockupIndex, LockupView},
+    airdrop::{Airdrop, AirdropApiIntegration, AirdropIndex},
+    config::{Config, FtOnTransferPolicy, TerminationPolicy},
+    draft::{
+        Draft, DraftGroupConversionReport, DraftGroupIndex, DraftGroupStatusView, DraftGroupView, DraftIndex,
+        DraftView, PendingOperatorActions,
+    },
+    escrow::EscrowApiIntegration,
+    extension::ScheduleExtension,
+    fee::{ClaimFee, FeeConfig},
+    gas::GasConfig,
+    grant::{GrantIndex, GrantView},
+    lien::LienApiIntegration,
+    lockup::{
+        AccountLockupsPage, ClaimResult, LockupCreate, LockupId, LockupIndex, LockupSortBy, LockupStatus, LockupView,
+        UnlockRate,
+    },
     lockup_api::LockupApiIntegration,
-    schedule::Schedule,
+    memo::MemoTemplates,
+    merkle::{LockupLeaf, LockupsMerkleRoot},
+    note::LockupNote,
+    payout::PayoutAddress,
+    pending::PendingLockupProof,
+    privacy::{PrivacyApiIntegration, ViewAuth},
+    provenance::{Deposit, DepositIndex, FundingSource},
+    remap::AccountRemap,
+    revocation::UnclaimedRevocation,
+    schedule::{Schedule, ScheduleView},
+    signed::{SignedAction, SignedApiIntegration},
+    stats::{EmissionBucket, Health, NextIndices, SolvencyReport, TagStats, TokenFlows, UpcomingUnlock},
+    termination::{TerminationBurn, TerminationRecord},
+    throttle::ClaimThrottle,
     update::UpdateApiIntegration,
     view_api::LockupViewApiIntegration,
+    web4::{Web4ApiIntegration, Web4Request, Web4Response},
     TimestampSec, WrappedBalance,
 };
-use near_sdk::{json_types::Base58CryptoHash, serde_json::json, AccountId};
+use near_sdk::{
+    borsh,
+    json_types::{Base58CryptoHash, Base64VecU8},
+    serde_json::json,
+    AccountId, PublicKey,
+};
 use near_workspaces::Contract;
 
 pub struct LockupContract<'a> {
@@ -35,10 +70,41 @@ impl LockupApiIntegration for LockupContract<'_> {
             .unwrap()
     }
 
-    fn claim(&mut self, amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>) -> ContractCall<WrappedBalance> {
+    fn reinit_after_state_wipe(
+        &self,
+        token_account_id: AccountId,
+        deposit_whitelist: Vec<AccountId>,
+        draft_operators_whitelist: Option<Vec<AccountId>>,
+    ) -> ContractCall<()> {
+        self.make_call("reinit_after_state_wipe")
+            .args_json(json!({
+                "token_account_id": token_account_id,
+                "deposit_whitelist": deposit_whitelist,
+                "draft_operators_whitelist": draft_operators_whitelist,
+            }))
+            .unwrap()
+    }
+
+    fn claim(
+        &mut self,
+        amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+        memo: Option<String>,
+        max_amounts: Option<bool>,
+    ) -> ContractCall<ClaimResult> {
         self.make_call("claim")
             .args_json(json!({
-                "amounts": amounts
+                "amounts": amounts,
+                "memo": memo,
+                "max_amounts": max_amounts,
+            }))
+            .unwrap()
+    }
+
+    fn claim_amount(&mut self, total: WrappedBalance, memo: Option<String>) -> ContractCall<ClaimResult> {
+        self.make_call("claim_amount")
+            .args_json(json!({
+                "total": total,
+                "memo": memo,
             }))
             .unwrap()
     }
@@ -46,14 +112,25 @@ impl LockupApiIntegration for LockupContract<'_> {
     fn terminate(
         &mut self,
         lockup_index: LockupIndex,
-        hashed_schedule: Option<Schedule>,
         termination_timestamp: Option<TimestampSec>,
+        expected_unvested: Option<WrappedBalance>,
+        reason: Option<String>,
     ) -> ContractCall<WrappedBalance> {
         self.make_call("terminate")
             .args_json(json!({
                 "lockup_index": lockup_index,
-                "hashed_schedule": hashed_schedule,
-                "termination_timestamp": termination_timestamp
+                "termination_timestamp": termination_timestamp,
+                "expected_unvested": expected_unvested,
+                "reason": reason,
+            }))
+            .unwrap()
+    }
+
+    fn reveal_vesting_schedule(&mut self, lockup_index: LockupIndex, schedule: Schedule) -> ContractCall<()> {
+        self.make_call("reveal_vesting_schedule")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "schedule": schedule,
             }))
             .unwrap()
     }
@@ -75,11 +152,13 @@ impl LockupApiIntegration for LockupContract<'_> {
         &mut self,
         account_id: Option<AccountId>,
         account_ids: Option<Vec<AccountId>>,
+        reason: Option<String>,
     ) -> ContractCall<()> {
         self.make_call("remove_from_deposit_whitelist")
             .args_json(json!({
                 "account_id": account_id,
                 "account_ids": account_ids,
+                "reason": reason,
             }))
             .unwrap()
     }
@@ -92,202 +171,1471 @@ impl LockupApiIntegration for LockupContract<'_> {
             .unwrap()
     }
 
-    fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+    fn remove_from_draft_operators_whitelist(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    ) -> ContractCall<()> {
         self.make_call("remove_from_draft_operators_whitelist")
             .args_json(json!({
-                "account_ids": account_ids
+                "account_ids": account_ids,
+                "reason": reason,
             }))
             .unwrap()
     }
 
-    fn create_draft_group(&mut self) -> ContractCall<DraftGroupIndex> {
-        self.make_call("create_draft_group")
+    fn warn_unclaimed_revocation(&mut self, lockup_index: LockupIndex) -> ContractCall<()> {
+        self.make_call("warn_unclaimed_revocation")
+            .args_json(json!({
+                "lockup_index": lockup_index
+            }))
+            .unwrap()
     }
 
-    fn create_draft(&mut self, draft: Draft) -> ContractCall<DraftIndex> {
-        self.make_call("create_draft")
+    fn claim_for_keeper(&mut self, lockup_index: LockupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("claim_for_keeper")
             .args_json(json!({
-                "draft": draft
+                "lockup_index": lockup_index
             }))
             .unwrap()
     }
 
-    fn create_drafts(&mut self, drafts: Vec<Draft>) -> ContractCall<Vec<DraftIndex>> {
-        self.make_call("create_drafts")
+    fn set_payout_address(&mut self, payout_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("set_payout_address")
             .args_json(json!({
-                "drafts": drafts
+                "payout_id": payout_id,
             }))
             .unwrap()
     }
 
-    fn convert_draft(&mut self, draft_id: DraftIndex) -> ContractCall<LockupIndex> {
-        self.make_call("convert_draft")
+    fn cancel_payout_address_change(&mut self) -> ContractCall<()> {
+        self.make_call("cancel_payout_address_change")
+    }
+
+    fn revoke_unclaimed(&mut self, lockup_index: LockupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("revoke_unclaimed")
             .args_json(json!({
-                "draft_id": draft_id
+                "lockup_index": lockup_index
             }))
             .unwrap()
     }
 
-    fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<()> {
-        self.make_call("discard_draft_group")
+    fn renounce_lockup(&mut self, lockup_index: LockupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("renounce_lockup")
             .args_json(json!({
-                "draft_group_id": draft_group_id
+                "lockup_index": lockup_index
             }))
             .unwrap()
     }
 
-    fn delete_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> ContractCall<()> {
-        self.make_call("delete_drafts")
+    fn claim_pending_lockup(&mut self, proof: PendingLockupProof) -> ContractCall<()> {
+        self.make_call("claim_pending_lockup")
             .args_json(json!({
-                "draft_ids": draft_ids
+                "proof": proof
             }))
             .unwrap()
     }
-}
 
-impl<'a> LockupViewApiIntegration for LockupContract<'a> {
-    fn get_token_account_id(&self) -> ContractCall<AccountId> {
-        self.make_call("get_token_account_id")
+    fn relock(&mut self, lockup_index: LockupIndex, extend_by_sec: TimestampSec) -> ContractCall<()> {
+        self.make_call("relock")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "extend_by_sec": extend_by_sec
+            }))
+            .unwrap()
     }
 
-    fn get_account_lockups(&self, account_id: AccountId) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
-        self.make_call("get_account_lockups")
+    fn set_lockup_note(&mut self, lockup_index: LockupIndex, note: String) -> ContractCall<()> {
+        self.make_call("set_lockup_note")
             .args_json(json!({
-                "account_id": account_id
+                "lockup_index": lockup_index,
+                "note": note,
             }))
             .unwrap()
     }
 
-    fn get_lockup(&self, index: LockupIndex) -> ContractCall<Option<LockupView>> {
-        self.make_call("get_lockup")
+    fn refresh_token_metadata(&mut self) -> ContractCall<Option<u8>> {
+        self.make_call("refresh_token_metadata")
+    }
+
+    fn reindex_account_lockups(&mut self, from_index: LockupIndex, limit: u32) -> ContractCall<Option<LockupIndex>> {
+        self.make_call("reindex_account_lockups")
             .args_json(json!({
-                "index": index
+                "from_index": from_index,
+                "limit": limit
             }))
             .unwrap()
     }
 
-    fn get_lockups(&self, indices: Vec<LockupIndex>) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
-        self.make_call("get_lockups")
+    fn set_event_log_enabled(&mut self, enabled: bool) -> ContractCall<()> {
+        self.make_call("set_event_log_enabled")
             .args_json(json!({
-                "indices": indices
+                "enabled": enabled,
             }))
             .unwrap()
     }
 
-    fn get_num_lockups(&self) -> ContractCall<u32> {
-        self.make_call("get_num_lockups")
+    fn set_event_batch_threshold(&mut self, event_batch_threshold: Option<u32>) -> ContractCall<()> {
+        self.make_call("set_event_batch_threshold")
+            .args_json(json!({
+                "event_batch_threshold": event_batch_threshold
+            }))
+            .unwrap()
     }
 
-    fn get_lockups_paged(
-        &self,
-        from_index: Option<LockupIndex>,
-        limit: Option<LockupIndex>,
-    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
-        self.make_call("get_lockups_paged")
+    fn set_account_factory(&mut self, account_factory_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("set_account_factory")
             .args_json(json!({
-                "from_index": from_index,
-                "limit": limit
+                "account_factory_id": account_factory_id
             }))
             .unwrap()
     }
 
-    fn get_deposit_whitelist(&self) -> ContractCall<Vec<AccountId>> {
-        self.make_call("get_deposit_whitelist")
+    fn set_claim_escrow(&mut self, claim_escrow_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("set_claim_escrow")
+            .args_json(json!({
+                "claim_escrow_id": claim_escrow_id
+            }))
+            .unwrap()
     }
 
-    fn get_draft_operators_whitelist(&self) -> ContractCall<Vec<AccountId>> {
-        self.make_call("get_draft_operators_whitelist")
+    fn set_ft_on_transfer_policy(&mut self, ft_on_transfer_policy: FtOnTransferPolicy) -> ContractCall<()> {
+        self.make_call("set_ft_on_transfer_policy")
+            .args_json(json!({
+                "ft_on_transfer_policy": ft_on_transfer_policy
+            }))
+            .unwrap()
     }
 
-    fn hash_schedule(&self, schedule: Schedule) -> ContractCall<Base58CryptoHash> {
-        self.make_call("hash_schedule")
+    fn set_termination_policy(&mut self, termination_policy: TerminationPolicy) -> ContractCall<()> {
+        self.make_call("set_termination_policy")
             .args_json(json!({
-                "schedule": schedule
+                "termination_policy": termination_policy,
             }))
             .unwrap()
     }
 
-    fn validate_schedule(
-        &self,
-        schedule: Schedule,
-        total_balance: WrappedBalance,
-        termination_schedule: Option<Schedule>,
+    fn set_draft_group_approval_threshold(
+        &mut self,
+        draft_group_approval_threshold: Option<WrappedBalance>,
     ) -> ContractCall<()> {
-        self.make_call("validate_schedule")
+        self.make_call("set_draft_group_approval_threshold")
             .args_json(json!({
-                "schedule": schedule,
-                "total_balance": total_balance,
-                "termination_schedule": termination_schedule,
+                "draft_group_approval_threshold": draft_group_approval_threshold
             }))
             .unwrap()
     }
 
-    fn get_next_draft_group_id(&self) -> ContractCall<DraftGroupIndex> {
-        self.make_call("get_next_draft_group_id")
+    fn set_gas_config(&mut self, gas_config: GasConfig) -> ContractCall<()> {
+        self.make_call("set_gas_config")
+            .args_json(json!({
+                "gas_config": gas_config,
+            }))
+            .unwrap()
     }
 
-    fn get_next_draft_id(&self) -> ContractCall<DraftGroupIndex> {
-        self.make_call("get_next_draft_id")
+    fn set_max_managed_balance(&mut self, max_managed_balance: Option<WrappedBalance>) -> ContractCall<()> {
+        self.make_call("set_max_managed_balance")
+            .args_json(json!({
+                "max_managed_balance": max_managed_balance,
+            }))
+            .unwrap()
     }
 
-    fn get_num_draft_groups(&self) -> ContractCall<u32> {
-        self.make_call("get_num_draft_groups")
+    fn set_anonymize_upcoming_unlocks(&mut self, anonymize: bool) -> ContractCall<()> {
+        self.make_call("set_anonymize_upcoming_unlocks")
+            .args_json(json!({
+                "anonymize": anonymize,
+            }))
+            .unwrap()
     }
 
-    fn get_draft_group(&self, index: DraftGroupIndex) -> ContractCall<Option<DraftGroupView>> {
-        self.make_call("get_draft_group")
+    fn set_privacy_mode(&mut self, enabled: bool) -> ContractCall<()> {
+        self.make_call("set_privacy_mode")
             .args_json(json!({
-                "index": index
+                "enabled": enabled,
             }))
             .unwrap()
     }
 
-    fn get_draft_groups_paged(
-        &self,
-        from_index: Option<DraftGroupIndex>,
-        to_index: Option<DraftGroupIndex>,
-    ) -> ContractCall<Vec<(DraftGroupIndex, DraftGroupView)>> {
-        self.make_call("get_draft_groups_paged")
+    fn set_max_unlocked_at_creation(&mut self, max_unlocked_bps: Option<u16>) -> ContractCall<()> {
+        self.make_call("set_max_unlocked_at_creation")
             .args_json(json!({
-                "from_index": from_index,
-                "to_index": to_index
+                "max_unlocked_bps": max_unlocked_bps,
             }))
             .unwrap()
     }
 
-    fn get_draft(&self, index: DraftIndex) -> ContractCall<Option<DraftView>> {
-        self.make_call("get_draft")
+    fn set_require_admin_reason(&mut self, required: bool) -> ContractCall<()> {
+        self.make_call("set_require_admin_reason")
             .args_json(json!({
-                "index": index
+                "required": required,
             }))
             .unwrap()
     }
 
-    fn get_drafts(&self, indices: Vec<DraftIndex>) -> ContractCall<Vec<(DraftIndex, DraftView)>> {
-        self.make_call("get_drafts")
+    fn set_max_draft_groups_per_day(&mut self, max_draft_groups_per_day: Option<u32>) -> ContractCall<()> {
+        self.make_call("set_max_draft_groups_per_day")
             .args_json(json!({
-                "indices": indices
+                "max_draft_groups_per_day": max_draft_groups_per_day,
             }))
             .unwrap()
     }
 
-    fn get_version(&self) -> ContractCall<String> {
-        self.make_call("get_version")
+    fn set_memo_templates(&mut self, memo_templates: MemoTemplates) -> ContractCall<()> {
+        self.make_call("set_memo_templates")
+            .args_json(json!({
+                "memo_templates": memo_templates,
+            }))
+            .unwrap()
     }
-}
 
-impl<'a> UpdateApiIntegration for LockupContract<'a> {
-    fn update_contract(&mut self, code: Vec<u8>) -> ContractCall<()> {
-        self.make_call("update_contract").args(code)
+    fn set_termination_burn(&mut self, termination_burn: Option<TerminationBurn>) -> ContractCall<()> {
+        self.make_call("set_termination_burn")
+            .args_json(json!({
+                "termination_burn": termination_burn,
+            }))
+            .unwrap()
     }
 
-    fn set_multisig(&mut self, multisig: AccountId) -> ContractCall<()> {
-        self.make_call("set_multisig")
+    fn set_claim_fee(&mut self, claim_fee: Option<ClaimFee>) -> ContractCall<()> {
+        self.make_call("set_claim_fee")
             .args_json(json!({
-                "multisig": multisig
+                "claim_fee": claim_fee,
+            }))
+            .unwrap()
+    }
+
+    fn cancel_claim_fee_change(&mut self) -> ContractCall<()> {
+        self.make_call("cancel_claim_fee_change")
+    }
+
+    fn schedule_account_remap(&mut self, old_account_id: AccountId, new_account_id: AccountId) -> ContractCall<()> {
+        self.make_call("schedule_account_remap")
+            .args_json(json!({
+                "old_account_id": old_account_id,
+                "new_account_id": new_account_id,
+            }))
+            .unwrap()
+    }
+
+    fn cancel_account_remap(&mut self, old_account_id: AccountId) -> ContractCall<()> {
+        self.make_call("cancel_account_remap")
+            .args_json(json!({
+                "old_account_id": old_account_id,
+            }))
+            .unwrap()
+    }
+
+    fn remap_account(&mut self, old_account_id: AccountId, new_account_id: AccountId) -> ContractCall<()> {
+        self.make_call("remap_account")
+            .args_json(json!({
+                "old_account_id": old_account_id,
+                "new_account_id": new_account_id,
+            }))
+            .unwrap()
+    }
+
+    fn extend_all_schedules(&mut self, by_sec: TimestampSec) -> ContractCall<()> {
+        self.make_call("extend_all_schedules")
+            .args_json(json!({
+                "by_sec": by_sec,
+            }))
+            .unwrap()
+    }
+
+    fn cancel_schedule_extension(&mut self) -> ContractCall<()> {
+        self.make_call("cancel_schedule_extension")
+    }
+
+    fn apply_schedule_extension(&mut self, limit: u32) -> ContractCall<Option<LockupIndex>> {
+        self.make_call("apply_schedule_extension")
+            .args_json(json!({
+                "limit": limit,
+            }))
+            .unwrap()
+    }
+
+    fn compute_lockups_merkle_root(&mut self, limit: u32) -> ContractCall<Option<LockupIndex>> {
+        self.make_call("compute_lockups_merkle_root")
+            .args_json(json!({
+                "limit": limit,
+            }))
+            .unwrap()
+    }
+
+    fn set_claim_throttle(&mut self, claim_throttle: Option<ClaimThrottle>) -> ContractCall<()> {
+        self.make_call("set_claim_throttle")
+            .args_json(json!({
+                "claim_throttle": claim_throttle,
+            }))
+            .unwrap()
+    }
+
+    fn set_draft_group_conversion_deadline(
+        &mut self,
+        conversion_deadline_sec: Option<TimestampSec>,
+    ) -> ContractCall<()> {
+        self.make_call("set_draft_group_conversion_deadline")
+            .args_json(json!({
+                "conversion_deadline_sec": conversion_deadline_sec,
+            }))
+            .unwrap()
+    }
+
+    fn add_to_payer_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("add_to_payer_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids
+            }))
+            .unwrap()
+    }
+
+    fn remove_from_payer_whitelist(&mut self, account_ids: Vec<AccountId>, reason: Option<String>) -> ContractCall<()> {
+        self.make_call("remove_from_payer_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids,
+                "reason": reason,
+            }))
+            .unwrap()
+    }
+
+    fn pause_drafts(&mut self, paused: bool) -> ContractCall<()> {
+        self.make_call("pause_drafts")
+            .args_json(json!({
+                "paused": paused,
+            }))
+            .unwrap()
+    }
+
+    fn pause_claims(&mut self, paused: bool) -> ContractCall<()> {
+        self.make_call("pause_claims")
+            .args_json(json!({
+                "paused": paused,
+            }))
+            .unwrap()
+    }
+
+    fn pause_terminations(&mut self, paused: bool) -> ContractCall<()> {
+        self.make_call("pause_terminations")
+            .args_json(json!({
+                "paused": paused,
+            }))
+            .unwrap()
+    }
+
+    fn set_attestor(&mut self, attestor_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("set_attestor")
+            .args_json(json!({
+                "attestor_id": attestor_id
+            }))
+            .unwrap()
+    }
+
+    fn set_attestation(&mut self, account_ids: Vec<AccountId>, attested: bool) -> ContractCall<()> {
+        self.make_call("set_attestation")
+            .args_json(json!({
+                "account_ids": account_ids,
+                "attested": attested
+            }))
+            .unwrap()
+    }
+
+    fn set_email_attestor_key(&mut self, email_attestor_key: Option<PublicKey>) -> ContractCall<()> {
+        self.make_call("set_email_attestor_key")
+            .args_json(json!({
+                "email_attestor_key": email_attestor_key
+            }))
+            .unwrap()
+    }
+
+    fn set_blocklist_manager(&mut self, blocklist_manager_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("set_blocklist_manager")
+            .args_json(json!({
+                "blocklist_manager_id": blocklist_manager_id
+            }))
+            .unwrap()
+    }
+
+    fn set_blocked(&mut self, account_ids: Vec<AccountId>, blocked: bool) -> ContractCall<()> {
+        self.make_call("set_blocked")
+            .args_json(json!({
+                "account_ids": account_ids,
+                "blocked": blocked
             }))
             .unwrap()
     }
+
+    fn add_to_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("add_to_vesting_oracles_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids
+            }))
+            .unwrap()
+    }
+
+    fn remove_from_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("remove_from_vesting_oracles_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids
+            }))
+            .unwrap()
+    }
+
+    fn resign(&mut self) -> ContractCall<()> {
+        self.make_call("resign")
+    }
+
+    fn create_lockups(&mut self, lockups: Vec<LockupCreate>) -> ContractCall<Vec<LockupIndex>> {
+        self.make_call("create_lockups")
+            .args_json(json!({
+                "lockups": lockups,
+            }))
+            .unwrap()
+    }
+
+    fn withdraw_lockup_pool(&mut self) -> ContractCall<WrappedBalance> {
+        self.make_call("withdraw_lockup_pool")
+    }
+
+    fn create_draft_group(&mut self) -> ContractCall<DraftGroupIndex> {
+        self.make_call("create_draft_group")
+    }
+
+    fn reclaim_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("reclaim_draft_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id,
+            }))
+            .unwrap()
+    }
+
+    fn create_draft(&mut self, draft: Draft) -> ContractCall<DraftIndex> {
+        self.make_call("create_draft")
+            .args_json(json!({
+                "draft": draft
+            }))
+            .unwrap()
+    }
+
+    fn create_drafts(&mut self, drafts: Vec<Draft>) -> ContractCall<Vec<DraftIndex>> {
+        self.make_call("create_drafts")
+            .args_json(json!({
+                "drafts": drafts
+            }))
+            .unwrap()
+    }
+
+    fn convert_draft(&mut self, draft_id: DraftIndex) -> ContractCall<LockupIndex> {
+        self.make_call("convert_draft")
+            .args_json(json!({
+                "draft_id": draft_id
+            }))
+            .unwrap()
+    }
+
+    fn convert_draft_with_account_creation(
+        &mut self,
+        draft_id: DraftIndex,
+        public_key: PublicKey,
+    ) -> ContractCall<Option<LockupIndex>> {
+        self.make_call("convert_draft_with_account_creation")
+            .args_json(json!({
+                "draft_id": draft_id,
+                "public_key": public_key
+            }))
+            .unwrap()
+    }
+
+    fn freeze_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<()> {
+        self.make_call("freeze_draft_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn unfreeze_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<()> {
+        self.make_call("unfreeze_draft_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn approve_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<()> {
+        self.make_call("approve_draft_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> ContractCall<()> {
+        self.make_call("discard_draft_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn delete_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> ContractCall<()> {
+        self.make_call("delete_drafts")
+            .args_json(json!({
+                "draft_ids": draft_ids
+            }))
+            .unwrap()
+    }
+
+    fn delete_drafts_in_group(&mut self, draft_group_id: DraftGroupIndex, limit: u32) -> ContractCall<u32> {
+        self.make_call("delete_drafts_in_group")
+            .args_json(json!({
+                "draft_group_id": draft_group_id,
+                "limit": limit,
+            }))
+            .unwrap()
+    }
+}
+
+impl<'a> LockupViewApiIntegration for LockupContract<'a> {
+    fn get_token_account_id(&self) -> ContractCall<AccountId> {
+        self.make_call("get_token_account_id")
+    }
+
+    fn get_token_decimals(&self) -> ContractCall<Option<u8>> {
+        self.make_call("get_token_decimals")
+    }
+
+    fn get_account_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_account_lockups")
+            .args_json(json!({
+                "account_id": account_id,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_account_lockups_paged(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        sort_by: Option<LockupSortBy>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<AccountLockupsPage> {
+        self.make_call("get_account_lockups_paged")
+            .args_json(json!({
+                "account_id": account_id,
+                "from_index": from_index,
+                "limit": limit,
+                "sort_by": sort_by,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_account_terminated_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_account_terminated_lockups")
+            .args_json(json!({
+                "account_id": account_id,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_accounts_lockups(
+        &self,
+        account_ids: Vec<AccountId>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<HashMap<AccountId, Vec<(LockupIndex, LockupView)>>> {
+        self.make_call("get_accounts_lockups")
+            .args_json(json!({
+                "account_ids": account_ids,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup(
+        &self,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Option<LockupView>> {
+        self.make_call("get_lockup")
+            .args_json(json!({
+                "index": index,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_account_lockups_signed(
+        &self,
+        auth: ViewAuth,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_account_lockups_signed")
+            .args_json(json!({
+                "auth": auth,
+                "account_id": account_id,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_signed(
+        &self,
+        auth: ViewAuth,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Option<LockupView>> {
+        self.make_call("get_lockup_signed")
+            .args_json(json!({
+                "auth": auth,
+                "index": index,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockups(
+        &self,
+        indices: Vec<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_lockups")
+            .args_json(json!({
+                "indices": indices,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_note_history(&self, index: LockupIndex) -> ContractCall<Vec<LockupNote>> {
+        self.make_call("get_lockup_note_history")
+            .args_json(json!({
+                "index": index,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_id(&self, index: LockupIndex) -> ContractCall<Option<LockupId>> {
+        self.make_call("get_lockup_id")
+            .args_json(json!({
+                "index": index,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_index(&self, lockup_id: LockupId) -> ContractCall<Option<LockupIndex>> {
+        self.make_call("get_lockup_index")
+            .args_json(json!({
+                "lockup_id": lockup_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_status(&self, index: LockupIndex) -> ContractCall<Option<LockupStatus>> {
+        self.make_call("get_lockup_status")
+            .args_json(json!({
+                "index": index,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_by_id(
+        &self,
+        lockup_id: LockupId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Option<(LockupIndex, LockupView)>> {
+        self.make_call("get_lockup_by_id")
+            .args_json(json!({
+                "lockup_id": lockup_id,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_schedule(&self, lockup_index: LockupIndex, humanize: bool) -> ContractCall<Option<ScheduleView>> {
+        self.make_call("get_schedule")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "humanize": humanize,
+            }))
+            .unwrap()
+    }
+
+    fn get_unlock_rate(
+        &self,
+        lockup_index: LockupIndex,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Option<UnlockRate>> {
+        self.make_call("get_unlock_rate")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_num_lockups(&self) -> ContractCall<u32> {
+        self.make_call("get_num_lockups")
+    }
+
+    fn get_lockups_paged(
+        &self,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_lockups_paged")
+            .args_json(json!({
+                "from_index": from_index,
+                "limit": limit,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockups_by_tag(
+        &self,
+        tag: String,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Vec<(LockupIndex, LockupView)>> {
+        self.make_call("get_lockups_by_tag")
+            .args_json(json!({
+                "tag": tag,
+                "from_index": from_index,
+                "limit": limit,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_num_grants(&self) -> ContractCall<u32> {
+        self.make_call("get_num_grants")
+    }
+
+    fn get_grant(
+        &self,
+        grant_id: GrantIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> ContractCall<Option<GrantView>> {
+        self.make_call("get_grant")
+            .args_json(json!({
+                "grant_id": grant_id,
+                "humanized": humanized,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_unclaimed_revocation(&self, lockup_index: LockupIndex) -> ContractCall<Option<UnclaimedRevocation>> {
+        self.make_call("get_unclaimed_revocation")
+            .args_json(json!({
+                "lockup_index": lockup_index
+            }))
+            .unwrap()
+    }
+
+    fn verify_agreement(&self, lockup_index: LockupIndex, preimage: Base64VecU8) -> ContractCall<bool> {
+        self.make_call("verify_agreement")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "preimage": preimage,
+            }))
+            .unwrap()
+    }
+
+    fn get_tag_stats(&self, tag: String, at_timestamp: Option<TimestampSec>) -> ContractCall<TagStats> {
+        self.make_call("get_tag_stats")
+            .args_json(json!({
+                "tag": tag,
+                "at_timestamp": at_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_top_upcoming_unlocks(
+        &self,
+        window_sec: TimestampSec,
+        limit: Option<u32>,
+        from_index: Option<LockupIndex>,
+        lockups_limit: Option<LockupIndex>,
+    ) -> ContractCall<Vec<UpcomingUnlock>> {
+        self.make_call("get_top_upcoming_unlocks")
+            .args_json(json!({
+                "window_sec": window_sec,
+                "limit": limit,
+                "from_index": from_index,
+                "lockups_limit": lockups_limit,
+            }))
+            .unwrap()
+    }
+
+    fn get_emission_schedule(
+        &self,
+        bucket_sec: TimestampSec,
+        from_ts: TimestampSec,
+        to_ts: TimestampSec,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> ContractCall<Vec<EmissionBucket>> {
+        self.make_call("get_emission_schedule")
+            .args_json(json!({
+                "bucket_sec": bucket_sec,
+                "from_ts": from_ts,
+                "to_ts": to_ts,
+                "from_index": from_index,
+                "limit": limit,
+            }))
+            .unwrap()
+    }
+
+    fn get_deposit_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_deposit_whitelist")
+    }
+
+    fn get_draft_operators_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_draft_operators_whitelist")
+    }
+
+    fn get_payer_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_payer_whitelist")
+    }
+
+    fn get_lien_holders_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_lien_holders_whitelist")
+    }
+
+    fn get_privacy_viewers_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_privacy_viewers_whitelist")
+    }
+
+    fn get_vesting_oracles_whitelist(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_vesting_oracles_whitelist")
+    }
+
+    fn get_viewing_key(&self, account_id: AccountId) -> ContractCall<Option<PublicKey>> {
+        self.make_call("get_viewing_key")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_manager_signing_key(&self) -> ContractCall<Option<PublicKey>> {
+        self.make_call("get_manager_signing_key")
+    }
+
+    fn get_signed_action_nonce(&self) -> ContractCall<u64> {
+        self.make_call("get_signed_action_nonce")
+    }
+
+    fn get_signed_action_message(&self, action: SignedAction) -> ContractCall<Base64VecU8> {
+        self.make_call("get_signed_action_message")
+            .args_json(json!({
+                "action": action,
+            }))
+            .unwrap()
+    }
+
+    fn get_update_guardians(&self) -> ContractCall<Vec<AccountId>> {
+        self.make_call("get_update_guardians")
+    }
+
+    fn get_approved_code_hashes(&self) -> ContractCall<Vec<Base58CryptoHash>> {
+        self.make_call("get_approved_code_hashes")
+    }
+
+    fn is_attested(&self, account_id: AccountId) -> ContractCall<bool> {
+        self.make_call("is_attested")
+            .args_json(json!({
+                "account_id": account_id
+            }))
+            .unwrap()
+    }
+
+    fn is_blocked(&self, account_id: AccountId) -> ContractCall<bool> {
+        self.make_call("is_blocked")
+            .args_json(json!({
+                "account_id": account_id
+            }))
+            .unwrap()
+    }
+
+    fn get_payout_address(&self, account_id: AccountId) -> ContractCall<Option<PayoutAddress>> {
+        self.make_call("get_payout_address")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_account_remap(&self, old_account_id: AccountId) -> ContractCall<Option<AccountRemap>> {
+        self.make_call("get_account_remap")
+            .args_json(json!({
+                "old_account_id": old_account_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_schedule_extension(&self) -> ContractCall<Option<ScheduleExtension>> {
+        self.make_call("get_schedule_extension")
+    }
+
+    fn get_num_airdrops(&self) -> ContractCall<u32> {
+        self.make_call("get_num_airdrops")
+    }
+
+    fn get_airdrop(&self, airdrop_id: AirdropIndex) -> ContractCall<Option<Airdrop>> {
+        self.make_call("get_airdrop")
+            .args_json(json!({
+                "airdrop_id": airdrop_id,
+            }))
+            .unwrap()
+    }
+
+    fn is_airdrop_lockup_registered(&self, airdrop_id: AirdropIndex, leaf_index: u32) -> ContractCall<bool> {
+        self.make_call("is_airdrop_lockup_registered")
+            .args_json(json!({
+                "airdrop_id": airdrop_id,
+                "leaf_index": leaf_index,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockups_merkle_root(&self) -> ContractCall<Option<LockupsMerkleRoot>> {
+        self.make_call("get_lockups_merkle_root")
+    }
+
+    fn get_lockup_leaf(&self, index: LockupIndex) -> ContractCall<Option<LockupLeaf>> {
+        self.make_call("get_lockup_leaf")
+            .args_json(json!({
+                "index": index,
+            }))
+            .unwrap()
+    }
+
+    fn hash_schedule(&self, schedule: Schedule) -> ContractCall<Base58CryptoHash> {
+        self.make_call("hash_schedule")
+            .args_json(json!({
+                "schedule": schedule
+            }))
+            .unwrap()
+    }
+
+    fn validate_schedule(
+        &self,
+        schedule: Schedule,
+        total_balance: WrappedBalance,
+        termination_schedule: Option<Schedule>,
+    ) -> ContractCall<()> {
+        self.make_call("validate_schedule")
+            .args_json(json!({
+                "schedule": schedule,
+                "total_balance": total_balance,
+                "termination_schedule": termination_schedule,
+            }))
+            .unwrap()
+    }
+
+    fn build_schedule_with_cliff(
+        &self,
+        total_balance: WrappedBalance,
+        initial_balance: WrappedBalance,
+        start_timestamp: TimestampSec,
+        cliff_timestamp: TimestampSec,
+        finish_timestamp: TimestampSec,
+    ) -> ContractCall<Schedule> {
+        self.make_call("build_schedule_with_cliff")
+            .args_json(json!({
+                "total_balance": total_balance,
+                "initial_balance": initial_balance,
+                "start_timestamp": start_timestamp,
+                "cliff_timestamp": cliff_timestamp,
+                "finish_timestamp": finish_timestamp,
+            }))
+            .unwrap()
+    }
+
+    fn get_next_draft_group_id(&self) -> ContractCall<DraftGroupIndex> {
+        self.make_call("get_next_draft_group_id")
+    }
+
+    fn get_next_draft_id(&self) -> ContractCall<DraftGroupIndex> {
+        self.make_call("get_next_draft_id")
+    }
+
+    fn get_next_indices(&self) -> ContractCall<NextIndices> {
+        self.make_call("get_next_indices")
+    }
+
+    fn get_lockups_count(&self) -> ContractCall<u32> {
+        self.make_call("get_lockups_count")
+    }
+
+    fn get_drafts_count(&self) -> ContractCall<u32> {
+        self.make_call("get_drafts_count")
+    }
+
+    fn get_draft_groups_count(&self) -> ContractCall<u32> {
+        self.make_call("get_draft_groups_count")
+    }
+
+    fn get_drafts_pending_account(&self) -> ContractCall<Vec<DraftIndex>> {
+        self.make_call("get_drafts_pending_account")
+    }
+
+    fn get_num_draft_groups(&self) -> ContractCall<u32> {
+        self.make_call("get_num_draft_groups")
+    }
+
+    fn get_draft_group(&self, index: DraftGroupIndex) -> ContractCall<Option<DraftGroupView>> {
+        self.make_call("get_draft_group")
+            .args_json(json!({
+                "index": index
+            }))
+            .unwrap()
+    }
+
+    fn get_draft_group_status(&self, draft_group_id: DraftGroupIndex) -> ContractCall<Option<DraftGroupStatusView>> {
+        self.make_call("get_draft_group_status")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn encode_create_lockup_msg(&self, spec: LockupCreate) -> ContractCall<String> {
+        self.make_call("encode_create_lockup_msg")
+            .args_json(json!({
+                "spec": spec
+            }))
+            .unwrap()
+    }
+
+    fn encode_fund_draft_group_msg(
+        &self,
+        draft_group_id: DraftGroupIndex,
+        try_convert: Option<bool>,
+    ) -> ContractCall<String> {
+        self.make_call("encode_fund_draft_group_msg")
+            .args_json(json!({
+                "draft_group_id": draft_group_id,
+                "try_convert": try_convert,
+            }))
+            .unwrap()
+    }
+
+    fn get_draft_groups_paged(
+        &self,
+        from_index: Option<DraftGroupIndex>,
+        to_index: Option<DraftGroupIndex>,
+    ) -> ContractCall<Vec<(DraftGroupIndex, DraftGroupView)>> {
+        self.make_call("get_draft_groups_paged")
+            .args_json(json!({
+                "from_index": from_index,
+                "to_index": to_index
+            }))
+            .unwrap()
+    }
+
+    fn get_draft(&self, index: DraftIndex) -> ContractCall<Option<DraftView>> {
+        self.make_call("get_draft")
+            .args_json(json!({
+                "index": index
+            }))
+            .unwrap()
+    }
+
+    fn get_drafts(&self, indices: Vec<DraftIndex>) -> ContractCall<Vec<(DraftIndex, DraftView)>> {
+        self.make_call("get_drafts")
+            .args_json(json!({
+                "indices": indices
+            }))
+            .unwrap()
+    }
+
+    fn get_pending_operator_actions(&self) -> ContractCall<PendingOperatorActions> {
+        self.make_call("get_pending_operator_actions")
+    }
+
+    fn validate_draft_group_conversion(
+        &self,
+        draft_group_id: DraftGroupIndex,
+    ) -> ContractCall<DraftGroupConversionReport> {
+        self.make_call("validate_draft_group_conversion")
+            .args_json(json!({
+                "draft_group_id": draft_group_id
+            }))
+            .unwrap()
+    }
+
+    fn get_num_terminations(&self) -> ContractCall<u32> {
+        self.make_call("get_num_terminations")
+    }
+
+    fn get_terminations(&self, from_index: Option<u32>, limit: Option<u32>) -> ContractCall<Vec<TerminationRecord>> {
+        self.make_call("get_terminations")
+            .args_json(json!({
+                "from_index": from_index,
+                "limit": limit
+            }))
+            .unwrap()
+    }
+
+    fn get_num_deposits(&self) -> ContractCall<u32> {
+        self.make_call("get_num_deposits")
+    }
+
+    fn get_deposit(&self, deposit_id: DepositIndex) -> ContractCall<Option<Deposit>> {
+        self.make_call("get_deposit")
+            .args_json(json!({
+                "deposit_id": deposit_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_pool_balance(&self, account_id: AccountId) -> ContractCall<WrappedBalance> {
+        self.make_call("get_lockup_pool_balance")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_lockup_funding(&self, index: LockupIndex) -> ContractCall<Vec<FundingSource>> {
+        self.make_call("get_lockup_funding")
+            .args_json(json!({
+                "index": index,
+            }))
+            .unwrap()
+    }
+
+    fn get_draft_group_funding(&self, draft_group_id: DraftGroupIndex) -> ContractCall<Option<FundingSource>> {
+        self.make_call("get_draft_group_funding")
+            .args_json(json!({
+                "draft_group_id": draft_group_id,
+            }))
+            .unwrap()
+    }
+
+    fn get_token_flows(&self) -> ContractCall<TokenFlows> {
+        self.make_call("get_token_flows")
+    }
+
+    fn check_solvency(
+        &self,
+        contract_balance: WrappedBalance,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> ContractCall<SolvencyReport> {
+        self.make_call("check_solvency")
+            .args_json(json!({
+                "contract_balance": contract_balance,
+                "from_index": from_index,
+                "limit": limit,
+            }))
+            .unwrap()
+    }
+
+    fn get_next_event_seq(&self) -> ContractCall<u64> {
+        self.make_call("get_next_event_seq")
+    }
+
+    fn get_event_log_enabled(&self) -> ContractCall<bool> {
+        self.make_call("get_event_log_enabled")
+    }
+
+    fn get_events_since(&self, seq: u64, limit: Option<u32>) -> ContractCall<Vec<(u64, String)>> {
+        self.make_call("get_events_since")
+            .args_json(json!({
+                "seq": seq,
+                "limit": limit
+            }))
+            .unwrap()
+    }
+
+    fn get_config(&self) -> ContractCall<Config> {
+        self.make_call("get_config")
+    }
+
+    fn get_gas_config(&self) -> ContractCall<GasConfig> {
+        self.make_call("get_gas_config")
+    }
+
+    fn get_fee_config(&self) -> ContractCall<FeeConfig> {
+        self.make_call("get_fee_config")
+    }
+
+    fn get_remaining_claim_quota(&self) -> ContractCall<Option<WrappedBalance>> {
+        self.make_call("get_remaining_claim_quota")
+    }
+
+    fn get_version(&self) -> ContractCall<String> {
+        self.make_call("get_version")
+    }
+
+    fn event_version(&self) -> ContractCall<String> {
+        self.make_call("event_version")
+    }
+
+    fn health(&self) -> ContractCall<Health> {
+        self.make_call("health")
+    }
+}
+
+impl<'a> UpdateApiIntegration for LockupContract<'a> {
+    fn update_contract(&mut self, code: Vec<u8>) -> ContractCall<()> {
+        self.make_call("update_contract").args(code)
+    }
+
+    fn set_multisig(&mut self, multisig: AccountId) -> ContractCall<()> {
+        self.make_call("set_multisig")
+            .args_json(json!({
+                "multisig": multisig
+            }))
+            .unwrap()
+    }
+
+    fn add_update_guardians(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("add_update_guardians")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+
+    fn remove_update_guardians(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("remove_update_guardians")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+
+    fn approve_code_hash(&mut self, code_hash: Base58CryptoHash) -> ContractCall<()> {
+        self.make_call("approve_code_hash")
+            .args_json(json!({
+                "code_hash": code_hash,
+            }))
+            .unwrap()
+    }
+
+    fn revoke_code_hash(&mut self, code_hash: Base58CryptoHash) -> ContractCall<()> {
+        self.make_call("revoke_code_hash")
+            .args_json(json!({
+                "code_hash": code_hash,
+            }))
+            .unwrap()
+    }
+}
+
+impl LienApiIntegration for LockupContract<'_> {
+    fn approve_lien(&mut self, lockup_index: LockupIndex, holder_id: Option<AccountId>) -> ContractCall<()> {
+        self.make_call("approve_lien")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "holder_id": holder_id,
+            }))
+            .unwrap()
+    }
+
+    fn place_lien(
+        &mut self,
+        lockup_index: LockupIndex,
+        amount: WrappedBalance,
+        redirect_claims: bool,
+    ) -> ContractCall<()> {
+        self.make_call("place_lien")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+                "amount": amount,
+                "redirect_claims": redirect_claims,
+            }))
+            .unwrap()
+    }
+
+    fn release_lien(&mut self, lockup_index: LockupIndex) -> ContractCall<()> {
+        self.make_call("release_lien")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+            }))
+            .unwrap()
+    }
+
+    fn claim_lien(&mut self, lockup_index: LockupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("claim_lien")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+            }))
+            .unwrap()
+    }
+
+    fn add_to_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("add_to_lien_holders_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+
+    fn remove_from_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("remove_from_lien_holders_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+}
+
+impl EscrowApiIntegration for LockupContract<'_> {
+    fn sweep_to_escrow(&mut self, lockup_index: LockupIndex) -> ContractCall<WrappedBalance> {
+        self.make_call("sweep_to_escrow")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+            }))
+            .unwrap()
+    }
+
+    fn remove_cliff_escrow(&mut self, lockup_index: LockupIndex) -> ContractCall<()> {
+        self.make_call("remove_cliff_escrow")
+            .args_json(json!({
+                "lockup_index": lockup_index,
+            }))
+            .unwrap()
+    }
+}
+
+impl AirdropApiIntegration for LockupContract<'_> {
+    fn create_merkle_airdrop(
+        &mut self,
+        root: Base58CryptoHash,
+        num_leaves: u32,
+        total_balance: WrappedBalance,
+    ) -> ContractCall<AirdropIndex> {
+        self.make_call("create_merkle_airdrop")
+            .args_json(json!({
+                "root": root,
+                "num_leaves": num_leaves,
+                "total_balance": total_balance,
+            }))
+            .unwrap()
+    }
+
+    fn register_airdrop_lockup(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        leaf_index: u32,
+        schedule: Schedule,
+        proof: Vec<Base58CryptoHash>,
+    ) -> ContractCall<LockupIndex> {
+        self.make_call("register_airdrop_lockup")
+            .args_json(json!({
+                "airdrop_id": airdrop_id,
+                "leaf_index": leaf_index,
+                "schedule": schedule,
+                "proof": proof,
+            }))
+            .unwrap()
+    }
+}
+
+impl PrivacyApiIntegration for LockupContract<'_> {
+    fn set_viewing_key(&mut self, public_key: Option<PublicKey>) -> ContractCall<()> {
+        self.make_call("set_viewing_key")
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .unwrap()
+    }
+
+    fn add_to_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("add_to_privacy_viewers_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+
+    fn remove_from_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>) -> ContractCall<()> {
+        self.make_call("remove_from_privacy_viewers_whitelist")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .unwrap()
+    }
+}
+
+impl SignedApiIntegration for LockupContract<'_> {
+    fn set_manager_signing_key(&mut self, public_key: Option<PublicKey>) -> ContractCall<()> {
+        self.make_call("set_manager_signing_key")
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .unwrap()
+    }
+
+    fn execute_signed(&mut self, action: SignedAction, signature: Base64VecU8) -> ContractCall<WrappedBalance> {
+        self.make_call("execute_signed")
+            .args_json(json!({
+                "action": action,
+                "signature": signature,
+            }))
+            .unwrap()
+    }
+}
+
+impl Web4ApiIntegration for LockupContract<'_> {
+    fn web4_get(&self, request: Web4Request) -> ContractCall<Web4Response> {
+        self.make_call("web4_get")
+            .args_json(json!({
+                "request": request,
+            }))
+            .unwrap()
+    }
+}
+
+impl LockupContract<'_> {
+    pub fn create_drafts_packed(&mut self, drafts: Vec<Draft>) -> ContractCall<Vec<DraftIndex>> {
+        self.make_call("create_drafts_packed")
+            .args(borsh::to_vec(&drafts).unwrap())
+    }
 }
 
 impl<'a> IntegrationContract<'a> for LockupContract<'a> {
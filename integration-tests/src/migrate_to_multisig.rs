@@ -97,6 +97,17 @@ async fn create_lockups(context: &mut Context) -> anyhow::Result<()> {
             account_id: account.to_near(),
             schedule: Schedule::new_unlocked(100),
             vesting_schedule: None,
+            tags: vec![],
+            owner_key: None,
+            owner_email_hash: None,
+            unclaimed_revocation_after_sec: None,
+            smoothing_sec: None,
+            agreement_hash: None,
+            keeper_incentive: None,
+            burn_on_termination: false,
+            curve: None,
+            cliff_escrow_id: None,
+            vesting: None,
         });
 
         context
@@ -0,0 +1,42 @@
+#![cfg(test)]
+
+use anyhow::Result;
+use integration_utils::misc::ToNear;
+use model::{draft::Draft, lockup::LockupCreate, lockup_api::LockupApiIntegration, view_api::LockupViewApiIntegration};
+
+use crate::context::{prepare_contract, IntegrationContext};
+
+#[tokio::test]
+async fn create_drafts_packed() -> Result<()> {
+    let mut context = prepare_contract().await?;
+    let manager = context.manager().await?;
+    let alice = context.alice().await?;
+    let bob = context.bob().await?;
+
+    let draft_group_id = context.lockup().create_draft_group().with_user(&manager).call().await?;
+    let drafts: Vec<Draft> = [alice.to_near(), bob.to_near()]
+        .into_iter()
+        .map(|account_id| Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(account_id, 1_000),
+            recipients: vec![],
+        })
+        .collect();
+
+    let draft_ids = context
+        .lockup()
+        .create_drafts_packed(drafts.clone())
+        .with_user(&manager)
+        .call()
+        .await?;
+    assert_eq!(draft_ids.len(), 2);
+
+    let draft_views = context.lockup().get_drafts(draft_ids).call().await?;
+    assert_eq!(draft_views.len(), 2);
+    for ((_, draft_view), draft) in draft_views.iter().zip(&drafts) {
+        assert_eq!(draft_view.draft_group_id, draft_group_id);
+        assert_eq!(draft_view.lockup_create.account_id, draft.lockup_create.account_id);
+    }
+
+    Ok(())
+}
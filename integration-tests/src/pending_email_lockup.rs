@@ -0,0 +1,114 @@
+#![cfg(test)]
+
+use anyhow::Result;
+use ed25519_dalek::{Signer, SigningKey};
+use integration_utils::{integration_contract::IntegrationContract, misc::ToNear};
+use model::{
+    ft_message::FtMessage,
+    lockup::LockupCreate,
+    lockup_api::LockupApiIntegration,
+    pending::{pending_lockup_message, PendingLockupProof},
+    view_api::LockupViewApiIntegration,
+    Balance,
+};
+use near_sdk::{
+    json_types::{Base58CryptoHash, U128},
+    serde_json::{json, to_string},
+    CurveType, PublicKey,
+};
+use near_workspaces::types::NearToken;
+use sweat_model::SweatApiIntegration;
+
+use crate::{
+    context::{prepare_contract, IntegrationContext},
+    lockup_interface::GetContractAccount,
+};
+
+const LOCKUP_AMOUNT: Balance = 1_000;
+
+fn email_hash(email: &str) -> Base58CryptoHash {
+    let digest = sha256::digest(email);
+    let bytes: Vec<u8> = (0..digest.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&digest[index..index + 2], 16).unwrap())
+        .collect();
+    <[u8; 32]>::try_from(bytes).unwrap().into()
+}
+
+#[tokio::test]
+async fn claim_pending_email_lockup() -> Result<()> {
+    let mut context = prepare_contract().await?;
+    let manager = context.manager().await?;
+    let alice = context.alice().await?;
+    let lockup_id = context.lockup().contract().id().clone();
+    let lockup_account = context.lockup().contract_account();
+
+    context
+        .ft_contract()
+        .tge_mint(&manager.to_near(), LOCKUP_AMOUNT.into())
+        .call()
+        .await?;
+
+    let owner_email_hash = email_hash("alice@example.com");
+    let message = FtMessage::LockupCreate(LockupCreate {
+        owner_email_hash: Some(owner_email_hash),
+        ..LockupCreate::new_unlocked(lockup_account.clone(), LOCKUP_AMOUNT)
+    });
+    manager
+        .call(context.ft_contract().contract().id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": lockup_id,
+            "amount": U128(LOCKUP_AMOUNT),
+            "msg": to_string(&message)?,
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let lockup = context.lockup().get_lockup(0, None, None).call().await?.unwrap();
+    assert_eq!(lockup.pending_owner_email_hash, Some(owner_email_hash));
+
+    // the email attestor verifies the email of alice off-chain and signs the binding to her account
+    let attestor_key = SigningKey::from_bytes(&[7; 32]);
+    let signature = attestor_key.sign(&pending_lockup_message(&lockup_account, 0, &alice.to_near()));
+    let proof = PendingLockupProof {
+        lockup_index: 0,
+        signature: signature.to_bytes().to_vec().into(),
+    };
+
+    let Err(error) = context
+        .lockup()
+        .claim_pending_lockup(proof.clone())
+        .with_user(&alice)
+        .call()
+        .await
+    else {
+        panic!("Claim without an email attestor key should fail");
+    };
+    assert!(error.to_string().contains("email attestor key is not set"));
+
+    let email_attestor_key =
+        PublicKey::from_parts(CurveType::ED25519, attestor_key.verifying_key().to_bytes().to_vec()).unwrap();
+    manager
+        .call(&lockup_id, "set_email_attestor_key")
+        .args_json(json!({ "email_attestor_key": email_attestor_key }))
+        .deposit(NearToken::from_yoctonear(1))
+        .transact()
+        .await?
+        .into_result()?;
+
+    context
+        .lockup()
+        .claim_pending_lockup(proof)
+        .with_user(&alice)
+        .call()
+        .await?;
+
+    let lockup = context.lockup().get_lockup(0, None, None).call().await?.unwrap();
+    assert_eq!(lockup.account_id, alice.to_near());
+    assert_eq!(lockup.pending_owner_email_hash, None);
+
+    Ok(())
+}
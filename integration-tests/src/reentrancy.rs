@@ -0,0 +1,259 @@
+#![cfg(test)]
+
+use anyhow::Result;
+use integration_utils::{integration_contract::IntegrationContract, misc::ToNear};
+use model::{
+    ft_message::FtMessage,
+    lockup::{LockupCreate, LockupIndex, LockupStage, LockupStatus},
+    lockup_api::LockupApiIntegration,
+    schedule::Schedule,
+    termination::VestingConditions,
+    view_api::LockupViewApiIntegration,
+    TimestampSec,
+};
+use near_sdk::{
+    json_types::U128,
+    serde_json::{json, to_string, Value},
+};
+use near_workspaces::{result::ExecutionFinalResult, types::NearToken, Contract};
+
+use crate::{
+    lockup_interface::{GetContractAccount, LockupContract},
+    utils::load_wasm,
+};
+
+const TOTAL_SUPPLY: u128 = 1_000_000;
+const LOCKUP_AMOUNT: u128 = 1_000;
+
+/// Deploys the lockup contract for the malicious token, which owns a single lockup terminable by itself.
+async fn prepare_malicious_token(schedule: Schedule) -> Result<(Contract, Contract)> {
+    let worker = near_workspaces::sandbox().await?;
+    let lockup = worker.dev_deploy(&load_wasm("../res/hodl_lockup.wasm")?).await?;
+    let token = worker.dev_deploy(&load_wasm("../res/malicious_token.wasm")?).await?;
+
+    token
+        .call("new")
+        .args_json(json!({ "total_supply": U128(TOTAL_SUPPLY) }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let token_id = token.as_account().to_near();
+    LockupContract::with_contract(&lockup)
+        .new(token_id.clone(), vec![token_id.clone()], None, token_id.clone())
+        .call()
+        .await?;
+
+    let message = FtMessage::LockupCreate(LockupCreate {
+        account_id: token_id,
+        schedule,
+        vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        tags: vec![],
+        owner_key: None,
+        owner_email_hash: None,
+        unclaimed_revocation_after_sec: None,
+        smoothing_sec: None,
+        agreement_hash: None,
+        keeper_incentive: None,
+        burn_on_termination: false,
+        curve: None,
+        cliff_escrow_id: None,
+        vesting: None,
+    });
+    token
+        .call("ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": lockup.id(),
+            "amount": U128(LOCKUP_AMOUNT),
+            "msg": to_string(&message)?,
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    Ok((lockup, token))
+}
+
+/// Makes the next `ft_transfer` of the token call the lockup contract before it completes.
+async fn set_reentry(token: &Contract, lockup: &Contract, method_name: &str, args: Value) -> Result<()> {
+    token
+        .call("set_reentry")
+        .args_json(json!({ "reentry": lockup_call(lockup, method_name, args) }))
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+/// Calls the lockup contract on behalf of the token account, attaching 1 yocto to calls requiring it.
+async fn call_as_token(
+    token: &Contract,
+    lockup: &Contract,
+    method_name: &str,
+    args: Value,
+) -> Result<ExecutionFinalResult> {
+    let result = token
+        .call("call_contract")
+        .args_json(json!({ "call": lockup_call(lockup, method_name, args) }))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_success(), "{result:?}");
+    Ok(result)
+}
+
+fn assert_reentry_rejected(result: &ExecutionFinalResult, message: &str) {
+    assert!(
+        result
+            .receipt_failures()
+            .iter()
+            .any(|outcome| format!("{outcome:?}").contains(message)),
+        "{result:?}"
+    );
+}
+
+fn lockup_call(lockup: &Contract, method_name: &str, args: Value) -> Value {
+    let deposit = if method_name == "claim" {
+        NearToken::from_yoctonear(0)
+    } else {
+        NearToken::from_yoctonear(1)
+    };
+    json!({
+        "contract_id": lockup.id(),
+        "method_name": method_name,
+        "args": args.to_string(),
+        "deposit": deposit,
+    })
+}
+
+async fn token_balance(token: &Contract, lockup: &Contract) -> Result<(u128, u128)> {
+    let balance_of = |account_id| async move {
+        token
+            .view("ft_balance_of")
+            .args_json(json!({ "account_id": account_id }))
+            .await?
+            .json::<U128>()
+    };
+    Ok((
+        balance_of(token.id().clone()).await?.0,
+        balance_of(lockup.id().clone()).await?.0,
+    ))
+}
+
+async fn last_reentry_result(token: &Contract) -> Result<Option<bool>> {
+    Ok(token.view("get_last_reentry_result").await?.json()?)
+}
+
+#[tokio::test]
+async fn reentrant_claim_is_rejected() -> Result<()> {
+    let (lockup, token) = prepare_malicious_token(Schedule::new_unlocked(LOCKUP_AMOUNT)).await?;
+    let lockup_index: LockupIndex = 0;
+    let claim_amount = 400;
+
+    // the reentrant claim tries to claim the rest while the partial claim is in flight
+    set_reentry(&token, &lockup, "claim", json!({})).await?;
+    let result = call_as_token(
+        &token,
+        &lockup,
+        "claim",
+        json!({ "amounts": [[lockup_index, U128(claim_amount)]] }),
+    )
+    .await?;
+
+    assert_reentry_rejected(&result, "Claim of lockup #0 is in flight, try later");
+    assert_eq!(last_reentry_result(&token).await?, Some(false));
+    assert_eq!(
+        token_balance(&token, &lockup).await?,
+        (
+            TOTAL_SUPPLY - LOCKUP_AMOUNT + claim_amount,
+            LOCKUP_AMOUNT - claim_amount
+        )
+    );
+
+    let lockup_view = LockupContract::with_contract(&lockup)
+        .get_lockup(lockup_index, None, None)
+        .call()
+        .await?
+        .expect("lockup not found");
+    assert_eq!(lockup_view.claimed_balance, claim_amount);
+    assert_eq!(lockup_view.in_flight_claim_balance, 0);
+
+    // the guard is released once the callback resolves
+    call_as_token(&token, &lockup, "claim", json!({})).await?;
+    assert_eq!(token_balance(&token, &lockup).await?, (TOTAL_SUPPLY, 0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reentrant_termination_is_rejected() -> Result<()> {
+    let (lockup, token) = prepare_malicious_token(Schedule::new_unlocked(LOCKUP_AMOUNT)).await?;
+    let lockup_index: LockupIndex = 0;
+
+    set_reentry(&token, &lockup, "terminate", json!({ "lockup_index": lockup_index })).await?;
+    let result = call_as_token(&token, &lockup, "claim", json!({})).await?;
+
+    assert_reentry_rejected(&result, "Claim of lockup #0 is in flight, try later");
+    assert_eq!(last_reentry_result(&token).await?, Some(false));
+    assert_eq!(token_balance(&token, &lockup).await?, (TOTAL_SUPPLY, 0));
+    assert!(LockupContract::with_contract(&lockup)
+        .get_terminations(None, None)
+        .call()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reentrant_claim_during_termination_is_rejected() -> Result<()> {
+    // half of the balance is vested at once, the rest vests linearly until 2106
+    let schedule = Schedule::new_with_cliff(LOCKUP_AMOUNT, LOCKUP_AMOUNT / 2, 1, 1, TimestampSec::MAX);
+    let (lockup, token) = prepare_malicious_token(schedule).await?;
+    let lockup_index: LockupIndex = 0;
+
+    set_reentry(&token, &lockup, "claim", json!({})).await?;
+    let result = call_as_token(&token, &lockup, "terminate", json!({ "lockup_index": lockup_index })).await?;
+
+    assert_reentry_rejected(&result, "Termination of lockup #0 is in flight, try later");
+    assert_eq!(last_reentry_result(&token).await?, Some(false));
+
+    let lockup_view = LockupContract::with_contract(&lockup)
+        .get_lockup(lockup_index, None, None)
+        .call()
+        .await?
+        .expect("lockup not found");
+    assert_eq!(lockup_view.claimed_balance, 0);
+    assert_eq!(lockup_view.stage, LockupStage::Terminated);
+    assert_eq!(lockup_view.status, LockupStatus::Active);
+    assert_eq!(
+        token_balance(&token, &lockup).await?,
+        (TOTAL_SUPPLY - lockup_view.total_balance, lockup_view.total_balance)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn claim_guard_is_released_after_callback() -> Result<()> {
+    let (lockup, token) = prepare_malicious_token(Schedule::new_unlocked(LOCKUP_AMOUNT)).await?;
+    let lockup_index: LockupIndex = 0;
+
+    call_as_token(&token, &lockup, "claim", json!({})).await?;
+    assert_eq!(last_reentry_result(&token).await?, None);
+
+    // terminating after the claim resolved isn't blocked by the guard, the lockup is fully vested
+    call_as_token(&token, &lockup, "terminate", json!({ "lockup_index": lockup_index })).await?;
+    assert_eq!(
+        LockupContract::with_contract(&lockup)
+            .get_terminations(None, None)
+            .call()
+            .await?
+            .len(),
+        1
+    );
+
+    Ok(())
+}
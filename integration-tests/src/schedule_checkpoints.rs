@@ -0,0 +1,129 @@
+#![cfg(test)]
+
+use anyhow::Result;
+use integration_utils::{integration_contract::IntegrationContract, misc::ToNear};
+use model::{
+    ft_message::FtMessage,
+    lockup::LockupCreate,
+    schedule::{Checkpoint, Schedule, MAX_SCHEDULE_CHECKPOINTS},
+    Balance, TimestampSec,
+};
+use near_sdk::{
+    json_types::U128,
+    serde_json::{json, to_string},
+};
+use near_workspaces::{
+    result::ExecutionFinalResult,
+    types::{Gas, NearToken},
+};
+use sweat_model::{StorageManagementIntegration, SweatApiIntegration};
+
+use crate::{
+    context::{prepare_contract, IntegrationContext},
+    lockup_interface::GetContractAccount,
+};
+
+const LOCKUP_AMOUNT: Balance = 1_000_000;
+
+/// The gas the lockup contract may burn for a lockup with the largest schedule,
+/// a fraction of the 300 TGas of a transaction so the calls stay safe as the contract grows.
+const MAX_LOCKUP_GAS: Gas = Gas::from_tgas(50);
+
+const TWO_WEEKS_SEC: TimestampSec = 14 * 24 * 60 * 60;
+
+/// A step schedule of bi-weekly payroll with `MAX_SCHEDULE_CHECKPOINTS`, fully unlocked by now.
+fn payroll_schedule() -> Schedule {
+    let num_steps = u32::try_from(MAX_SCHEDULE_CHECKPOINTS / 2).unwrap();
+    let step_balance = LOCKUP_AMOUNT / Balance::from(num_steps);
+    let mut checkpoints = vec![];
+    for step in 0..num_steps {
+        let timestamp = 1 + step * TWO_WEEKS_SEC;
+        let balance = Balance::from(step) * step_balance;
+        checkpoints.push(Checkpoint { timestamp, balance });
+        checkpoints.push(Checkpoint {
+            timestamp: timestamp + 1,
+            balance: if step + 1 == num_steps {
+                LOCKUP_AMOUNT
+            } else {
+                balance + step_balance
+            },
+        });
+    }
+    Schedule(checkpoints)
+}
+
+/// The gas burnt by the receipts of the lockup contract, including its callbacks.
+fn lockup_gas_burnt(result: &ExecutionFinalResult, lockup_id: &near_workspaces::AccountId) -> Gas {
+    Gas::from_gas(
+        result
+            .receipt_outcomes()
+            .iter()
+            .filter(|outcome| &outcome.executor_id == lockup_id)
+            .map(|outcome| outcome.gas_burnt.as_gas())
+            .sum(),
+    )
+}
+
+#[tokio::test]
+async fn max_schedule_checkpoints_fit_into_gas() -> Result<()> {
+    let mut context = prepare_contract().await?;
+    let manager = context.manager().await?;
+    let alice = context.alice().await?;
+    let lockup_id = context.lockup().contract().id().clone();
+
+    context
+        .ft_contract()
+        .tge_mint(&manager.to_near(), LOCKUP_AMOUNT.into())
+        .call()
+        .await?;
+    context
+        .ft_contract()
+        .storage_deposit(alice.to_near().into(), None)
+        .call()
+        .await?;
+
+    let schedule = payroll_schedule();
+    assert_eq!(schedule.0.len(), MAX_SCHEDULE_CHECKPOINTS);
+    let message = FtMessage::LockupCreate(LockupCreate {
+        account_id: alice.to_near(),
+        schedule,
+        vesting_schedule: None,
+        tags: vec![],
+        owner_key: None,
+        owner_email_hash: None,
+        unclaimed_revocation_after_sec: None,
+        smoothing_sec: None,
+        agreement_hash: None,
+        keeper_incentive: None,
+        burn_on_termination: false,
+        curve: None,
+        cliff_escrow_id: None,
+        vesting: None,
+    });
+    let result = manager
+        .call(context.ft_contract().contract().id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": context.lockup().contract_account(),
+            "amount": U128(LOCKUP_AMOUNT),
+            "msg": to_string(&message)?,
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_success(), "{result:?}");
+    let creation_gas = dbg!(lockup_gas_burnt(&result, &lockup_id));
+    assert!(creation_gas < MAX_LOCKUP_GAS, "lockup creation burnt {creation_gas}");
+
+    let result = alice
+        .call(&lockup_id, "claim")
+        .args_json(json!({}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_success(), "{result:?}");
+    let claim_gas = dbg!(lockup_gas_burnt(&result, &lockup_id));
+    assert!(claim_gas < MAX_LOCKUP_GAS, "claim burnt {claim_gas}");
+
+    Ok(())
+}
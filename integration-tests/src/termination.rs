@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+use anyhow::Result;
+use integration_utils::{integration_contract::IntegrationContract, misc::ToNear};
+use model::{
+    ft_message::FtMessage, lockup::LockupCreate, schedule::Schedule, termination::VestingConditions,
+    view_api::LockupViewApiIntegration, Balance,
+};
+use near_sdk::{
+    json_types::U128,
+    serde_json::{json, to_string},
+};
+use near_workspaces::types::NearToken;
+
+use crate::{
+    context::{prepare_contract, IntegrationContext},
+    lockup_interface::GetContractAccount,
+};
+
+const LOCKUP_AMOUNT: Balance = 1_000;
+
+#[tokio::test]
+async fn terminate_with_one_yocto() -> Result<()> {
+    let mut context = prepare_contract().await?;
+    let manager = context.manager().await?;
+    let alice = context.alice().await?;
+    let lockup_id = context.lockup().contract().id().clone();
+
+    // nothing is vested before the far future start, so the whole balance is refunded
+    let message = FtMessage::LockupCreate(LockupCreate {
+        schedule: Schedule::new_with_cliff(LOCKUP_AMOUNT, 0, 4_000_000_000, 4_000_000_000, 4_100_000_000),
+        vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        ..LockupCreate::new_unlocked(alice.to_near(), LOCKUP_AMOUNT)
+    });
+    manager
+        .call(context.ft_contract().contract().id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": lockup_id,
+            "amount": U128(LOCKUP_AMOUNT),
+            "msg": to_string(&message)?,
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let terminate_args = json!({ "lockup_index": 0 });
+
+    let result = manager
+        .call(&lockup_id, "terminate")
+        .args_json(&terminate_args)
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(result.is_failure(), "Termination without a deposit should fail");
+
+    let unvested: U128 = manager
+        .call(&lockup_id, "terminate")
+        .args_json(&terminate_args)
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?
+        .json()?;
+    assert_eq!(unvested.0, LOCKUP_AMOUNT);
+
+    let lockup = context.lockup().get_lockup(0, None, None).call().await?.unwrap();
+    assert_eq!(lockup.total_balance, 0);
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 #![cfg(test)]
 
 use anyhow::Result;
-use integration_utils::misc::ToNear;
+use integration_utils::{integration_contract::IntegrationContract, misc::ToNear};
 use model::update::UpdateApiIntegration;
+use near_sdk::{json_types::Base58CryptoHash, serde_json::json};
+use near_workspaces::types::NearToken;
 
 use crate::{
     context::{prepare_contract, IntegrationContext},
@@ -20,6 +22,36 @@ async fn simple_update() -> Result<()> {
 
     let wasm = load_wasm("../res/hodl_lockup.wasm")?;
 
+    let Err(error) = context
+        .lockup()
+        .update_contract(wasm.clone())
+        .with_user(&alice)
+        .call()
+        .await
+    else {
+        panic!("Update without an approved code hash should fail");
+    };
+    assert!(error
+        .to_string()
+        .contains("The code hash is not approved by the update guardians"));
+
+    let manager = context.manager().await?;
+    let lockup_id = context.lockup().contract().id().clone();
+    manager
+        .call(&lockup_id, "add_update_guardians")
+        .args_json(json!({ "account_ids": [alice.id()] }))
+        .deposit(NearToken::from_yoctonear(1))
+        .transact()
+        .await?
+        .into_result()?;
+    alice
+        .call(&lockup_id, "approve_code_hash")
+        .args_json(json!({ "code_hash": code_hash(&wasm) }))
+        .deposit(NearToken::from_yoctonear(1))
+        .transact()
+        .await?
+        .into_result()?;
+
     context
         .lockup()
         .update_contract(wasm.clone())
@@ -37,3 +69,12 @@ async fn simple_update() -> Result<()> {
 
     Ok(())
 }
+
+fn code_hash(code: &[u8]) -> Base58CryptoHash {
+    let digest = sha256::digest(code);
+    let bytes: Vec<u8> = (0..digest.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&digest[index..index + 2], 16).unwrap())
+        .collect();
+    <[u8; 32]>::try_from(bytes).unwrap().into()
+}
@@ -0,0 +1,132 @@
+//! A fungible token for integration tests that calls back into the receiving contract from `ft_transfer`,
+//! simulating a misbehaving token in the middle of a lockup claim.
+
+use near_contract_standards::fungible_token::receiver::ext_ft_receiver;
+use near_sdk::{
+    assert_one_yocto, collections::LookupMap, env, is_promise_success, json_types::U128, log, near, AccountId, Gas,
+    GasWeight, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+};
+
+const GAS_FOR_AFTER_REENTRY: Gas = Gas::from_tgas(2);
+const GAS_FOR_FT_ON_TRANSFER: Gas = Gas::from_tgas(100);
+
+/// A call made on behalf of the token account, `set_reentry` makes it from the next `ft_transfer`.
+#[near(serializers=[borsh, json])]
+pub struct Reentry {
+    pub contract_id: AccountId,
+    pub method_name: String,
+    pub args: String,
+    pub deposit: NearToken,
+}
+
+#[near(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct Contract {
+    balances: LookupMap<AccountId, u128>,
+    reentry: Option<Reentry>,
+    /// Whether the last reentrant call succeeded
+    last_reentry_result: Option<bool>,
+}
+
+#[near]
+impl Contract {
+    #[init]
+    pub fn new(total_supply: U128) -> Self {
+        let mut balances = LookupMap::new(b"b");
+        balances.insert(&env::predecessor_account_id(), &total_supply.0);
+        Self {
+            balances,
+            reentry: None,
+            last_reentry_result: None,
+        }
+    }
+
+    pub fn set_reentry(&mut self, reentry: Option<Reentry>) {
+        self.reentry = reentry;
+    }
+
+    /// Calls the contract on behalf of this token account, e.g. to claim lockups owned by it.
+    #[allow(clippy::unused_self)]
+    pub fn call_contract(&mut self, call: Reentry) -> Promise {
+        call.into_promise()
+    }
+
+    #[payable]
+    pub fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) -> PromiseOrValue<()> {
+        assert_one_yocto();
+        if let Some(memo) = memo {
+            log!("Memo: {memo}");
+        }
+        self.internal_transfer(&env::predecessor_account_id(), &receiver_id, amount.0);
+
+        // the transfer completes only after the reentrant call resolves, so it happens before the sender's callback
+        match self.reentry.take() {
+            Some(reentry) => reentry
+                .into_promise()
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_AFTER_REENTRY)
+                        .with_unused_gas_weight(0)
+                        .after_reentry(),
+                )
+                .into(),
+            None => PromiseOrValue::Value(()),
+        }
+    }
+
+    /// Transfers without resolving the unused amount, the tests only make deposits accepted in full.
+    #[payable]
+    pub fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> Promise {
+        assert_one_yocto();
+        if let Some(memo) = memo {
+            log!("Memo: {memo}");
+        }
+        let sender_id = env::predecessor_account_id();
+        self.internal_transfer(&sender_id, &receiver_id, amount.0);
+        ext_ft_receiver::ext(receiver_id)
+            .with_static_gas(GAS_FOR_FT_ON_TRANSFER)
+            .ft_on_transfer(sender_id, amount, msg)
+    }
+
+    #[private]
+    pub fn after_reentry(&mut self) {
+        self.last_reentry_result = Some(is_promise_success());
+    }
+
+    pub fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.balances.get(&account_id).unwrap_or_default().into()
+    }
+
+    pub fn get_last_reentry_result(&self) -> Option<bool> {
+        self.last_reentry_result
+    }
+}
+
+impl Reentry {
+    /// The call receives all the remaining gas.
+    fn into_promise(self) -> Promise {
+        Promise::new(self.contract_id).function_call_weight(
+            self.method_name,
+            self.args.into_bytes(),
+            self.deposit,
+            Gas::from_gas(0),
+            GasWeight(1),
+        )
+    }
+}
+
+impl Contract {
+    fn internal_transfer(&mut self, sender_id: &AccountId, receiver_id: &AccountId, amount: u128) {
+        let sender_balance = self.balances.get(sender_id).unwrap_or_default();
+        assert!(sender_balance >= amount, "The account doesn't have enough balance");
+        self.balances.insert(sender_id, &(sender_balance - amount));
+        let receiver_balance = self.balances.get(receiver_id).unwrap_or_default();
+        self.balances.insert(receiver_id, &(receiver_balance + amount));
+    }
+}
@@ -0,0 +1,75 @@
+//! Benchmarks of the schedule math with large checkpoint counts.
+//!
+//! Run with `cargo bench --package hodl-model`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use hodl_model::{
+    schedule::{Checkpoint, Schedule},
+    TimestampSec,
+};
+use near_sdk::borsh;
+
+const CHECKPOINT_COUNTS: [u32; 5] = [2, 10, 100, 1_000, 10_000];
+const WARMUP: Duration = Duration::from_millis(200);
+const MEASUREMENT: Duration = Duration::from_secs(1);
+
+/// A monotonic schedule with a checkpoint every 1000 seconds.
+fn schedule_with_checkpoints(count: u32) -> Schedule {
+    Schedule(
+        (0..count)
+            .map(|index| Checkpoint {
+                timestamp: 1_000_000 + index * 1_000,
+                balance: u128::from(index) * 1_000_000,
+            })
+            .collect(),
+    )
+}
+
+/// Runs `routine` for the warmup period and then reports the mean time per iteration.
+fn bench(name: &str, mut routine: impl FnMut()) {
+    let started = Instant::now();
+    while started.elapsed() < WARMUP {
+        routine();
+    }
+
+    let mut iterations: u32 = 0;
+    let started = Instant::now();
+    while started.elapsed() < MEASUREMENT {
+        routine();
+        iterations += 1;
+    }
+    let elapsed = started.elapsed();
+
+    println!(
+        "{name:<48} {:>12.1} ns/iter ({iterations} iterations)",
+        elapsed.as_secs_f64() * 1e9 / f64::from(iterations)
+    );
+}
+
+fn main() {
+    for count in CHECKPOINT_COUNTS {
+        let schedule = schedule_with_checkpoints(count);
+        let finish: TimestampSec = schedule.0.last().unwrap().timestamp;
+        let timestamps: Vec<TimestampSec> = (0..64).map(|step| 999_000 + (finish - 999_000) / 64 * step).collect();
+
+        println!(
+            "checkpoints: {count}, borsh size: {} bytes",
+            borsh::to_vec(&schedule).unwrap().len()
+        );
+
+        bench(&format!("unlocked_balance/{count}"), || {
+            for &timestamp in &timestamps {
+                black_box(black_box(&schedule).unlocked_balance(black_box(timestamp)));
+            }
+        });
+
+        let serialized = borsh::to_vec(&schedule).unwrap();
+        bench(&format!("deserialize/{count}"), || {
+            black_box(borsh::from_slice::<Schedule>(black_box(&serialized)).unwrap());
+        });
+    }
+}
@@ -0,0 +1,157 @@
+use near_sdk::{borsh, env, json_types::Base58CryptoHash, near, AccountId, CryptoHash};
+use nitka::make_integration_version;
+
+use crate::{
+    lockup::LockupIndex, merkle::verify_merkle_proof, provenance::DepositIndex, schedule::Schedule,
+    util::u128_dec_format, Balance, WrappedBalance,
+};
+
+pub type AirdropIndex = u32;
+
+/// A lockup of a merkle airdrop, the leaf of the airdrop tree is the sha256 hash of its borsh.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AirdropLeaf {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub schedule: Schedule,
+}
+
+impl AirdropLeaf {
+    pub fn hash(&self) -> CryptoHash {
+        env::sha256_array(&borsh::to_vec(self).unwrap())
+    }
+}
+
+/// A snapshot of lockups posted by the manager as a merkle root, every account registers its own lockup
+/// with a proof. The tree is built by `merkle::merkle_root` over the hashes of `AirdropLeaf`.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Airdrop {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub root: Base58CryptoHash,
+    pub num_leaves: u32,
+    /// The balance of all lockups of the snapshot
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_balance: Balance,
+    /// The balance of the lockups registered so far
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub registered_balance: Balance,
+    /// The deposit funding the airdrop, lockups can be registered once it's funded
+    pub deposit_id: Option<DepositIndex>,
+}
+
+impl Airdrop {
+    pub fn new(root: Base58CryptoHash, num_leaves: u32, total_balance: Balance) -> Self {
+        assert!(num_leaves > 0, "airdrop should have lockups");
+        assert!(total_balance > 0, "airdrop total balance should be positive");
+        Self {
+            root,
+            num_leaves,
+            total_balance,
+            registered_balance: 0,
+            deposit_id: None,
+        }
+    }
+
+    pub fn fund(&mut self, amount: Balance, deposit_id: DepositIndex) {
+        assert!(self.deposit_id.is_none(), "airdrop is already funded");
+        assert_eq!(
+            self.total_balance, amount,
+            "The airdrop total balance doesn't match the transferred balance"
+        );
+        self.deposit_id = Some(deposit_id);
+    }
+
+    /// Checks the proof of the leaf and accounts its balance, returns the deposit funding it.
+    pub fn register(&mut self, leaf: &AirdropLeaf, leaf_index: u32, proof: &[Base58CryptoHash]) -> DepositIndex {
+        let deposit_id = self.deposit_id.expect("airdrop is not funded");
+        let proof: Vec<CryptoHash> = proof.iter().copied().map(Into::into).collect();
+        assert!(
+            verify_merkle_proof(leaf.hash(), leaf_index, self.num_leaves, &proof, &self.root.into()),
+            "invalid airdrop proof"
+        );
+        self.registered_balance = self
+            .registered_balance
+            .checked_add(leaf.schedule.total_balance())
+            .filter(|registered_balance| *registered_balance <= self.total_balance)
+            .expect("airdrop lockups exceed its total balance");
+        deposit_id
+    }
+
+    /// The funded balance of the lockups not registered yet.
+    pub fn unregistered_balance(&self) -> Balance {
+        if self.deposit_id.is_some() {
+            self.total_balance - self.registered_balance
+        } else {
+            0
+        }
+    }
+}
+
+#[make_integration_version]
+pub trait AirdropApi {
+    /// Posts the merkle root of a snapshot of `num_leaves` lockups with the total balance of `total_balance`.
+    /// The airdrop is funded with `ft_transfer_call` of the total balance with an `AirdropFunding` message,
+    /// allowed to the senders of the draft group funding. Only the manager can call it.
+    fn create_merkle_airdrop(
+        &mut self,
+        root: Base58CryptoHash,
+        num_leaves: u32,
+        total_balance: WrappedBalance,
+    ) -> AirdropIndex;
+
+    /// Creates the lockup of the caller at `leaf_index` of the airdrop with the sibling hashes as a proof,
+    /// see `merkle::verify_merkle_proof`. The caller pays for the storage of the lockup with the attached deposit,
+    /// the rest of the deposit is refunded. Every leaf can be registered once.
+    fn register_airdrop_lockup(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        leaf_index: u32,
+        schedule: Schedule,
+        proof: Vec<Base58CryptoHash>,
+    ) -> LockupIndex;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::merkle::{hash_pair, merkle_root};
+
+    fn leaf(account_id: &str, balance: Balance) -> AirdropLeaf {
+        AirdropLeaf {
+            account_id: AccountId::from_str(account_id).unwrap(),
+            schedule: Schedule::new_unlocked(balance),
+        }
+    }
+
+    #[test]
+    fn test_airdrop_register() {
+        let leaves = [leaf("alice.near", 100), leaf("bob.near", 200), leaf("carol.near", 300)];
+        let hashes: Vec<CryptoHash> = leaves.iter().map(AirdropLeaf::hash).collect();
+        let mut airdrop = Airdrop::new(merkle_root(hashes.clone()).into(), 3, 600);
+        airdrop.fund(600, 7);
+
+        let deposit_id = airdrop.register(&leaves[2], 2, &[hash_pair(&hashes[0], &hashes[1]).into()]);
+        assert_eq!(deposit_id, 7);
+        assert_eq!(airdrop.registered_balance, 300);
+        assert_eq!(airdrop.unregistered_balance(), 300);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid airdrop proof")]
+    fn test_airdrop_register_other_schedule() {
+        let leaves = [leaf("alice.near", 100), leaf("bob.near", 200)];
+        let hashes: Vec<CryptoHash> = leaves.iter().map(AirdropLeaf::hash).collect();
+        let mut airdrop = Airdrop::new(merkle_root(hashes.clone()).into(), 2, 300);
+        airdrop.fund(300, 0);
+
+        airdrop.register(&leaf("alice.near", 200), 0, &[hashes[1].into()]);
+    }
+}
@@ -0,0 +1,132 @@
+use near_sdk::{near, AccountId, PublicKey};
+
+use crate::{
+    fee::FeeConfig, ft_message::FtMessage, gas::GasConfig, memo::MemoTemplates, termination::TerminationBurn,
+    throttle::ClaimThrottle, TimestampSec, WrappedBalance,
+};
+
+/// Deployment configuration managed by the contract manager.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Config {
+    /// Bulk operations with more items than this threshold emit a single summary event
+    /// with a merkle root of the items instead of an event entry per item.
+    pub event_batch_threshold: Option<u32>,
+    /// Senders allowed to trigger each `ft_on_transfer` operation.
+    pub ft_on_transfer_policy: FtOnTransferPolicy,
+    /// An account factory (e.g. a linkdrop contract) used to create accounts of new lockup owners.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub account_factory_id: Option<AccountId>,
+    /// Draft groups with a total amount above this threshold can be funded only after the manager approval.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub draft_group_approval_threshold: Option<WrappedBalance>,
+    /// An account attesting lockup owners (e.g. after KYC). When set, only attested accounts can claim.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub attestor_id: Option<AccountId>,
+    /// A cap on the total amount claimed per block or per hour, excess claims are rejected.
+    pub claim_throttle: Option<ClaimThrottle>,
+    /// The payer of a draft group can reclaim the funds of drafts not converted this long after the funding.
+    pub draft_group_conversion_deadline_sec: Option<TimestampSec>,
+    /// The number of draft groups an account can create per day, so empty groups can't inflate iteration costs.
+    pub max_draft_groups_per_day: Option<u32>,
+    /// Localized memos of claim and termination transfers.
+    pub memo_templates: MemoTemplates,
+    /// Burns the unvested balance of terminated lockups created with `burn_on_termination`.
+    pub termination_burn: Option<TerminationBurn>,
+    /// The protocol fee on claims, changed with a timelock.
+    pub fee_config: FeeConfig,
+    /// Accounts allowed to terminate lockups and to revoke their unclaimed balance.
+    pub termination_policy: TerminationPolicy,
+    /// A cap on `TokenFlows::managed_balance`, deposits exceeding it are refunded.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub max_managed_balance: Option<WrappedBalance>,
+    /// The gas of the token calls and of the callbacks.
+    pub gas: GasConfig,
+    /// Operations paused by the manager.
+    pub pause_flags: PauseFlags,
+    /// Hides the owners in `get_top_upcoming_unlocks`, returning only the unlock totals.
+    pub anonymize_upcoming_unlocks: bool,
+    /// Restricts the detailed lockup and draft views to signed views of the owners and of the privacy viewers,
+    /// only aggregates stay public.
+    pub privacy_mode: bool,
+    /// The largest share of the total balance, in bps, new lockups and drafts can unlock at creation,
+    /// so a schedule starting in the past by mistake can't be drained at once.
+    pub max_unlocked_at_creation_bps: Option<u16>,
+    /// An account managing the blocklist of sanctioned or compromised accounts, which can't receive new lockups.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub blocklist_manager_id: Option<AccountId>,
+    /// Requires a stated `reason` for terminations and whitelist removals, e.g. by the governance of clawbacks.
+    pub require_admin_reason: bool,
+    /// An ed25519 key of the service verifying emails, it signs the bindings of lockups pending
+    /// an email owner to the accounts of the email holders.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub email_attestor_key: Option<PublicKey>,
+    /// An escrow receiving the claims the owner can't receive, e.g. when not registered with the token,
+    /// with the `CliffEscrowMessage` of the lockup. Failed claims are refunded to the lockups if not set.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub claim_escrow_id: Option<AccountId>,
+}
+
+/// Subsystems paused by the manager, e.g. freezing the grant administration during an audit while
+/// beneficiaries keep claiming. Each flag pauses only its own operations.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PauseFlags {
+    /// Creating, administering, funding and converting drafts and draft groups
+    pub drafts: bool,
+    /// Claims of owners, keepers and lien holders, and sweeps to cliff escrows
+    pub claims: bool,
+    /// Terminations, unclaimed revocations and renouncements
+    pub terminations: bool,
+}
+
+impl PauseFlags {
+    pub fn is_any(&self) -> bool {
+        self.drafts || self.claims || self.terminations
+    }
+}
+
+/// Accounts allowed to send tokens for an `ft_on_transfer` operation.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum SenderPolicy {
+    #[default]
+    DepositWhitelist,
+    PayerWhitelist,
+    DepositOrPayerWhitelist,
+}
+
+/// Accounts allowed to terminate a lockup, so programs sharing the contract can't claw back each other's lockups.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TerminationPolicy {
+    #[default]
+    DepositWhitelist,
+    /// The manager, or the whitelisted account that created the lockup
+    CreatorOrManager,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FtOnTransferPolicy {
+    /// Applies to both single lockups and grants
+    pub lockup_create: SenderPolicy,
+    /// Applies to both draft groups and merkle airdrops
+    pub draft_group_funding: SenderPolicy,
+}
+
+impl FtOnTransferPolicy {
+    pub fn sender_policy(&self, message: &FtMessage) -> SenderPolicy {
+        match message {
+            FtMessage::LockupCreate(_) | FtMessage::GrantCreate(_) => self.lockup_create,
+            FtMessage::DraftGroupFunding(_) | FtMessage::AirdropFunding(_) => self.draft_group_funding,
+            // only the deposit whitelist can allocate the pool with `create_lockups`
+            FtMessage::LockupPoolFunding(_) => SenderPolicy::DepositWhitelist,
+        }
+    }
+}
@@ -1,25 +1,39 @@
 use std::collections::HashSet;
 
-use near_sdk::{
-    env, near,
-    serde::{Deserialize, Serialize},
-    AccountId,
-};
+use near_sdk::{env, near, AccountId};
 
 use crate::{
     lockup::{LockupCreate, LockupCreateView},
-    util::u128_dec_format,
-    Balance,
+    schedule::Schedule,
+    termination::VestingConditions,
+    util::{current_timestamp_sec, u128_dec_format},
+    Balance, TimestampSec,
 };
 
 pub type DraftGroupIndex = u32;
 pub type DraftIndex = u32;
 
+const DAY_SEC: TimestampSec = 24 * 60 * 60;
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Draft {
     pub draft_group_id: DraftGroupIndex,
     pub lockup_create: LockupCreate,
+    /// Splits the draft into a lockup per recipient, replacing the `account_id` of `lockup_create`.
+    #[serde(default)]
+    pub recipients: Vec<DraftRecipient>,
+}
+
+/// A recipient of a multi-recipient draft, receiving `weight` out of the total weight of the balance.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DraftRecipient {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub weight: u32,
 }
 
 impl Draft {
@@ -28,71 +42,326 @@ impl Draft {
     }
 
     pub fn assert_new_valid(&self) {
-        let amount = self.lockup_create.schedule.total_balance();
-        // any valid near account id will work fine here as a parameter
-        self.lockup_create
-            .into_lockup(&env::predecessor_account_id())
-            .assert_new_valid(amount);
+        self.validate_new().unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate_new(&self) -> Result<(), String> {
+        self.lockup_create.validate()?;
+        for lockup_create in self.lockup_creates()? {
+            let amount = lockup_create.schedule.total_balance();
+            // any valid near account id will work fine here as a parameter,
+            // using the current account id since the predecessor is not available in view calls
+            lockup_create
+                .into_lockup(&env::current_account_id())
+                .validate_new(amount)?;
+        }
+        Ok(())
     }
+
+    /// The accounts receiving the lockups of the draft.
+    pub fn account_ids(&self) -> Vec<&AccountId> {
+        if self.recipients.is_empty() {
+            vec![&self.lockup_create.account_id]
+        } else {
+            self.recipients.iter().map(|recipient| &recipient.account_id).collect()
+        }
+    }
+
+    /// The lockups the draft converts into, the balances of a multi-recipient draft
+    /// are split by weight, see `Schedule::split`.
+    pub fn lockup_creates(&self) -> Result<Vec<LockupCreate>, String> {
+        if self.recipients.is_empty() {
+            return Ok(vec![self.lockup_create.clone()]);
+        }
+        if self.lockup_create.owner_key.is_some() {
+            return Err("draft with recipients can't have an owner key".to_string());
+        }
+        if self.lockup_create.owner_email_hash.is_some() {
+            return Err("draft with recipients can't have an owner email hash".to_string());
+        }
+        let mut account_ids: HashSet<&AccountId> = HashSet::new();
+        let mut total_weight: u32 = 0;
+        for recipient in &self.recipients {
+            if recipient.weight == 0 {
+                return Err(format!("weight of recipient {} must be positive", recipient.account_id));
+            }
+            if !account_ids.insert(&recipient.account_id) {
+                return Err(format!("duplicate recipient {}", recipient.account_id));
+            }
+            total_weight = total_weight
+                .checked_add(recipient.weight)
+                .ok_or("total weight of recipients overflows")?;
+        }
+
+        let weights: Vec<u32> = self.recipients.iter().map(|recipient| recipient.weight).collect();
+        let vesting_schedules: Vec<Option<VestingConditions>> = match &self.lockup_create.vesting_schedule {
+            Some(VestingConditions::Schedule(schedule)) => schedule
+                .split(&weights)
+                .into_iter()
+                .map(|schedule| Some(VestingConditions::Schedule(schedule)))
+                .collect(),
+            Some(VestingConditions::Hash(_)) => {
+                return Err("hashed vesting schedule can't be split between recipients".to_string())
+            }
+            vesting_schedule => vec![vesting_schedule.clone(); weights.len()],
+        };
+        let vestings: Vec<Option<Schedule>> = match &self.lockup_create.vesting {
+            Some(vesting) => vesting.split(&weights).into_iter().map(Some).collect(),
+            None => vec![None; weights.len()],
+        };
+        Ok(self
+            .recipients
+            .iter()
+            .zip(self.lockup_create.schedule.split(&weights))
+            .zip(vesting_schedules.into_iter().zip(vestings))
+            .map(|((recipient, schedule), (vesting_schedule, vesting))| LockupCreate {
+                account_id: recipient.account_id.clone(),
+                schedule,
+                vesting_schedule,
+                vesting,
+                ..self.lockup_create.clone()
+            })
+            .collect())
+    }
+}
+
+/// The lifecycle of a draft group:
+/// `Open` -> `Frozen` -> `Funded` -> `Converting` -> `Closed`, or `Open` -> `Discarded`.
+/// A frozen group can be reopened to add drafts or to be discarded.
+/// A funded group not converted before the conversion deadline can be `Voided` by its payer.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum DraftGroupStatus {
+    /// Drafts can be added
+    #[default]
+    Open,
+    /// Drafts are final, the group is waiting for funding
+    Frozen,
+    /// The group is funded, drafts can be converted
+    Funded,
+    /// Some of the drafts are converted
+    Converting,
+    /// All drafts are converted
+    Closed,
+    /// The group is discarded, drafts can be deleted
+    Discarded,
+    /// The conversion deadline has passed, the remaining drafts are void and their funds are returned to the payer
+    Voided,
 }
 
 #[near(serializers=[borsh, json])]
 #[derive(Default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DraftGroup {
     pub total_amount: Balance,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub payer_id: Option<AccountId>,
     pub draft_indices: HashSet<DraftIndex>,
-    pub discarded: bool,
+    pub status: DraftGroupStatus,
+    /// Set by the manager for frozen groups requiring an approval to be funded
+    pub approved: bool,
+    /// Starts the conversion deadline
+    pub funded_timestamp: Option<TimestampSec>,
+    /// The balance transferred by the payer
+    pub funded_amount: Balance,
+    /// The balance of the drafts converted since the funding
+    pub converted_amount: Balance,
+    /// The balance of the drafts deleted after the group was discarded
+    pub deleted_amount: Balance,
 }
 
 impl DraftGroup {
+    fn assert_status(&self, allowed: &[DraftGroupStatus], action: &str) {
+        assert!(
+            allowed.contains(&self.status),
+            "cannot {action}, draft group is {:?}",
+            self.status
+        );
+    }
+
+    pub fn is_funded(&self) -> bool {
+        matches!(
+            self.status,
+            DraftGroupStatus::Funded | DraftGroupStatus::Converting | DraftGroupStatus::Closed
+        )
+    }
+
     pub fn assert_can_add_draft(&self) {
-        assert!(!self.discarded, "cannot add draft, draft group is discarded");
-        assert!(self.payer_id.is_none(), "cannot add draft, group already funded");
+        self.assert_status(&[DraftGroupStatus::Open], "add draft");
     }
 
-    pub fn assert_can_convert_draft(&self) {
-        assert!(!self.discarded, "cannot convert draft, draft group is discarded");
-        assert!(self.payer_id.is_some(), "cannot convert draft from not funded group");
+    pub fn freeze(&mut self) {
+        self.assert_status(&[DraftGroupStatus::Open], "freeze");
+        self.status = DraftGroupStatus::Frozen;
+    }
+
+    pub fn unfreeze(&mut self) {
+        self.assert_status(&[DraftGroupStatus::Frozen], "unfreeze");
+        self.status = DraftGroupStatus::Open;
+        // the drafts can be changed, so the approval is no longer valid
+        self.approved = false;
+    }
+
+    pub fn requires_approval(&self, approval_threshold: Option<Balance>) -> bool {
+        approval_threshold.is_some_and(|threshold| self.total_amount > threshold)
+    }
+
+    pub fn approve(&mut self) {
+        self.assert_status(&[DraftGroupStatus::Frozen], "approve");
+        self.approved = true;
     }
 
-    pub fn assert_can_fund(&self) {
-        assert!(!self.discarded, "cannot fund draft, draft group is discarded");
-        assert!(self.payer_id.is_none(), "draft group already funded");
+    pub fn assert_can_fund(&self, approval_threshold: Option<Balance>) {
+        self.assert_status(&[DraftGroupStatus::Frozen], "fund");
+        assert!(
+            self.approved || !self.requires_approval(approval_threshold),
+            "cannot fund, draft group requires the manager approval"
+        );
     }
 
-    pub fn fund(&mut self, payer_id: &AccountId) {
-        self.assert_can_fund();
+    pub fn fund(&mut self, payer_id: &AccountId, amount: Balance, approval_threshold: Option<Balance>) {
+        self.assert_can_fund(approval_threshold);
         self.payer_id = Some(payer_id.clone());
+        self.funded_amount = amount;
+        self.status = DraftGroupStatus::Funded;
+        self.funded_timestamp = Some(current_timestamp_sec());
+    }
+
+    /// Voids the drafts left unconverted after the conversion deadline, returns their ids and total amount.
+    pub fn void(
+        &mut self,
+        conversion_deadline_sec: TimestampSec,
+        timestamp: TimestampSec,
+    ) -> (Vec<DraftIndex>, Balance) {
+        self.assert_status(&[DraftGroupStatus::Funded, DraftGroupStatus::Converting], "void");
+        let funded_timestamp = self.funded_timestamp.expect("expected present funded_timestamp");
+        assert!(
+            timestamp >= funded_timestamp.saturating_add(conversion_deadline_sec),
+            "cannot void, the conversion deadline hasn't passed"
+        );
+        let mut draft_ids: Vec<DraftIndex> = self.draft_indices.drain().collect();
+        draft_ids.sort_unstable();
+        let amount = std::mem::take(&mut self.total_amount);
+        self.status = DraftGroupStatus::Voided;
+        (draft_ids, amount)
+    }
+
+    pub fn assert_can_convert_draft(&self) {
+        self.assert_status(
+            &[DraftGroupStatus::Funded, DraftGroupStatus::Converting],
+            "convert draft",
+        );
+    }
+
+    /// Removes the converted draft from the group and returns its payer.
+    pub fn convert_draft(&mut self, draft_id: DraftIndex, amount: Balance) -> AccountId {
+        self.assert_can_convert_draft();
+        assert!(self.draft_indices.remove(&draft_id), "Invariant");
+        assert!(self.total_amount >= amount, "Invariant");
+        // not trusting `total_amount` alone, the conversions must be covered by the transferred balance
+        let converted_amount = self
+            .converted_amount
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+        assert!(
+            converted_amount <= self.funded_amount,
+            "cannot convert draft, the converted drafts exceed the funded amount of the draft group"
+        );
+        self.converted_amount = converted_amount;
+        self.total_amount -= amount;
+        self.status = if self.draft_indices.is_empty() {
+            DraftGroupStatus::Closed
+        } else {
+            DraftGroupStatus::Converting
+        };
+        self.payer_id.clone().expect("expected present payer_id")
+    }
+
+    /// The balance left for conversion by the funding, if it diverges from `total_amount`.
+    pub fn funding_discrepancy(&self) -> Option<Balance> {
+        if !self.is_funded() {
+            return None;
+        }
+        let unconverted_amount = self.funded_amount.saturating_sub(self.converted_amount);
+        (unconverted_amount != self.total_amount).then_some(unconverted_amount)
     }
 
     pub fn assert_can_discard(&mut self) {
-        assert!(!self.discarded, "cannot discard, draft group already discarded");
-        assert!(self.payer_id.is_none(), "cannot discard, draft group already funded");
+        self.assert_status(&[DraftGroupStatus::Open], "discard");
     }
 
     pub fn discard(&mut self) {
         self.assert_can_discard();
-        self.discarded = true;
+        self.status = DraftGroupStatus::Discarded;
     }
 
     pub fn assert_can_delete_draft(&mut self) {
-        assert!(self.discarded, "cannot delete draft, draft group is not discarded");
+        self.assert_status(&[DraftGroupStatus::Discarded], "delete draft");
+    }
+
+    /// Removes the deleted draft from the discarded group.
+    pub fn delete_draft(&mut self, draft_id: DraftIndex, amount: Balance) {
+        self.assert_can_delete_draft();
+        assert!(self.total_amount >= amount, "Invariant");
+        self.total_amount -= amount;
+        self.deleted_amount += amount;
+        assert!(self.draft_indices.remove(&draft_id), "Invariant");
+    }
+
+    /// The balance of the drafts left to convert, the drafts of a discarded group are only deleted.
+    pub fn remaining_to_convert(&self) -> Balance {
+        if self.status == DraftGroupStatus::Discarded {
+            0
+        } else {
+            self.total_amount
+        }
+    }
+}
+
+/// The number of draft groups an account created within the current day.
+#[near(serializers=[borsh])]
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct DraftGroupCreations {
+    pub day: u32,
+    pub count: u32,
+}
+
+impl DraftGroupCreations {
+    pub fn record(&mut self, max_per_day: u32, timestamp: TimestampSec) {
+        let day = timestamp / DAY_SEC;
+        if self.day != day {
+            self.day = day;
+            self.count = 0;
+        }
         assert!(
-            self.payer_id.is_none(),
-            "cannot delete draft, draft group already funded"
+            self.count < max_per_day,
+            "Limit of {max_per_day} draft groups per day is reached, try later"
         );
+        self.count += 1;
     }
 }
 
 #[near(serializers=[borsh, json])]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DraftGroupView {
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub total_amount: Balance,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub payer_id: Option<AccountId>,
     pub draft_indices: Vec<DraftIndex>,
     pub discarded: bool,
     pub funded: bool,
+    pub status: DraftGroupStatus,
+    pub approved: bool,
+    pub funded_timestamp: Option<TimestampSec>,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub funded_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub converted_amount: Balance,
 }
 
 impl From<DraftGroup> for DraftGroupView {
@@ -101,17 +370,138 @@ impl From<DraftGroup> for DraftGroupView {
             total_amount: draft_group.total_amount,
             payer_id: draft_group.payer_id.clone(),
             draft_indices: draft_group.draft_indices.into_iter().collect(),
-            discarded: draft_group.discarded,
-            funded: draft_group.payer_id.is_some(),
+            discarded: draft_group.status == DraftGroupStatus::Discarded,
+            funded: draft_group.is_funded(),
+            status: draft_group.status,
+            approved: draft_group.approved,
+            funded_timestamp: draft_group.funded_timestamp,
+            funded_amount: draft_group.funded_amount,
+            converted_amount: draft_group.converted_amount,
         }
     }
 }
 
-#[derive(Serialize, Debug, PartialEq, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+/// The funding and conversion progress of a draft group, also kept after the group is closed or emptied.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DraftGroupStatusView {
+    pub status: DraftGroupStatus,
+    pub num_drafts: u32,
+    /// The balance of the drafts left in the group
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub funded_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub converted_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub remaining_to_convert: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub deleted_amount: Balance,
+}
+
+impl From<DraftGroup> for DraftGroupStatusView {
+    fn from(draft_group: DraftGroup) -> Self {
+        Self {
+            status: draft_group.status,
+            num_drafts: u32::try_from(draft_group.draft_indices.len()).unwrap(),
+            total_amount: draft_group.total_amount,
+            funded_amount: draft_group.funded_amount,
+            converted_amount: draft_group.converted_amount,
+            remaining_to_convert: draft_group.remaining_to_convert(),
+            deleted_amount: draft_group.deleted_amount,
+        }
+    }
+}
+
+/// The summary of draft groups waiting for the same operator action.
+#[near(serializers=[json])]
+#[derive(Default, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PendingDraftGroups {
+    pub draft_group_ids: Vec<DraftGroupIndex>,
+    pub num_drafts: u32,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_amount: Balance,
+}
+
+impl PendingDraftGroups {
+    pub fn add(&mut self, draft_group_id: DraftGroupIndex, draft_group: &DraftGroup) {
+        self.draft_group_ids.push(draft_group_id);
+        self.num_drafts += u32::try_from(draft_group.draft_indices.len()).unwrap();
+        self.total_amount = self.total_amount.saturating_add(draft_group.total_amount);
+    }
+}
+
+/// The queue of the draft operators.
+#[near(serializers=[json])]
+#[derive(Default, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PendingOperatorActions {
+    /// Frozen groups waiting for the funding
+    pub awaiting_funding: PendingDraftGroups,
+    /// Funded groups with drafts left to convert
+    pub awaiting_conversion: PendingDraftGroups,
+    /// Discarded groups with drafts left to delete
+    pub awaiting_deletion: PendingDraftGroups,
+}
+
+impl PendingOperatorActions {
+    pub fn add(&mut self, draft_group_id: DraftGroupIndex, draft_group: &DraftGroup) {
+        let pending = match draft_group.status {
+            DraftGroupStatus::Frozen => &mut self.awaiting_funding,
+            DraftGroupStatus::Funded | DraftGroupStatus::Converting => &mut self.awaiting_conversion,
+            DraftGroupStatus::Discarded if !draft_group.draft_indices.is_empty() => &mut self.awaiting_deletion,
+            _ => return,
+        };
+        pending.add(draft_group_id, draft_group);
+    }
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DraftConversionIssue {
+    pub draft_id: DraftIndex,
+    pub reason: String,
+}
+
+/// The result of a dry run of a draft group conversion.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct DraftGroupConversionReport {
+    pub draft_group_id: DraftGroupIndex,
+    /// Issues preventing the conversion of any draft in the group
+    pub group_issues: Vec<String>,
+    /// Issues of individual drafts
+    pub draft_issues: Vec<DraftConversionIssue>,
+    /// Worth a second look, but not preventing the conversion, e.g. a backdated schedule
+    #[serde(default)]
+    pub draft_warnings: Vec<DraftConversionIssue>,
+}
+
+impl DraftGroupConversionReport {
+    pub fn is_convertible(&self) -> bool {
+        self.group_issues.is_empty() && self.draft_issues.is_empty()
+    }
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DraftView {
     pub draft_group_id: DraftGroupIndex,
     pub lockup_create: LockupCreateView,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<DraftRecipient>,
 }
 
 impl From<Draft> for DraftView {
@@ -119,6 +509,166 @@ impl From<Draft> for DraftView {
         Self {
             draft_group_id: draft.draft_group_id,
             lockup_create: draft.lockup_create.into(),
+            recipients: draft.recipients,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn recipient(account_id: &str, weight: u32) -> DraftRecipient {
+        DraftRecipient {
+            account_id: AccountId::from_str(account_id).unwrap(),
+            weight,
         }
     }
+
+    #[test]
+    fn test_draft_split_by_weight() {
+        let mut draft = Draft {
+            draft_group_id: 0,
+            lockup_create: LockupCreate::new_unlocked(AccountId::from_str("syndicate.near").unwrap(), 1_000),
+            recipients: vec![recipient("alice.near", 3), recipient("bob.near", 1)],
+        };
+        let lockup_creates = draft.lockup_creates().unwrap();
+        assert_eq!(lockup_creates.len(), 2);
+        assert_eq!(lockup_creates[0].account_id, AccountId::from_str("alice.near").unwrap());
+        assert_eq!(lockup_creates[0].schedule, Schedule::new_unlocked(750));
+        assert_eq!(lockup_creates[1].schedule, Schedule::new_unlocked(250));
+
+        // the shares are floored and the last recipient gets the remainder
+        draft.recipients.push(recipient("carol.near", 3));
+        let lockup_creates = draft.lockup_creates().unwrap();
+        assert_eq!(lockup_creates[0].schedule, Schedule::new_unlocked(428));
+        assert_eq!(lockup_creates[1].schedule, Schedule::new_unlocked(142));
+        assert_eq!(lockup_creates[2].schedule, Schedule::new_unlocked(430));
+
+        draft.recipients = vec![recipient("alice.near", 1), recipient("alice.near", 1)];
+        assert_eq!(
+            draft.lockup_creates(),
+            Err("duplicate recipient alice.near".to_string())
+        );
+    }
+
+    #[test]
+    fn test_draft_group_funding_coverage() {
+        let payer_id = AccountId::from_str("payer.near").unwrap();
+        let mut draft_group = DraftGroup {
+            total_amount: 300,
+            draft_indices: HashSet::from([0, 1]),
+            status: DraftGroupStatus::Frozen,
+            ..Default::default()
+        };
+        draft_group.fund(&payer_id, 300, None);
+        draft_group.convert_draft(0, 100);
+        assert_eq!(draft_group.funding_discrepancy(), None);
+
+        // a drift of the bookkeeping
+        draft_group.total_amount += 50;
+        assert_eq!(draft_group.funding_discrepancy(), Some(200));
+    }
+
+    #[test]
+    fn test_draft_group_status_amounts() {
+        let payer_id = AccountId::from_str("payer.near").unwrap();
+        let mut draft_group = DraftGroup {
+            total_amount: 300,
+            draft_indices: HashSet::from([0, 1]),
+            status: DraftGroupStatus::Frozen,
+            ..Default::default()
+        };
+        draft_group.fund(&payer_id, 300, None);
+        draft_group.convert_draft(0, 100);
+
+        let status: DraftGroupStatusView = draft_group.into();
+        assert_eq!(status.status, DraftGroupStatus::Converting);
+        assert_eq!(status.num_drafts, 1);
+        assert_eq!(status.funded_amount, 300);
+        assert_eq!(status.converted_amount, 100);
+        assert_eq!(status.remaining_to_convert, 200);
+
+        let mut discarded = DraftGroup {
+            total_amount: 300,
+            draft_indices: HashSet::from([0, 1]),
+            ..Default::default()
+        };
+        discarded.discard();
+        discarded.delete_draft(1, 200);
+        let status: DraftGroupStatusView = discarded.into();
+        assert_eq!(status.total_amount, 100);
+        assert_eq!(status.remaining_to_convert, 0);
+        assert_eq!(status.deleted_amount, 200);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot convert draft, the converted drafts exceed the funded amount of the draft group")]
+    fn test_draft_group_conversion_exceeding_funding() {
+        let payer_id = AccountId::from_str("payer.near").unwrap();
+        let mut draft_group = DraftGroup {
+            total_amount: 300,
+            draft_indices: HashSet::from([0]),
+            status: DraftGroupStatus::Frozen,
+            ..Default::default()
+        };
+        draft_group.fund(&payer_id, 200, None);
+        draft_group.convert_draft(0, 300);
+    }
+
+    #[test]
+    fn test_pending_operator_actions() {
+        let draft_group = |status, draft_indices: &[DraftIndex], total_amount| DraftGroup {
+            total_amount,
+            draft_indices: draft_indices.iter().copied().collect(),
+            status,
+            ..Default::default()
+        };
+        let mut actions = PendingOperatorActions::default();
+        actions.add(0, &draft_group(DraftGroupStatus::Open, &[0], 100));
+        actions.add(1, &draft_group(DraftGroupStatus::Frozen, &[1, 2], 200));
+        actions.add(2, &draft_group(DraftGroupStatus::Converting, &[3], 300));
+        actions.add(3, &draft_group(DraftGroupStatus::Funded, &[4, 5], 400));
+        actions.add(4, &draft_group(DraftGroupStatus::Discarded, &[6], 500));
+        actions.add(5, &draft_group(DraftGroupStatus::Discarded, &[], 0));
+
+        assert_eq!(
+            actions.awaiting_funding,
+            PendingDraftGroups {
+                draft_group_ids: vec![1],
+                num_drafts: 2,
+                total_amount: 200,
+            }
+        );
+        assert_eq!(
+            actions.awaiting_conversion,
+            PendingDraftGroups {
+                draft_group_ids: vec![2, 3],
+                num_drafts: 3,
+                total_amount: 700,
+            }
+        );
+        assert_eq!(actions.awaiting_deletion.draft_group_ids, vec![4]);
+    }
+
+    #[test]
+    fn test_draft_group_creations() {
+        let mut creations = DraftGroupCreations::default();
+        creations.record(2, DAY_SEC);
+        creations.record(2, DAY_SEC + 10);
+        assert_eq!(creations.count, 2);
+
+        creations.record(2, 2 * DAY_SEC);
+        assert_eq!(creations, DraftGroupCreations { day: 2, count: 1 });
+    }
+
+    #[test]
+    #[should_panic(expected = "Limit of 1 draft groups per day is reached, try later")]
+    fn test_draft_group_creations_limit() {
+        let mut creations = DraftGroupCreations::default();
+        creations.record(1, DAY_SEC);
+        creations.record(1, 2 * DAY_SEC - 1);
+    }
 }
@@ -0,0 +1,51 @@
+use near_sdk::{near, AccountId, PromiseOrValue};
+use nitka::make_integration_version;
+
+use crate::{
+    lockup::{LockupId, LockupIndex},
+    WrappedBalance,
+};
+
+/// The `ft_transfer_call` message of a sweep into a cliff escrow,
+/// the escrow stakes the received tokens on behalf of the lockup owner.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CliffEscrowMessage {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub owner_id: AccountId,
+    pub lockup_id: LockupId,
+}
+
+#[make_integration_version]
+pub trait EscrowApi {
+    /// Forwards the claimable balance of the lockup to its cliff escrow with `ft_transfer_call`,
+    /// instead of the first claim of the owner. Anyone can call it, e.g. the escrow right after the cliff.
+    /// The escrow is removed once it accepts a part of the balance, the unused part is returned to the lockup.
+    /// Sweeps are exempt from the claim fee. Returns the balance accepted by the escrow.
+    fn sweep_to_escrow(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// Removes the cliff escrow, so the owner claims the balance as usual. Only the lockup owner can call it.
+    fn remove_cliff_escrow(&mut self, lockup_index: LockupIndex);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use near_sdk::serde_json;
+
+    use super::*;
+
+    #[test]
+    fn test_cliff_escrow_message() {
+        let message = CliffEscrowMessage {
+            owner_id: AccountId::from_str("alice.near").unwrap(),
+            lockup_id: 7,
+        };
+        assert_eq!(
+            serde_json::to_string(&message).unwrap(),
+            r#"{"owner_id":"alice.near","lockup_id":7}"#
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use near_sdk::{ext_contract, AccountId, PromiseOrValue};
+
+use crate::{
+    lockup::{LockupId, LockupIndex, LockupStatus, LockupView, UnlockRate},
+    stats::Health,
+    TimestampSec, WrappedBalance,
+};
+
+/// The interface of a deployed lockup contract for typed cross-contract calls,
+/// e.g. `ext_ft_lockup::ext(lockup_id).get_account_lockups(account_id, None, None)`.
+/// The views return the same shapes as `LockupViewApi`, handle the results in own callbacks.
+#[ext_contract(ext_ft_lockup)]
+pub trait FtLockup {
+    fn get_token_account_id(&self) -> AccountId;
+
+    fn get_num_lockups(&self) -> u32;
+
+    fn get_lockup(
+        &self,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<LockupView>;
+
+    fn get_lockup_by_id(
+        &self,
+        lockup_id: LockupId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<(LockupIndex, LockupView)>;
+
+    fn get_account_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    fn get_lockup_status(&self, index: LockupIndex) -> Option<LockupStatus>;
+
+    fn get_unlock_rate(&self, lockup_index: LockupIndex, at_timestamp: Option<TimestampSec>) -> Option<UnlockRate>;
+
+    fn health(&self) -> Health;
+
+    /// Claims the lockup on behalf of its owner, requires the keeper incentive enabled by the owner.
+    /// Returns the claimed balance.
+    fn claim_for_keeper(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// The lien calls of a lending protocol, see `LienApi`. Require one yoctoNEAR attached.
+    fn place_lien(&mut self, lockup_index: LockupIndex, amount: WrappedBalance, redirect_claims: bool);
+
+    fn release_lien(&mut self, lockup_index: LockupIndex);
+
+    fn claim_lien(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// Sweeps the first claim of the lockup to its cliff escrow, see `EscrowApi`.
+    fn sweep_to_escrow(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+}
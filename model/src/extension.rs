@@ -0,0 +1,73 @@
+use near_sdk::near;
+
+use crate::{lockup::LockupIndex, TimestampSec};
+
+/// The delay before a scheduled extension of all schedules can be applied, so beneficiaries can react to it.
+pub const SCHEDULE_EXTENSION_DELAY_SEC: TimestampSec = 3 * 24 * 60 * 60;
+
+/// The longest extension of all schedules at once, e.g. to compensate a token contract outage.
+pub const MAX_SCHEDULE_EXTENSION_SEC: TimestampSec = 30 * 24 * 60 * 60;
+
+/// An extension of all lockup schedules scheduled by the manager, applied in batches of lockups.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ScheduleExtension {
+    /// The duration every schedule is postponed by
+    pub by_sec: TimestampSec,
+    /// The timestamp the remaining schedules are postponed after, captured when the extension is scheduled,
+    /// so every batch of lockups is postponed from the same point
+    pub timestamp: TimestampSec,
+    /// The timestamp the extension can be applied since
+    pub executable_at: TimestampSec,
+    /// The index of the next lockup to extend, nonzero once the extension is being applied
+    pub next_index: LockupIndex,
+}
+
+impl ScheduleExtension {
+    pub fn new(by_sec: TimestampSec, timestamp: TimestampSec) -> Self {
+        assert!(
+            by_sec > 0 && by_sec <= MAX_SCHEDULE_EXTENSION_SEC,
+            "the extension should be positive and not exceed {MAX_SCHEDULE_EXTENSION_SEC} seconds"
+        );
+        Self {
+            by_sec,
+            timestamp,
+            executable_at: timestamp.saturating_add(SCHEDULE_EXTENSION_DELAY_SEC),
+            next_index: 0,
+        }
+    }
+
+    pub fn assert_executable(&self, timestamp: TimestampSec) {
+        assert!(
+            self.executable_at <= timestamp,
+            "the schedule extension can't be applied before {}",
+            self.executable_at
+        );
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.next_index > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_extension_delay() {
+        let extension = ScheduleExtension::new(14 * 24 * 60 * 60, 1_000);
+
+        assert_eq!(extension.timestamp, 1_000);
+        assert_eq!(extension.executable_at, 1_000 + SCHEDULE_EXTENSION_DELAY_SEC);
+        assert!(!extension.is_started());
+        extension.assert_executable(extension.executable_at);
+    }
+
+    #[test]
+    #[should_panic(expected = "the extension should be positive and not exceed")]
+    fn test_schedule_extension_bounded() {
+        ScheduleExtension::new(MAX_SCHEDULE_EXTENSION_SEC + 1, 1_000);
+    }
+}
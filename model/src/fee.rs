@@ -0,0 +1,126 @@
+use near_sdk::{near, AccountId};
+
+use crate::{u256::U256, Balance, TimestampSec};
+
+/// The highest protocol fee, 5% of the claim.
+pub const MAX_CLAIM_FEE_BPS: u16 = 500;
+
+/// The delay before a claim fee change takes effect, so lockup owners can claim at the old fee first.
+pub const CLAIM_FEE_CHANGE_DELAY_SEC: TimestampSec = 7 * 24 * 60 * 60;
+
+const BPS_DENOMINATOR: u16 = 10_000;
+
+/// A protocol fee withheld from every claim and transferred to the fee collector.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClaimFee {
+    /// The share of the claimed balance, in basis points
+    pub fee_bps: u16,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub collector_id: AccountId,
+}
+
+impl ClaimFee {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fee_bps == 0 || self.fee_bps > MAX_CLAIM_FEE_BPS {
+            return Err(format!("claim fee should be between 1 and {MAX_CLAIM_FEE_BPS} bps"));
+        }
+        Ok(())
+    }
+
+    pub fn fee(&self, claim_amount: Balance) -> Balance {
+        (U256::from(claim_amount) * U256::from(self.fee_bps) / U256::from(BPS_DENOMINATOR)).as_u128()
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClaimFeeChange {
+    /// The new claim fee, `None` to disable the fee
+    pub claim_fee: Option<ClaimFee>,
+    pub effective_at: TimestampSec,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FeeConfig {
+    pub claim_fee: Option<ClaimFee>,
+    pub pending_change: Option<ClaimFeeChange>,
+}
+
+impl FeeConfig {
+    /// The claim fee at the given timestamp.
+    pub fn claim_fee(&self, timestamp: TimestampSec) -> Option<&ClaimFee> {
+        match &self.pending_change {
+            Some(change) if change.effective_at <= timestamp => change.claim_fee.as_ref(),
+            _ => self.claim_fee.as_ref(),
+        }
+    }
+
+    /// Schedules the change replacing any pending one, returns the timestamp it takes effect.
+    pub fn change(&mut self, claim_fee: Option<ClaimFee>, timestamp: TimestampSec) -> TimestampSec {
+        if let Some(claim_fee) = &claim_fee {
+            claim_fee.validate().unwrap_or_else(|error| panic!("{error}"));
+        }
+        self.claim_fee = self.claim_fee(timestamp).cloned();
+        let effective_at = timestamp.saturating_add(CLAIM_FEE_CHANGE_DELAY_SEC);
+        self.pending_change = Some(ClaimFeeChange {
+            claim_fee,
+            effective_at,
+        });
+        effective_at
+    }
+
+    pub fn cancel_change(&mut self, timestamp: TimestampSec) {
+        self.claim_fee = self.claim_fee(timestamp).cloned();
+        let change = self.pending_change.take();
+        assert!(
+            change.is_some_and(|change| change.effective_at > timestamp),
+            "no pending claim fee change"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_claim_fee_change_delay() {
+        let claim_fee = ClaimFee {
+            fee_bps: 100,
+            collector_id: AccountId::from_str("fees.near").unwrap(),
+        };
+        assert_eq!(claim_fee.fee(1_050), 10);
+
+        let mut fee_config = FeeConfig::default();
+        let effective_at = fee_config.change(Some(claim_fee.clone()), 1_000);
+        assert_eq!(effective_at, 1_000 + CLAIM_FEE_CHANGE_DELAY_SEC);
+        assert_eq!(fee_config.claim_fee(effective_at - 1), None);
+        assert_eq!(fee_config.claim_fee(effective_at), Some(&claim_fee));
+
+        // disabling the fee is delayed as well
+        fee_config.change(None, effective_at);
+        assert_eq!(fee_config.claim_fee(effective_at + 1), Some(&claim_fee));
+
+        fee_config.cancel_change(effective_at + 1);
+        assert_eq!(fee_config.claim_fee(u32::MAX), Some(&claim_fee));
+    }
+
+    #[test]
+    #[should_panic(expected = "claim fee should be between 1 and 500 bps")]
+    fn test_claim_fee_cap() {
+        FeeConfig::default().change(
+            Some(ClaimFee {
+                fee_bps: MAX_CLAIM_FEE_BPS + 1,
+                collector_id: AccountId::from_str("fees.near").unwrap(),
+            }),
+            0,
+        );
+    }
+}
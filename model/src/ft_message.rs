@@ -1,19 +1,81 @@
-use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near, serde_json};
 
-use crate::{draft::DraftGroupIndex, lockup::LockupCreate};
+use crate::{airdrop::AirdropIndex, draft::DraftGroupIndex, grant::GrantCreate, lockup::LockupCreate};
 
-#[derive(Serialize, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[near(serializers=[json])]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct DraftGroupFunding {
     pub draft_group_id: DraftGroupIndex,
     // use remaining gas to try converting drafts
     pub try_convert: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[near(serializers=[json])]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AirdropFunding {
+    pub airdrop_id: AirdropIndex,
+}
+
+/// Credits the deposit to the lockup pool of the sender, `{"fund_lockup_pool": true}`, see `create_lockups`.
+#[near(serializers=[json])]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupPoolFunding {
+    pub fund_lockup_pool: bool,
+}
+
+#[near(serializers=[json])]
 #[serde(untagged)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum FtMessage {
     LockupCreate(LockupCreate),
     DraftGroupFunding(DraftGroupFunding),
+    GrantCreate(GrantCreate),
+    AirdropFunding(AirdropFunding),
+    LockupPoolFunding(LockupPoolFunding),
+}
+
+impl FtMessage {
+    /// The `msg` of `ft_transfer_call` carrying the message.
+    pub fn to_msg(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use near_sdk::AccountId;
+
+    use super::*;
+
+    #[test]
+    fn test_ft_message_msg_round_trip() {
+        let lockup_create = LockupCreate::new_unlocked(AccountId::from_str("alice.near").unwrap(), 1_000);
+        let msg = FtMessage::LockupCreate(lockup_create).to_msg();
+        assert!(matches!(
+            serde_json::from_str(&msg).unwrap(),
+            FtMessage::LockupCreate(LockupCreate { account_id, .. }) if account_id.as_str() == "alice.near"
+        ));
+
+        let msg = FtMessage::DraftGroupFunding(DraftGroupFunding {
+            draft_group_id: 7,
+            try_convert: Some(true),
+        })
+        .to_msg();
+        assert_eq!(msg, r#"{"draft_group_id":7,"try_convert":true}"#);
+        assert!(matches!(
+            serde_json::from_str(&msg).unwrap(),
+            FtMessage::DraftGroupFunding(DraftGroupFunding {
+                draft_group_id: 7,
+                try_convert: Some(true),
+            })
+        ));
+
+        let msg = FtMessage::LockupPoolFunding(LockupPoolFunding { fund_lockup_pool: true }).to_msg();
+        assert!(matches!(
+            serde_json::from_str(&msg).unwrap(),
+            FtMessage::LockupPoolFunding(LockupPoolFunding { fund_lockup_pool: true })
+        ));
+    }
 }
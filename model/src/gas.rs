@@ -0,0 +1,183 @@
+use near_sdk::{near, Gas};
+
+/// The largest gas attached to a single call or callback.
+pub const MAX_GAS_CONFIG_VALUE: Gas = Gas::from_tgas(100);
+
+/// The largest gas of claiming a single lockup with its transfer, so a claim always fits into a transaction.
+pub const MAX_GAS_FOR_SINGLE_CLAIM: Gas = Gas::from_tgas(200);
+
+/// The gas attached to the token calls and to the callbacks of the contract. The manager can tune it
+/// when the protocol gas costs change, but every value stays between half its default and `MAX_GAS_CONFIG_VALUE`.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GasConfig {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub ft_transfer: Gas,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub ft_transfer_call: Gas,
+    /// The gas of `after_ft_transfer` without the lockups processed by it.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub after_ft_transfer: Gas,
+    /// Measured gas of processing a single lockup claim in `after_ft_transfer`, including the rollback.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub after_ft_transfer_per_lockup: Gas,
+    /// Measured gas of claiming a single lockup, excluding reading it.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub per_claimed_lockup: Gas,
+    /// The overhead of scheduling a cross-contract call.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub ext_call_cost: Gas,
+    /// Drafts of a draft group funded with `try_convert` are converted only with at least this gas left.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub min_for_convert: Gas,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub ft_metadata: Gas,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub after_ft_metadata: Gas,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub create_account: Gas,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub after_create_account: Gas,
+    /// The view of a vesting oracle fetching the vested balance of `VestingConditions::External`.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub vesting_oracle: Gas,
+    /// The callback completing the termination once the vested balance is fetched, including the transfer.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub after_vesting_oracle: Gas,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            ft_transfer: Gas::from_tgas(15),
+            ft_transfer_call: Gas::from_tgas(60),
+            after_ft_transfer: Gas::from_tgas(20),
+            after_ft_transfer_per_lockup: Gas::from_tgas(2),
+            per_claimed_lockup: Gas::from_tgas(2),
+            ext_call_cost: Gas::from_tgas(10),
+            min_for_convert: Gas::from_tgas(15),
+            ft_metadata: Gas::from_tgas(5),
+            after_ft_metadata: Gas::from_tgas(5),
+            create_account: Gas::from_tgas(30),
+            after_create_account: Gas::from_tgas(25),
+            vesting_oracle: Gas::from_tgas(10),
+            after_vesting_oracle: Gas::from_tgas(60),
+        }
+    }
+}
+
+impl GasConfig {
+    /// The gas a claim reserves for the token transfer and its callback.
+    pub fn claim_reserved_gas(&self) -> Gas {
+        Gas::from_gas(self.ft_transfer.as_gas() + self.after_ft_transfer.as_gas() + self.ext_call_cost.as_gas())
+    }
+
+    /// The gas of claiming a single lockup, including its processing in the callback.
+    pub fn claim_gas_per_lockup(&self) -> Gas {
+        Gas::from_gas(self.per_claimed_lockup.as_gas() + self.after_ft_transfer_per_lockup.as_gas())
+    }
+
+    /// The gas of the `after_ft_transfer` callback of a claim of `num_lockups` lockups.
+    pub fn after_ft_transfer_gas(&self, num_lockups: usize) -> Gas {
+        let num_lockups = u64::try_from(num_lockups).unwrap();
+        Gas::from_gas(self.after_ft_transfer.as_gas() + self.after_ft_transfer_per_lockup.as_gas() * num_lockups)
+    }
+
+    pub fn assert_valid(&self) {
+        self.validate().unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let default = Self::default();
+        let values = [
+            ("ft_transfer", self.ft_transfer, default.ft_transfer),
+            ("ft_transfer_call", self.ft_transfer_call, default.ft_transfer_call),
+            ("after_ft_transfer", self.after_ft_transfer, default.after_ft_transfer),
+            (
+                "after_ft_transfer_per_lockup",
+                self.after_ft_transfer_per_lockup,
+                default.after_ft_transfer_per_lockup,
+            ),
+            (
+                "per_claimed_lockup",
+                self.per_claimed_lockup,
+                default.per_claimed_lockup,
+            ),
+            ("ext_call_cost", self.ext_call_cost, default.ext_call_cost),
+            ("min_for_convert", self.min_for_convert, default.min_for_convert),
+            ("ft_metadata", self.ft_metadata, default.ft_metadata),
+            ("after_ft_metadata", self.after_ft_metadata, default.after_ft_metadata),
+            ("create_account", self.create_account, default.create_account),
+            (
+                "after_create_account",
+                self.after_create_account,
+                default.after_create_account,
+            ),
+            ("vesting_oracle", self.vesting_oracle, default.vesting_oracle),
+            (
+                "after_vesting_oracle",
+                self.after_vesting_oracle,
+                default.after_vesting_oracle,
+            ),
+        ];
+        for (name, value, default_value) in values {
+            if value.as_gas() < default_value.as_gas() / 2 || value > MAX_GAS_CONFIG_VALUE {
+                return Err(format!(
+                    "{name} should be between {} and {MAX_GAS_CONFIG_VALUE}",
+                    Gas::from_gas(default_value.as_gas() / 2)
+                ));
+            }
+        }
+        if self.claim_reserved_gas().as_gas() + self.claim_gas_per_lockup().as_gas() > MAX_GAS_FOR_SINGLE_CLAIM.as_gas()
+        {
+            return Err(format!(
+                "claiming a single lockup should take at most {MAX_GAS_FOR_SINGLE_CLAIM}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_config_default_is_valid() {
+        let gas_config = GasConfig::default();
+        gas_config.assert_valid();
+        assert_eq!(gas_config.claim_reserved_gas(), Gas::from_tgas(45));
+        assert_eq!(gas_config.after_ft_transfer_gas(3), Gas::from_tgas(26));
+    }
+
+    #[test]
+    fn test_gas_config_bounds() {
+        let too_low = GasConfig {
+            after_ft_transfer: Gas::from_tgas(9),
+            ..GasConfig::default()
+        };
+        assert!(too_low
+            .validate()
+            .unwrap_err()
+            .starts_with("after_ft_transfer should be between"));
+
+        let too_high = GasConfig {
+            ft_transfer_call: Gas::from_tgas(101),
+            ..GasConfig::default()
+        };
+        assert!(too_high.validate().is_err());
+
+        let claim_too_expensive = GasConfig {
+            ft_transfer: Gas::from_tgas(100),
+            after_ft_transfer: Gas::from_tgas(100),
+            ..GasConfig::default()
+        };
+        assert_eq!(
+            claim_too_expensive.validate(),
+            Err(format!(
+                "claiming a single lockup should take at most {MAX_GAS_FOR_SINGLE_CLAIM}"
+            ))
+        );
+    }
+}
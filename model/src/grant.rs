@@ -0,0 +1,78 @@
+use near_sdk::near;
+
+use crate::{
+    lockup::{LockupCreate, LockupIndex, LockupView},
+    util::u128_dec_format,
+    Balance,
+};
+
+pub type GrantIndex = u32;
+
+/// A grant split into sequential stages with different beneficiaries, e.g. the first part vests
+/// to a fiscal sponsor and the remainder to the grantee. Every stage becomes a lockup.
+#[near(serializers=[json])]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GrantCreate {
+    pub stages: Vec<LockupCreate>,
+}
+
+impl GrantCreate {
+    pub fn total_balance(&self) -> Balance {
+        self.stages.iter().map(|stage| stage.schedule.total_balance()).sum()
+    }
+
+    pub fn assert_valid(&self) {
+        self.validate().unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stages.len() < 2 {
+            return Err("grant should have at least 2 stages".to_string());
+        }
+        for stage in &self.stages {
+            stage.validate()?;
+        }
+        for (index, stages) in self.stages.windows(2).enumerate() {
+            let start_timestamp = stages[1]
+                .schedule
+                .0
+                .first()
+                .map_or(0, |checkpoint| checkpoint.timestamp);
+            if start_timestamp < stages[0].schedule.finish_timestamp() {
+                return Err(format!(
+                    "grant stage {} starts before the previous one finishes",
+                    index + 1
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct GrantView {
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub claimed_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub unclaimed_balance: Balance,
+    /// Lockups of the stages in order
+    pub lockups: Vec<(LockupIndex, LockupView)>,
+}
+
+impl From<Vec<(LockupIndex, LockupView)>> for GrantView {
+    fn from(lockups: Vec<(LockupIndex, LockupView)>) -> Self {
+        Self {
+            total_balance: lockups.iter().map(|(_, lockup)| lockup.total_balance).sum(),
+            claimed_balance: lockups.iter().map(|(_, lockup)| lockup.claimed_balance).sum(),
+            unclaimed_balance: lockups.iter().map(|(_, lockup)| lockup.unclaimed_balance).sum(),
+            lockups,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use near_sdk::near;
+
+use crate::{u256::U256, Balance, WrappedBalance};
+
+/// The highest keeper fee, 10% of the claim.
+pub const MAX_KEEPER_FEE_BPS: u16 = 1_000;
+
+const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Allows anyone to claim the lockup on behalf of its owner for a share of the claimed balance,
+/// so passive owners receive their tokens without claiming themselves.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct KeeperIncentive {
+    /// The share of the claimed balance paid to the keeper, in basis points
+    pub fee_bps: u16,
+    /// The keeper can claim only when the claimable balance is at least this amount
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub min_claim_amount: WrappedBalance,
+}
+
+impl KeeperIncentive {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fee_bps > MAX_KEEPER_FEE_BPS {
+            return Err(format!("keeper fee should be at most {MAX_KEEPER_FEE_BPS} bps"));
+        }
+        if self.min_claim_amount.0 == 0 {
+            return Err("keeper minimum claim amount must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn fee(&self, claim_amount: Balance) -> Balance {
+        (U256::from(claim_amount) * U256::from(self.fee_bps) / U256::from(BPS_DENOMINATOR)).as_u128()
+    }
+}
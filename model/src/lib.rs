@@ -1,14 +1,39 @@
 use near_sdk::{json_types::U128, AccountId};
 
+pub mod airdrop;
+pub mod config;
 pub mod draft;
+pub mod escrow;
+pub mod ext;
+pub mod extension;
+pub mod fee;
 pub mod ft_message;
+pub mod gas;
+pub mod grant;
+pub mod keeper;
+pub mod lien;
 pub mod lockup;
 pub mod lockup_api;
+pub mod memo;
+pub mod merkle;
+pub mod note;
+pub mod payout;
+pub mod pending;
+pub mod pool;
+pub mod privacy;
+pub mod provenance;
+pub mod reason;
+pub mod remap;
+pub mod revocation;
 pub mod schedule;
+pub mod signed;
+pub mod stats;
 pub mod termination;
+pub mod throttle;
 pub mod update;
 pub mod util;
 pub mod view_api;
+pub mod web4;
 
 pub type WrappedBalance = U128;
 pub type TimestampSec = u32;
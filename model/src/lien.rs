@@ -0,0 +1,80 @@
+use near_sdk::{near, AccountId, PromiseOrValue};
+use nitka::make_integration_version;
+
+use crate::{lockup::LockupIndex, util::u128_dec_format, Balance, WrappedBalance};
+
+/// A lien of a whitelisted lien holder, e.g. a lending protocol accepting the lockup as collateral.
+/// The owner can't claim the reserved balance until the lien is released.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Lien {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub holder_id: AccountId,
+    /// The unclaimed balance reserved for the lien holder
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: Balance,
+    /// Whether the lien holder claims the reserved balance as it unlocks
+    pub redirect_claims: bool,
+}
+
+impl Lien {
+    /// The part of the claimable balance the owner can claim.
+    pub fn owner_claimable(&self, claimable: Balance) -> Balance {
+        claimable.saturating_sub(self.amount)
+    }
+
+    /// The part of the claimable balance the lien holder can claim.
+    pub fn holder_claimable(&self, claimable: Balance) -> Balance {
+        if self.redirect_claims {
+            claimable.min(self.amount)
+        } else {
+            0
+        }
+    }
+}
+
+#[make_integration_version]
+pub trait LienApi {
+    /// Allows the lien holder to place a lien on the lockup, or revokes the approval. Only the lockup owner can call it.
+    fn approve_lien(&mut self, lockup_index: LockupIndex, holder_id: Option<AccountId>);
+
+    /// Places a lien on the lockup approved by its owner. Only a whitelisted lien holder can call it.
+    fn place_lien(&mut self, lockup_index: LockupIndex, amount: WrappedBalance, redirect_claims: bool);
+
+    /// Releases the remaining reserved balance to the owner. Only the lien holder can call it.
+    fn release_lien(&mut self, lockup_index: LockupIndex);
+
+    /// Claims the unlocked part of the reserved balance to the lien holder, the lien is released once fully claimed.
+    /// Lien claims are exempt from the claim fee.
+    fn claim_lien(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    fn add_to_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>);
+
+    fn remove_from_lien_holders_whitelist(&mut self, account_ids: Vec<AccountId>);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_lien_claimable_split() {
+        let mut lien = Lien {
+            holder_id: AccountId::from_str("lender.near").unwrap(),
+            amount: 100,
+            redirect_claims: true,
+        };
+        assert_eq!(lien.owner_claimable(60), 0);
+        assert_eq!(lien.holder_claimable(60), 60);
+        assert_eq!(lien.owner_claimable(150), 50);
+        assert_eq!(lien.holder_claimable(150), 100);
+
+        lien.redirect_claims = false;
+        assert_eq!(lien.owner_claimable(150), 50);
+        assert_eq!(lien.holder_claimable(150), 0);
+    }
+}
@@ -1,37 +1,198 @@
-use near_sdk::{
-    near,
-    serde::{Deserialize, Serialize},
-    AccountId,
-};
+use std::collections::HashSet;
+
+use near_sdk::{env, json_types::Base58CryptoHash, near, AccountId, CurveType, PublicKey};
 
 use crate::{
-    schedule::Schedule,
-    termination::{TerminationConfig, VestingConditions},
-    util::{current_timestamp_sec, u128_dec_format},
+    grant::GrantIndex,
+    keeper::KeeperIncentive,
+    lien::Lien,
+    note::LockupNote,
+    schedule::{Schedule, UnlockCurve, MAX_SCHEDULE_CHECKPOINTS},
+    stats::LockupClaimStats,
+    termination::{TerminationConfig, TerminationSummary, VestingConditions},
+    u256::U256,
+    util::{current_timestamp_sec, format_balance, u128_dec_format},
     Balance, TimestampSec, WrappedBalance,
 };
 
+const BPS_DENOMINATOR: u16 = 10_000;
+
 pub type LockupIndex = u32;
+/// The identifier of a lockup in events and views, equal to its `LockupIndex` since lockups are never moved.
+pub type LockupId = u64;
+
+/// Tokens become claimable according to the lockup schedule, but never ahead of the vesting schedule.
+fn unlocked_balance(
+    schedule: &Schedule,
+    curve: Option<UnlockCurve>,
+    vesting_schedule: Option<&Schedule>,
+    timestamp: TimestampSec,
+) -> Balance {
+    let unlocked_balance = schedule.curved_unlocked_balance(timestamp, curve);
+    vesting_schedule.map_or(unlocked_balance, |vesting_schedule| {
+        std::cmp::min(unlocked_balance, vesting_schedule.unlocked_balance(timestamp))
+    })
+}
+
+/// The schedule postponed after `timestamp`, `None` if it's already fully unlocked.
+fn postponed(schedule: &Schedule, timestamp: TimestampSec, by_sec: TimestampSec) -> Result<Option<Schedule>, String> {
+    if timestamp >= schedule.finish_timestamp() {
+        return Ok(None);
+    }
+    let mut schedule = schedule.clone();
+    schedule.postpone(timestamp, by_sec);
+    if schedule.0.len() > MAX_SCHEDULE_CHECKPOINTS {
+        return Err(format!(
+            "the extended schedule would have more than {MAX_SCHEDULE_CHECKPOINTS} checkpoints"
+        ));
+    }
+    Ok(Some(schedule))
+}
+
+/// The lifecycle stage of a lockup, derived from its balances and the contract state.
+#[near(serializers=[json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LockupStage {
+    /// Tokens are unlocking
+    #[default]
+    Active,
+    /// The whole balance is unlocked and some of it is unclaimed
+    FullyUnlocked,
+    /// The whole balance is claimed
+    FullyClaimed,
+    /// The unvested balance was returned by a termination, an unclaimed revocation or a renouncement
+    Terminated,
+    /// The owner can't claim until the attestor attests the account
+    Paused,
+    /// The owner account is not bound to the owner key or to the owner email yet
+    PendingAcceptance,
+}
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
-#[serde(crate = "near_sdk::serde")]
+/// The pending transfers of a lockup, it can't be claimed or terminated until they resolve.
+#[near(serializers=[json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LockupStatus {
+    /// No transfer is pending
+    #[default]
+    Active,
+    /// A claim transfer is pending
+    ClaimInFlight,
+    /// A termination or revocation transfer of the lockup balance is pending
+    TerminationInFlight,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct LockupClaim {
     pub index: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub claim_amount: WrappedBalance,
     pub is_final: bool,
 }
 
-#[near(serializers=[borsh, json])]
+/// The result of all claim methods. Only `claimed_balance` is required and every other field has a default,
+/// so new fields are added without breaking the existing clients.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClaimResult {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub claimed_balance: WrappedBalance,
+    /// Whether some lockups were left unclaimed to fit the call into the gas limit
+    #[serde(default)]
+    pub has_more: bool,
+    /// The balance claimed from each lockup
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[number, string]>"))]
+    pub claimed_lockups: Vec<(LockupIndex, WrappedBalance)>,
+    /// The lockups whose requested amount exceeded the claimable balance with `max_amounts`,
+    /// with the requested amount. They are claimed up to the claimable balance instead.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[number, string]>"))]
+    pub clamped_lockups: Vec<(LockupIndex, WrappedBalance)>,
+    /// The lockups skipped by claiming all lockups, since a claim or a termination of them is in flight
+    #[serde(default)]
+    pub in_flight_lockups: Vec<LockupIndex>,
+    /// The balances the owner couldn't receive, forwarded to `Config::claim_escrow_id` instead of the refund.
+    /// They are claimed once accepted by the escrow, see the `ft_lockup_sweep_to_escrow` events.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[number, string]>"))]
+    pub escrowed_lockups: Vec<(LockupIndex, WrappedBalance)>,
+}
+
+/// The order of the account lockups in `get_account_lockups_paged`.
+#[near(serializers=[json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum LockupSortBy {
+    /// The oldest lockups first
+    #[default]
+    CreatedAt,
+    /// The largest unclaimed balances first
+    Claimable,
+    /// The earliest full unlocks first
+    EndDate,
+}
+
+impl LockupSortBy {
+    /// Sorts the lockups at the given timestamp, the ties are ordered by the lockup index.
+    pub fn sort(&self, lockups: &mut [(LockupIndex, Lockup)], timestamp: TimestampSec) {
+        match self {
+            Self::CreatedAt => lockups.sort_unstable_by_key(|(index, _)| *index),
+            Self::Claimable => lockups.sort_by_cached_key(|(index, lockup)| {
+                let unclaimed_balance = lockup
+                    .unlocked_balance(timestamp)
+                    .saturating_sub(lockup.claimed_balance);
+                (std::cmp::Reverse(unclaimed_balance), *index)
+            }),
+            Self::EndDate => lockups.sort_by_cached_key(|(index, lockup)| (lockup.unlock_finish_timestamp(), *index)),
+        }
+    }
+}
+
+/// A page of the account lockups.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AccountLockupsPage {
+    pub lockups: Vec<(LockupIndex, LockupView)>,
+    /// The number of all lockups of the account
+    pub total: u32,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct UnlockRate {
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub tokens_per_day: Balance,
+    /// The timestamp of the next rate change, `None` once fully unlocked
+    pub next_change_timestamp: Option<TimestampSec>,
+}
+
+/// The stored lockup state. It's never accepted as an input: lockups are created from `LockupCreate`,
+/// and the state like `claimed_balance` is initialized by the contract.
+#[near(serializers=[borsh])]
 #[derive(Debug, PartialEq, Clone)]
 pub struct Lockup {
     pub account_id: AccountId,
     pub schedule: Schedule,
 
-    #[serde(default)]
-    #[serde(with = "u128_dec_format")]
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    /// The shape of the unlocks between the checkpoints. It's stored by the contract apart from the lockup
+    /// to keep the layout of the existing lockups, and set when the lockup is loaded.
+    #[borsh(skip)]
+    pub curve: Option<UnlockCurve>,
+    /// The vesting schedule capping the claimable balance, stored and loaded the same way as the curve.
+    /// Unlike the vesting conditions of the termination config, it stays in force after the termination.
+    #[borsh(skip)]
+    pub vesting: Option<Schedule>,
 }
 
 impl Lockup {
@@ -41,6 +202,8 @@ impl Lockup {
             schedule: Schedule::new_unlocked_since(total_balance, timestamp),
             claimed_balance: 0,
             termination_config: None,
+            curve: None,
+            vesting: None,
         }
     }
 
@@ -49,7 +212,7 @@ impl Lockup {
     }
 
     pub fn claim(&mut self, index: LockupIndex, claim_amount: Balance) -> LockupClaim {
-        let unlocked_balance = self.schedule.unlocked_balance(current_timestamp_sec());
+        let unlocked_balance = self.unlocked_balance(current_timestamp_sec());
         let balance_claimed_new = self
             .claimed_balance
             .checked_add(claim_amount)
@@ -67,12 +230,176 @@ impl Lockup {
         }
     }
 
-    pub fn assert_new_valid(&self, total_balance: Balance) {
+    /// Fully claimed lockups are not listed in the account lockups.
+    pub fn is_fully_claimed(&self) -> bool {
+        self.claimed_balance == self.schedule.total_balance()
+    }
+
+    /// The stage derived from the balances only, the contract state can override it.
+    pub fn stage(&self, timestamp: TimestampSec) -> LockupStage {
+        if self.is_fully_claimed() {
+            LockupStage::FullyClaimed
+        } else if self.unlocked_balance(timestamp) == self.schedule.total_balance() {
+            LockupStage::FullyUnlocked
+        } else {
+            LockupStage::Active
+        }
+    }
+
+    /// The balance unlocked by the lockup schedule, capped by the vesting schedule if it's present.
+    pub fn unlocked_balance(&self, timestamp: TimestampSec) -> Balance {
+        unlocked_balance(&self.schedule, self.curve, self.vesting_schedule(), timestamp)
+    }
+
+    /// The rate of the schedule capping the unlocked balance at the moment.
+    pub fn unlock_rate(&self, timestamp: TimestampSec) -> UnlockRate {
+        let (mut tokens_per_day, mut next_change_timestamp) = self.schedule.curved_unlock_rate(timestamp, self.curve);
+        if let Some(vesting_schedule) = self.vesting_schedule() {
+            let (vesting_tokens_per_day, vesting_next_change_timestamp) = vesting_schedule.unlock_rate(timestamp);
+            if (vesting_schedule.unlocked_balance(timestamp), vesting_tokens_per_day)
+                < (
+                    self.schedule.curved_unlocked_balance(timestamp, self.curve),
+                    tokens_per_day,
+                )
+            {
+                tokens_per_day = vesting_tokens_per_day;
+            }
+            next_change_timestamp = next_change_timestamp
+                .into_iter()
+                .chain(vesting_next_change_timestamp)
+                .min();
+        }
+        UnlockRate {
+            tokens_per_day,
+            next_change_timestamp,
+        }
+    }
+
+    /// The timestamp when the lockup becomes fully unlocked.
+    pub fn unlock_finish_timestamp(&self) -> TimestampSec {
+        let finish_timestamp = self.schedule.finish_timestamp();
+        self.vesting_schedule().map_or(finish_timestamp, |vesting_schedule| {
+            finish_timestamp.max(vesting_schedule.finish_timestamp())
+        })
+    }
+
+    /// Removes the unclaimed balance of a fully unlocked lockup and returns it.
+    pub fn revoke_unclaimed(&mut self, timestamp: TimestampSec) -> Balance {
+        let total_balance = self.schedule.total_balance();
         assert_eq!(
-            self.claimed_balance, 0,
-            "The initial lockup claimed balance should be 0"
+            self.unlocked_balance(timestamp),
+            total_balance,
+            "lockup is not fully unlocked"
         );
-        self.schedule.assert_valid(total_balance);
+        let revoked_balance = total_balance - self.claimed_balance;
+        assert!(revoked_balance > 0, "nothing to revoke");
+        self.schedule.terminate(self.claimed_balance, timestamp);
+        revoked_balance
+    }
+
+    /// Forfeits the whole unclaimed balance at the owner's request, only the claimed part of the schedule is kept.
+    /// Returns the forfeited balance.
+    pub fn renounce(&mut self, timestamp: TimestampSec) -> Balance {
+        let forfeited_balance = self.schedule.total_balance() - self.claimed_balance;
+        assert!(forfeited_balance > 0, "nothing to renounce");
+        self.termination_config = None;
+        // the kept part is collapsed to fully unlocked now, cutting the schedule at the claimed balance
+        // could leave its end after `timestamp` and, read without the curve, unlock less than claimed
+        self.curve = None;
+        if self.claimed_balance > 0 {
+            self.schedule = Schedule::new_unlocked_since(self.claimed_balance, timestamp);
+        } else {
+            self.schedule.terminate(0, timestamp);
+        }
+        forfeited_balance
+    }
+
+    pub fn vesting_schedule(&self) -> Option<&Schedule> {
+        self.vesting.as_ref()
+    }
+
+    /// The timestamp the lockup starts unlocking after, nothing is unlocked until then.
+    pub fn unlock_start(&self) -> TimestampSec {
+        let unlock_start = self.schedule.unlock_start();
+        self.vesting_schedule().map_or(unlock_start, |vesting_schedule| {
+            std::cmp::max(unlock_start, vesting_schedule.unlock_start())
+        })
+    }
+
+    /// Voluntarily postpones the remaining part of the lockup schedule.
+    /// Returns the previous and the new finish timestamps.
+    pub fn relock(&mut self, extend_by_sec: TimestampSec) -> (TimestampSec, TimestampSec) {
+        assert!(self.curve.is_none(), "a lockup with an unlock curve can't be relocked");
+        let old_finish = self.schedule.finish_timestamp();
+        self.schedule.postpone(current_timestamp_sec(), extend_by_sec);
+        if let Some(TerminationConfig {
+            vesting_schedule: VestingConditions::Schedule(vesting_schedule),
+            ..
+        }) = &self.termination_config
+        {
+            self.schedule.assert_valid_termination_schedule(vesting_schedule);
+        }
+
+        (old_finish, self.schedule.finish_timestamp())
+    }
+
+    /// Postpones the parts of the lockup and vesting schedules after `timestamp`, see `extend_all_schedules`.
+    /// Hashed vesting schedules are kept as is. Returns whether any schedule was postponed, or the reason
+    /// the lockup can't be extended: an unlock curve, a claim past the balance unlocked at `timestamp`
+    /// or a postponed schedule exceeding `MAX_SCHEDULE_CHECKPOINTS`. The lockup is unchanged on error.
+    pub fn extend_schedules(&mut self, timestamp: TimestampSec, by_sec: TimestampSec) -> Result<bool, String> {
+        if self.curve.is_some() {
+            return Err("the lockup has an unlock curve".to_string());
+        }
+        if self.claimed_balance > self.unlocked_balance(timestamp) {
+            return Err("the lockup was claimed past the balance unlocked at the extension timestamp".to_string());
+        }
+        let schedule = postponed(&self.schedule, timestamp, by_sec)?;
+        let termination_schedule = match &self.termination_config {
+            Some(TerminationConfig {
+                vesting_schedule: VestingConditions::Schedule(vesting_schedule),
+                ..
+            }) => postponed(vesting_schedule, timestamp, by_sec)?,
+            _ => None,
+        };
+        let vesting = match &self.vesting {
+            Some(vesting) => postponed(vesting, timestamp, by_sec)?,
+            None => None,
+        };
+
+        let extended = schedule.is_some() || termination_schedule.is_some() || vesting.is_some();
+        if let Some(schedule) = schedule {
+            self.schedule = schedule;
+        }
+        if let (
+            Some(TerminationConfig {
+                vesting_schedule: VestingConditions::Schedule(vesting_schedule),
+                ..
+            }),
+            Some(termination_schedule),
+        ) = (&mut self.termination_config, termination_schedule)
+        {
+            *vesting_schedule = termination_schedule;
+        }
+        if vesting.is_some() {
+            self.vesting = vesting;
+        }
+        Ok(extended)
+    }
+
+    pub fn assert_new_valid(&self, total_balance: Balance) {
+        self.validate_new(total_balance).unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate_new(&self, total_balance: Balance) -> Result<(), String> {
+        if self.claimed_balance != 0 {
+            return Err("The initial lockup claimed balance should be 0".to_string());
+        }
+        self.schedule.validate(total_balance)?;
+        if let Some(vesting) = &self.vesting {
+            // The lockup schedule may be ahead of the vesting, the claimable balance is capped by the vesting.
+            vesting.validate(total_balance)?;
+        }
 
         if let Some(termination_config) = &self.termination_config {
             match &termination_config.vesting_schedule {
@@ -80,20 +407,189 @@ impl Lockup {
                     // Ok, using lockup schedule.
                 }
                 VestingConditions::Schedule(schedule) => {
-                    schedule.assert_valid(total_balance);
-                    self.schedule.assert_valid_termination_schedule(schedule);
+                    schedule.validate(total_balance)?;
+                    self.schedule.validate_termination_schedule(schedule)?;
+                }
+                VestingConditions::Hash(_) => {
+                    // Validated against the total balance once revealed, see `Lockup::reveal_vesting_schedule`.
+                }
+                VestingConditions::External { method, .. } => {
+                    // The oracle is checked against the whitelist at termination, it may be whitelisted later.
+                    if method.is_empty() {
+                        return Err("The vesting oracle method should be specified".to_string());
+                    }
                 }
             }
         }
+        Ok(())
     }
 }
 
+pub const MAX_TAGS: usize = 8;
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Tags are short labels of an allocation category, e.g. `team` or `investor-round-a`.
+pub fn validate_tags(tags: &[String]) -> Result<(), String> {
+    if tags.len() > MAX_TAGS {
+        return Err(format!("too many tags, at most {MAX_TAGS} are allowed"));
+    }
+    let mut unique_tags = HashSet::new();
+    for tag in tags {
+        if tag.is_empty() || tag.len() > MAX_TAG_LENGTH {
+            return Err(format!("tag length must be between 1 and {MAX_TAG_LENGTH}: {tag}"));
+        }
+        if !tag
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(format!("tag must consist of lowercase letters, digits, '-' or '_': {tag}"));
+        }
+        if !unique_tags.insert(tag) {
+            return Err(format!("duplicate tag: {tag}"));
+        }
+    }
+    Ok(())
+}
+
+pub const MAX_CLAIM_MEMO_LENGTH: usize = 64;
+
+/// A custom claim memo is forwarded to `ft_transfer`, so it's limited to printable ASCII.
+pub fn validate_claim_memo(memo: &str) -> Result<(), String> {
+    if memo.is_empty() || memo.len() > MAX_CLAIM_MEMO_LENGTH {
+        return Err(format!("memo length must be between 1 and {MAX_CLAIM_MEMO_LENGTH}"));
+    }
+    if !memo.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+        return Err("memo must consist of printable ASCII characters".to_string());
+    }
+    Ok(())
+}
+
+/// Splits the total across the lockups given as `(index, claimable balance, schedule start)`,
+/// claiming the lockups which started unlocking first in full, see `claim_amount`.
+pub fn allocate_claim_amount(
+    mut lockups: Vec<(LockupIndex, Balance, TimestampSec)>,
+    total: Balance,
+) -> Result<Vec<(LockupIndex, Balance)>, String> {
+    let claimable: Balance = lockups.iter().map(|(_, amount, _)| amount).sum();
+    if total > claimable {
+        return Err(format!(
+            "The requested amount {total} exceeds the claimable balance {claimable}"
+        ));
+    }
+    lockups.sort_unstable_by_key(|(index, _, start)| (*start, *index));
+    let mut remaining = total;
+    let mut amounts = vec![];
+    for (index, amount, _) in lockups {
+        if remaining == 0 {
+            break;
+        }
+        let claim_amount = std::cmp::min(amount, remaining);
+        amounts.push((index, claim_amount));
+        remaining -= claim_amount;
+    }
+    Ok(amounts)
+}
+
+/// The input to create a lockup, in `ft_on_transfer` messages and drafts.
 #[near(serializers=[borsh, json])]
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct LockupCreate {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub account_id: AccountId,
     pub schedule: Schedule,
     pub vesting_schedule: Option<VestingConditions>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// An ed25519 key of an owner without an account. The lockup is held by the contract account
+    /// until the key holder binds it to their account with `claim_pending_lockup`.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub owner_key: Option<PublicKey>,
+    /// The sha256 hash of the email of an owner without an account or a key. The lockup is held by
+    /// the contract account until `Config::email_attestor_key` signs its binding to the account of
+    /// the email holder, see `claim_pending_lockup`.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub owner_email_hash: Option<Base58CryptoHash>,
+    /// Allows to revoke the unclaimed balance back to the payer if the owner doesn't claim
+    /// for this long after the full unlock.
+    #[serde(default)]
+    pub unclaimed_revocation_after_sec: Option<TimestampSec>,
+    /// Converts step unlocks of the schedule into linear unlocks over this window, see `Schedule::smoothed`.
+    #[serde(default)]
+    pub smoothing_sec: Option<TimestampSec>,
+    /// The sha256 hash of the signed off-chain agreement, can't be changed after the creation.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub agreement_hash: Option<Base58CryptoHash>,
+    /// Allows anyone to claim on behalf of the owner for a fee, see `claim_for_keeper`.
+    #[serde(default)]
+    pub keeper_incentive: Option<KeeperIncentive>,
+    /// Burns the unvested balance upon termination instead of returning it to the payer,
+    /// see `Config::termination_burn`.
+    #[serde(default)]
+    pub burn_on_termination: bool,
+    /// Shapes the unlocks between the checkpoints of the schedule, linear if not set.
+    /// A curved lockup can't have a vesting schedule and can't be relocked.
+    #[serde(default)]
+    pub curve: Option<UnlockCurve>,
+    /// Forwards the first claim after the cliff to this escrow contract, see `EscrowApi::sweep_to_escrow`.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub cliff_escrow_id: Option<AccountId>,
+    /// The vesting schedule of what the owner owns, e.g. a 4-year vesting under a 1-year transfer lock
+    /// in `schedule`. The claimable balance follows the lockup schedule but never exceeds the vested balance.
+    #[serde(default)]
+    pub vesting: Option<Schedule>,
+}
+
+impl LockupCreate {
+    pub fn assert_valid(&self) {
+        self.validate().unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        validate_tags(&self.tags)?;
+        if let Some(owner_key) = &self.owner_key {
+            if owner_key.curve_type() != CurveType::ED25519 {
+                return Err("owner key must be an ed25519 key".to_string());
+            }
+            if self.account_id != env::current_account_id() {
+                return Err("lockup with owner key must be held by the contract account".to_string());
+            }
+        }
+        if self.owner_email_hash.is_some() {
+            if self.owner_key.is_some() {
+                return Err("lockup can't have both an owner key and an owner email hash".to_string());
+            }
+            if self.account_id != env::current_account_id() {
+                return Err("lockup with owner email hash must be held by the contract account".to_string());
+            }
+        }
+        if self.unclaimed_revocation_after_sec == Some(0) {
+            return Err("unclaimed revocation period must be positive".to_string());
+        }
+        if self.smoothing_sec == Some(0) {
+            return Err("smoothing window must be positive".to_string());
+        }
+        if let Some(keeper_incentive) = &self.keeper_incentive {
+            keeper_incentive.validate()?;
+        }
+        if self.burn_on_termination && self.vesting_schedule.is_none() {
+            return Err("burn on termination requires a vesting schedule".to_string());
+        }
+        if let Some(curve) = &self.curve {
+            curve.validate()?;
+            if self.vesting_schedule.is_some() || self.vesting.is_some() {
+                return Err("lockup with an unlock curve can't have a vesting schedule".to_string());
+            }
+        }
+        if self.cliff_escrow_id.as_ref() == Some(&env::current_account_id()) {
+            return Err("cliff escrow can't be the lockup contract".to_string());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -103,94 +599,292 @@ impl LockupCreate {
             account_id,
             schedule: Schedule::new_unlocked(total_balance),
             vesting_schedule: None,
+            tags: vec![],
+            owner_key: None,
+            owner_email_hash: None,
+            unclaimed_revocation_after_sec: None,
+            smoothing_sec: None,
+            agreement_hash: None,
+            keeper_incentive: None,
+            burn_on_termination: false,
+            curve: None,
+            cliff_escrow_id: None,
+            vesting: None,
         }
     }
 }
 
 impl LockupCreate {
+    /// The schedule of the created lockup, with the smoothing applied.
+    pub fn lockup_schedule(&self) -> Schedule {
+        match self.smoothing_sec {
+            Some(smoothing_sec) => self.schedule.smoothed(smoothing_sec),
+            None => self.schedule.clone(),
+        }
+    }
+
+    /// Rejects lockups unlocking more than `max_unlocked_bps` of their total balance at `timestamp`,
+    /// e.g. a schedule starting years in the past by mistake.
+    pub fn validate_unlocked_at_creation(&self, max_unlocked_bps: u16, timestamp: TimestampSec) -> Result<(), String> {
+        // the payer doesn't affect the unlocked balance
+        let lockup = self.into_lockup(&self.account_id);
+        let total_balance = U256::from(lockup.schedule.total_balance());
+        let unlocked_balance = U256::from(lockup.unlocked_balance(timestamp));
+        if unlocked_balance * U256::from(BPS_DENOMINATOR) > total_balance * U256::from(max_unlocked_bps) {
+            return Err(format!(
+                "the lockup of {} unlocks more than {max_unlocked_bps} bps at creation",
+                self.account_id
+            ));
+        }
+        Ok(())
+    }
+
     pub fn into_lockup(&self, payer_id: &AccountId) -> Lockup {
         let vesting_schedule = self.vesting_schedule.clone();
         Lockup {
             account_id: self.account_id.clone(),
-            schedule: self.schedule.clone(),
+            schedule: self.lockup_schedule(),
             claimed_balance: 0,
             termination_config: vesting_schedule.map(|vesting_schedule| TerminationConfig {
                 beneficiary_id: payer_id.clone(),
                 vesting_schedule,
             }),
+            curve: self.curve,
+            vesting: self.vesting.clone(),
         }
     }
 }
 
-#[derive(Serialize, Debug, PartialEq, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct LockupView {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub account_id: AccountId,
     pub schedule: Schedule,
 
     #[serde(default)]
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    /// Whether the unvested balance can be revoked by the deposit whitelist
+    #[serde(default)]
+    pub is_terminable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub termination: Option<TerminationSummary>,
 
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub total_balance: Balance,
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub unclaimed_balance: Balance,
     /// The current timestamp
     pub timestamp: TimestampSec,
+    /// Balances formatted as decimal strings according to the token decimals, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humanized: Option<LockupHumanizedView>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The key of the owner, while the lockup is pending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub pending_owner_key: Option<PublicKey>,
+    /// The email hash of the owner, while the lockup is pending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub pending_owner_email_hash: Option<Base58CryptoHash>,
+    /// The grant this lockup is a stage of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grant_id: Option<GrantIndex>,
+    /// The hash of the off-chain agreement bound to the lockup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub agreement_hash: Option<Base58CryptoHash>,
+    /// The fee for claiming on behalf of the owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keeper_incentive: Option<KeeperIncentive>,
+    /// The lien reserving a part of the unclaimed balance for a lien holder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lien: Option<Lien>,
+    /// The shape of the unlocks between the checkpoints, linear if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<UnlockCurve>,
+    /// The escrow the first claim is forwarded to, until it's swept or removed by the owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub cliff_escrow_id: Option<AccountId>,
+    /// Whether the unvested balance is burned upon termination instead of returned to the payer
+    #[serde(default)]
+    pub burn_on_termination: bool,
+    /// The account that created the lockup: the sender of the deposit or the creator of the draft group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub created_by: Option<AccountId>,
+    /// The current note of the operators, see `get_lockup_note_history`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<LockupNote>,
+    /// The stable id of the lockup
+    #[serde(default)]
+    pub lockup_id: LockupId,
+    /// The part of `claimed_balance` whose transfer hasn't resolved yet, it's returned to the lockup if it fails
+    #[serde(default, with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub in_flight_claim_balance: Balance,
+    #[serde(default)]
+    pub status: LockupStatus,
+    /// The lifecycle stage of the lockup
+    #[serde(default)]
+    pub stage: LockupStage,
+    /// The vesting schedule capping the claimable balance, see `LockupCreate::vesting`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vesting: Option<Schedule>,
+    /// The number of claims of the lockup and their gas charged to it
+    #[serde(default)]
+    pub claim_stats: LockupClaimStats,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupHumanizedView {
+    pub claimed_balance: String,
+    pub total_balance: String,
+    pub unclaimed_balance: String,
+}
+
+impl LockupView {
+    pub fn humanize(mut self, decimals: u8) -> Self {
+        self.humanized = Some(LockupHumanizedView {
+            claimed_balance: format_balance(self.claimed_balance, decimals),
+            total_balance: format_balance(self.total_balance, decimals),
+            unclaimed_balance: format_balance(self.unclaimed_balance, decimals),
+        });
+        self
+    }
 }
 
 impl From<Lockup> for LockupView {
     fn from(lockup: Lockup) -> Self {
+        Self::at_timestamp(lockup, current_timestamp_sec())
+    }
+}
+
+impl LockupView {
+    /// The view of the lockup at the given timestamp, e.g. to query future positions.
+    /// The claimed balance is always the current one, so the unclaimed balance in the past is a lower bound.
+    pub fn at_timestamp(lockup: Lockup, timestamp: TimestampSec) -> Self {
         let total_balance = lockup.schedule.total_balance();
-        let timestamp = current_timestamp_sec();
-        let unclaimed_balance = lockup.schedule.unlocked_balance(timestamp) - lockup.claimed_balance;
+        let unclaimed_balance = lockup
+            .unlocked_balance(timestamp)
+            .saturating_sub(lockup.claimed_balance);
+        let stage = lockup.stage(timestamp);
         let Lockup {
             account_id,
             schedule,
             claimed_balance,
             termination_config,
+            curve,
+            vesting,
         } = lockup;
+        let termination = termination_config.as_ref().map(Into::into);
         Self {
             account_id,
             schedule,
             claimed_balance,
+            is_terminable: termination_config.is_some(),
+            termination,
             termination_config,
             total_balance,
             unclaimed_balance,
             timestamp,
+            humanized: None,
+            tags: vec![],
+            pending_owner_key: None,
+            pending_owner_email_hash: None,
+            grant_id: None,
+            agreement_hash: None,
+            keeper_incentive: None,
+            lien: None,
+            curve,
+            cliff_escrow_id: None,
+            burn_on_termination: false,
+            created_by: None,
+            note: None,
+            vesting,
+            lockup_id: 0,
+            in_flight_claim_balance: 0,
+            status: LockupStatus::Active,
+            stage,
+            claim_stats: LockupClaimStats::default(),
         }
     }
 }
 
-#[derive(Serialize, Debug, PartialEq, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct LockupCreateView {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub account_id: AccountId,
     pub schedule: Schedule,
     pub vesting_schedule: Option<VestingConditions>,
 
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub claimed_balance: Balance,
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub total_balance: Balance,
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub unclaimed_balance: Balance,
     /// The current timestamp
     pub timestamp: TimestampSec,
+    pub tags: Vec<String>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub owner_key: Option<PublicKey>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub owner_email_hash: Option<Base58CryptoHash>,
+    pub unclaimed_revocation_after_sec: Option<TimestampSec>,
+    pub smoothing_sec: Option<TimestampSec>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub agreement_hash: Option<Base58CryptoHash>,
+    pub keeper_incentive: Option<KeeperIncentive>,
+    pub burn_on_termination: bool,
+    pub curve: Option<UnlockCurve>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub cliff_escrow_id: Option<AccountId>,
+    pub vesting: Option<Schedule>,
 }
 
 impl From<LockupCreate> for LockupCreateView {
     fn from(lockup_create: LockupCreate) -> Self {
         let total_balance = lockup_create.schedule.total_balance();
         let timestamp = current_timestamp_sec();
-        let unclaimed_balance = lockup_create.schedule.unlocked_balance(timestamp);
+        let unclaimed_balance = unlocked_balance(
+            &lockup_create.lockup_schedule(),
+            lockup_create.curve,
+            lockup_create.vesting.as_ref(),
+            timestamp,
+        );
         let LockupCreate {
             account_id,
             schedule,
             vesting_schedule,
+            tags,
+            owner_key,
+            owner_email_hash,
+            unclaimed_revocation_after_sec,
+            smoothing_sec,
+            agreement_hash,
+            keeper_incentive,
+            burn_on_termination,
+            curve,
+            cliff_escrow_id,
+            vesting,
         } = lockup_create;
         Self {
             account_id,
@@ -200,6 +894,330 @@ impl From<LockupCreate> for LockupCreateView {
             total_balance,
             unclaimed_balance,
             timestamp,
+            tags,
+            owner_key,
+            owner_email_hash,
+            unclaimed_revocation_after_sec,
+            smoothing_sec,
+            agreement_hash,
+            keeper_incentive,
+            burn_on_termination,
+            curve,
+            cliff_escrow_id,
+            vesting,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::schedule::Checkpoint;
+
+    #[test]
+    fn test_claim_result_additive_fields() {
+        let result: ClaimResult = near_sdk::serde_json::from_str(r#"{"claimed_balance":"100"}"#).unwrap();
+        assert_eq!(
+            result,
+            ClaimResult {
+                claimed_balance: 100.into(),
+                has_more: false,
+                claimed_lockups: vec![],
+                clamped_lockups: vec![],
+                in_flight_lockups: vec![],
+                escrowed_lockups: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_allocate_claim_amount_oldest_first() {
+        let lockups = vec![(0, 100, 2_000), (1, 50, 1_000), (2, 70, 1_000)];
+
+        assert_eq!(
+            allocate_claim_amount(lockups.clone(), 150),
+            Ok(vec![(1, 50), (2, 70), (0, 30)])
+        );
+        assert_eq!(allocate_claim_amount(lockups.clone(), 0), Ok(vec![]));
+        assert_eq!(
+            allocate_claim_amount(lockups, 221),
+            Err("The requested amount 221 exceeds the claimable balance 220".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lockup_renounce() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut lockup = Lockup {
+            schedule: Schedule(vec![
+                Checkpoint {
+                    timestamp: 1_000,
+                    balance: 0,
+                },
+                Checkpoint {
+                    timestamp: 2_000,
+                    balance: 1_000,
+                },
+            ]),
+            ..Lockup::new_unlocked(account_id, 0)
+        };
+        lockup.claimed_balance = 250;
+        lockup.curve = Some(UnlockCurve::Quadratic);
+
+        assert_eq!(lockup.renounce(1_500), 750);
+        assert_eq!(lockup.schedule.total_balance(), 250);
+        assert_eq!(lockup.unlocked_balance(1_500), 250);
+        assert!(lockup.is_fully_claimed());
+    }
+
+    #[test]
+    fn test_lockup_renounce_exponential_decay() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let new_lockup = || Lockup {
+            schedule: Schedule(vec![
+                Checkpoint {
+                    timestamp: 1_000,
+                    balance: 0,
+                },
+                Checkpoint {
+                    timestamp: 2_000,
+                    balance: 1_000,
+                },
+            ]),
+            curve: Some(UnlockCurve::ExponentialDecay { half_life_sec: 100 }),
+            ..Lockup::new_unlocked(account_id.clone(), 0)
+        };
+
+        // front-loaded, 7/8 of the balance is unlocked after 3 half-lives while the linear cut would be at 1_875
+        let mut lockup = new_lockup();
+        lockup.claimed_balance = lockup.unlocked_balance(1_300);
+        assert!(lockup.claimed_balance > 850);
+        let claimed_balance = lockup.claimed_balance;
+
+        assert_eq!(lockup.renounce(1_300), 1_000 - claimed_balance);
+        assert_eq!(lockup.schedule.total_balance(), claimed_balance);
+        assert_eq!(lockup.unlocked_balance(1_300), claimed_balance);
+        assert_eq!(lockup.unlocked_balance(1_500), claimed_balance);
+        assert!(lockup.is_fully_claimed());
+
+        let mut lockup = new_lockup();
+        assert_eq!(lockup.renounce(1_200), 1_000);
+        assert_eq!(lockup.schedule.total_balance(), 0);
+        assert_eq!(lockup.unlocked_balance(1_200), 0);
+    }
+
+    #[test]
+    fn test_lockup_create_unlocked_at_creation() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let lockup_create = LockupCreate {
+            schedule: Schedule(vec![
+                Checkpoint {
+                    timestamp: 1_000,
+                    balance: 0,
+                },
+                Checkpoint {
+                    timestamp: 2_000,
+                    balance: 1_000,
+                },
+            ]),
+            ..LockupCreate::new_unlocked(account_id, 1_000)
+        };
+
+        assert!(lockup_create.validate_unlocked_at_creation(0, 1_000).is_ok());
+        assert!(lockup_create.validate_unlocked_at_creation(1_000, 1_100).is_ok());
+        assert!(lockup_create.validate_unlocked_at_creation(1_000, 1_101).is_err());
+        assert!(lockup_create.validate_unlocked_at_creation(10_000, 3_000).is_ok());
+    }
+
+    #[test]
+    fn test_lockup_create_owner_email_hash() {
+        let lockup_create = LockupCreate {
+            owner_email_hash: Some([1; 32].into()),
+            ..LockupCreate::new_unlocked(env::current_account_id(), 1_000)
+        };
+        assert!(lockup_create.validate().is_ok());
+
+        let held_by_owner = LockupCreate {
+            account_id: AccountId::from_str("bob.near").unwrap(),
+            ..lockup_create.clone()
+        };
+        assert_eq!(
+            held_by_owner.validate(),
+            Err("lockup with owner email hash must be held by the contract account".to_string())
+        );
+
+        let with_owner_key = LockupCreate {
+            owner_key: Some(PublicKey::from_parts(CurveType::ED25519, vec![0; 32]).unwrap()),
+            ..lockup_create
+        };
+        assert_eq!(
+            with_owner_key.validate(),
+            Err("lockup can't have both an owner key and an owner email hash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lockup_sort_by() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut claimed = Lockup::new_unlocked_since(account_id.clone(), 300, 1_000);
+        claimed.claimed_balance = 250;
+        let mut lockups = vec![
+            (2, Lockup::new_unlocked_since(account_id.clone(), 100, 2_000)),
+            (0, claimed),
+            (1, Lockup::new_unlocked_since(account_id, 200, 3_000)),
+        ];
+        let indices = |lockups: &[(LockupIndex, Lockup)]| lockups.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+
+        LockupSortBy::CreatedAt.sort(&mut lockups, 2_500);
+        assert_eq!(indices(&lockups), vec![0, 1, 2]);
+
+        LockupSortBy::Claimable.sort(&mut lockups, 2_500);
+        assert_eq!(indices(&lockups), vec![2, 0, 1]);
+
+        LockupSortBy::EndDate.sort(&mut lockups, 2_500);
+        assert_eq!(indices(&lockups), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_extend_schedules() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut lockup = Lockup::new_unlocked_since(account_id.clone(), 100, 1_000);
+        lockup.schedule = Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 2_000);
+        lockup.termination_config = Some(TerminationConfig {
+            beneficiary_id: account_id.clone(),
+            vesting_schedule: VestingConditions::Schedule(Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 3_000)),
+        });
+        lockup.vesting = Some(Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 4_000));
+
+        assert_eq!(lockup.extend_schedules(1_500, 500), Ok(true));
+        assert_eq!(lockup.schedule.finish_timestamp(), 2_500);
+        assert_eq!(lockup.schedule.unlocked_balance(1_500), 500);
+        let Some(TerminationConfig {
+            vesting_schedule: VestingConditions::Schedule(termination_schedule),
+            ..
+        }) = &lockup.termination_config
+        else {
+            panic!("termination vesting schedule expected");
+        };
+        assert_eq!(termination_schedule.finish_timestamp(), 3_500);
+        assert_eq!(lockup.vesting_schedule().unwrap().finish_timestamp(), 4_500);
+
+        let mut unlocked = Lockup::new_unlocked_since(account_id.clone(), 100, 1_000);
+        assert_eq!(unlocked.extend_schedules(1_500, 500), Ok(false));
+
+        let mut claimed = Lockup::new_unlocked_since(account_id.clone(), 1_000, 1_000);
+        claimed.schedule = Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 2_000);
+        claimed.claimed_balance = 600;
+        assert!(claimed.extend_schedules(1_500, 500).is_err());
+        assert_eq!(claimed.schedule.finish_timestamp(), 2_000);
+
+        let mut curved = Lockup::new_unlocked_since(account_id, 1_000, 1_000);
+        curved.schedule = Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 2_000);
+        curved.curve = Some(UnlockCurve::Quadratic);
+        assert!(curved.extend_schedules(1_500, 500).is_err());
+    }
+
+    #[test]
+    fn test_extend_schedules_checkpoints_cap() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut lockup = Lockup::new_unlocked_since(account_id, 1_000, 1_000);
+        let checkpoints = (0..MAX_SCHEDULE_CHECKPOINTS)
+            .map(|i| Checkpoint {
+                timestamp: 1_000 + 10 * TimestampSec::try_from(i).unwrap(),
+                balance: Balance::try_from(i).unwrap() * 4,
+            })
+            .collect();
+        lockup.schedule = Schedule(checkpoints);
+
+        // the extension in the middle of a linear unlock inserts a checkpoint
+        assert!(lockup.extend_schedules(1_005, 500).is_err());
+        assert_eq!(lockup.schedule.0.len(), MAX_SCHEDULE_CHECKPOINTS);
+        assert_eq!(lockup.extend_schedules(1_010, 500), Ok(true));
+    }
+
+    #[test]
+    fn test_vesting_caps_claims_after_termination() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        // a transfer lock until 2000 over a linear vesting from 1000 to 5000
+        let mut lockup = Lockup::new_unlocked_since(account_id.clone(), 4_000, 2_000);
+        lockup.vesting = Some(Schedule::new_with_cliff(4_000, 0, 1_000, 1_000, 5_000));
+        lockup.termination_config = Some(TerminationConfig {
+            beneficiary_id: account_id,
+            vesting_schedule: VestingConditions::SameAsLockupSchedule,
+        });
+        lockup.validate_new(4_000).unwrap();
+
+        assert_eq!(lockup.unlocked_balance(1_500), 0);
+        assert_eq!(lockup.unlocked_balance(3_000), 2_000);
+
+        assert_eq!(lockup.terminate(3_000, 4_000).0, 0);
+        assert_eq!(lockup.unlocked_balance(3_000), 2_000);
+        assert_eq!(lockup.unlocked_balance(5_000), 4_000);
+    }
+
+    #[test]
+    fn test_termination_schedule_behind_lockup_schedule() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let mut lockup = Lockup::new_unlocked_since(account_id.clone(), 1_000, 1_000);
+        lockup.termination_config = Some(TerminationConfig {
+            beneficiary_id: account_id,
+            vesting_schedule: VestingConditions::Schedule(Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 3_000)),
+        });
+
+        assert!(lockup.validate_new(1_000).is_err());
+    }
+
+    #[test]
+    fn test_reveal_vesting_schedule() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let vesting_schedule = Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 3_000);
+        let mut lockup = Lockup::new_unlocked_since(account_id.clone(), 1_000, 3_000);
+        lockup.termination_config = Some(TerminationConfig {
+            beneficiary_id: account_id,
+            vesting_schedule: VestingConditions::Hash(vesting_schedule.hash().into()),
+        });
+        lockup.validate_new(1_000).unwrap();
+
+        assert_eq!(
+            lockup.reveal_vesting_schedule(Schedule::new_with_cliff(1_000, 0, 1_000, 1_000, 4_000)),
+            Err("The revealed vesting schedule doesn't match the hash".to_string())
+        );
+        assert_eq!(lockup.reveal_vesting_schedule(vesting_schedule.clone()), Ok(()));
+        assert_eq!(lockup.vested_balance(2_000), 500);
+        assert_eq!(
+            lockup.reveal_vesting_schedule(vesting_schedule),
+            Err("The lockup has no hashed vesting schedule".to_string())
+        );
+    }
+
+    #[test]
+    fn test_terminate_external_vesting() {
+        let account_id = AccountId::from_str("alice.near").unwrap();
+        let oracle_id = AccountId::from_str("milestones.near").unwrap();
+        let mut lockup = Lockup::new_unlocked_since(account_id.clone(), 1_000, 1_000);
+        lockup.claimed_balance = 200;
+        lockup.termination_config = Some(TerminationConfig {
+            beneficiary_id: account_id.clone(),
+            vesting_schedule: VestingConditions::External {
+                contract_id: oracle_id.clone(),
+                method: "get_vested_balance".to_string(),
+            },
+        });
+
+        assert_eq!(lockup.vesting_oracle(), Some((&oracle_id, "get_vested_balance")));
+        assert_eq!(
+            lockup.validate_vested_balance(100),
+            Err("The vested balance is behind the claimed balance".to_string())
+        );
+        assert_eq!(
+            lockup.validate_vested_balance(1_001),
+            Err("The vested balance 1001 exceeds the lockup balance 1000".to_string())
+        );
+
+        assert_eq!(lockup.terminate(2_000, 600), (400, account_id));
+        assert_eq!(lockup.schedule.total_balance(), 600);
+        assert_eq!(lockup.vesting_oracle(), None);
+    }
+}
@@ -1,9 +1,17 @@
-use near_sdk::{AccountId, PromiseOrValue};
+use near_sdk::{AccountId, PromiseOrValue, PublicKey};
 use nitka::make_integration_version;
 
 use crate::{
+    config::{FtOnTransferPolicy, TerminationPolicy},
     draft::{Draft, DraftGroupIndex, DraftIndex},
-    lockup::LockupIndex,
+    fee::ClaimFee,
+    gas::GasConfig,
+    lockup::{ClaimResult, LockupCreate, LockupIndex},
+    memo::MemoTemplates,
+    pending::PendingLockupProof,
+    schedule::Schedule,
+    termination::TerminationBurn,
+    throttle::ClaimThrottle,
     TimestampSec, WrappedBalance,
 };
 
@@ -16,33 +24,290 @@ pub trait LockupApi {
         manager: AccountId,
     ) -> Self;
 
-    fn claim(&mut self, amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>) -> PromiseOrValue<WrappedBalance>;
+    /// Initializes the contract again after its state was wiped, e.g. by a redeploy with a state cleanup.
+    /// Only the manager of the previous initialization can call it, and only if no lockups or drafts were left,
+    /// since `new` refuses to run once the contract was initialized. The config is reset to its defaults.
+    fn reinit_after_state_wipe(
+        token_account_id: AccountId,
+        deposit_whitelist: Vec<AccountId>,
+        draft_operators_whitelist: Option<Vec<AccountId>>,
+    ) -> Self;
+
+    /// Claims the given lockups, or all account's lockups if `amounts` are not provided.
+    /// In the latter case only as many lockups with the largest claimable balances as fit into the attached gas
+    /// are claimed, `has_more` in the result indicates that another call is required.
+    /// The optional `memo` replaces the default `ft_transfer` memo, e.g. to tag payouts by a payroll run id.
+    /// By default the call fails when a given amount exceeds the claimable balance of its lockup. With `max_amounts`
+    /// the given amounts are upper bounds instead, each lockup is claimed up to its claimable balance and the
+    /// clamped requests are reported in `clamped_lockups` of the result, e.g. for front-ends racing the unlocks.
+    /// A lockup can't be claimed, terminated or revoked again until the transfer of its claim resolves.
+    /// Claiming all lockups skips the lockups in flight and reports them in `in_flight_lockups` of the result.
+    fn claim(
+        &mut self,
+        amounts: Option<Vec<(LockupIndex, Option<WrappedBalance>)>>,
+        memo: Option<String>,
+        max_amounts: Option<bool>,
+    ) -> PromiseOrValue<ClaimResult>;
+
+    /// Claims exactly the `total` from the caller's lockups, the lockups which started unlocking first are
+    /// claimed in full before the later ones. `claimed_lockups` in the result is the breakdown per lockup.
+    /// Fails if the total exceeds the claimable balance or the lockups needed don't fit into the attached gas.
+    fn claim_amount(&mut self, total: WrappedBalance, memo: Option<String>) -> PromiseOrValue<ClaimResult>;
+
+    /// Claims the whole claimable balance of a lockup created with a keeper incentive on behalf of its owner.
+    /// Anyone can call it once the claimable balance reaches the incentive threshold,
+    /// the caller receives the keeper fee and the owner receives the rest, which must be positive after the claim fee.
+    /// Returns the claimed balance.
+    fn claim_for_keeper(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// Schedules a change of the account receiving the caller's claims, `None` for the caller's own account.
+    /// The change takes effect after `PAYOUT_ADDRESS_CHANGE_DELAY_SEC`, replacing any pending change.
+    fn set_payout_address(&mut self, payout_id: Option<AccountId>);
+
+    fn cancel_payout_address_change(&mut self);
 
     fn terminate(
         &mut self,
         lockup_index: LockupIndex,
         termination_timestamp: Option<TimestampSec>,
+        // fails the termination if the unvested balance deviates from this value beyond the tolerance
+        expected_unvested: Option<WrappedBalance>,
+        // recorded in the termination and its event, required by `Config::require_admin_reason`
+        reason: Option<String>,
     ) -> PromiseOrValue<WrappedBalance>;
 
+    /// Reveals the vesting schedule of a lockup created with a hashed one, so it can be terminated.
+    /// Anyone knowing the schedule can reveal it, it should match the hash.
+    fn reveal_vesting_schedule(&mut self, lockup_index: LockupIndex, schedule: Schedule);
+
+    /// Warns the owner of a lockup created with `unclaimed_revocation_after_sec`, who hasn't claimed for that long
+    /// after the full unlock, that the unclaimed balance can be revoked after the warning period.
+    fn warn_unclaimed_revocation(&mut self, lockup_index: LockupIndex);
+
+    /// Transfers the unclaimed balance of a warned inactive lockup back to its payer.
+    fn revoke_unclaimed(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// Forfeits the whole unclaimed balance of the caller's lockup, e.g. for grantees who must refuse
+    /// the compensation. The balance is transferred to the termination beneficiary of the lockup,
+    /// or to its creator if it's not terminable. Returns the forfeited balance.
+    fn renounce_lockup(&mut self, lockup_index: LockupIndex) -> PromiseOrValue<WrappedBalance>;
+
+    /// Binds a pending lockup to the caller's account, proving control over the lockup owner key,
+    /// or over the owner email with a signature of the email attestor.
+    fn claim_pending_lockup(&mut self, proof: PendingLockupProof);
+
+    /// Postpones the remaining checkpoints of the caller's lockup by `extend_by_sec`.
+    fn relock(&mut self, lockup_index: LockupIndex, extend_by_sec: TimestampSec);
+
+    /// Sets the note of the operators on the lockup, an empty note clears it. The previous notes are kept
+    /// in the history of the lockup. Only the deposit whitelist can call it, no event is emitted.
+    fn set_lockup_note(&mut self, lockup_index: LockupIndex, note: String);
+
+    /// Fetches the token metadata and caches its decimals.
+    fn refresh_token_metadata(&mut self) -> PromiseOrValue<Option<u8>>;
+
+    /// Rebuilds the account lockups index entries of lockups in `[from_index, from_index + limit)`
+    /// from the lockups themselves. Returns the index to continue from, or `None` when all lockups are processed.
+    /// Entries pointing to lockups owned by other accounts can't be found this way and are kept.
+    fn reindex_account_lockups(&mut self, from_index: LockupIndex, limit: u32) -> Option<LockupIndex>;
+
+    fn set_event_batch_threshold(&mut self, event_batch_threshold: Option<u32>);
+
+    /// Turns the storing of emitted events for `get_events_since` on or off, it's on by default.
+    /// While it's off, the events are only logged and don't get a sequence number.
+    fn set_event_log_enabled(&mut self, enabled: bool);
+
+    /// Sets the account factory used by `convert_draft_with_account_creation`.
+    fn set_account_factory(&mut self, account_factory_id: Option<AccountId>);
+
+    /// Sets the escrow receiving the claims the owner can't receive, see `Config::claim_escrow_id`.
+    fn set_claim_escrow(&mut self, claim_escrow_id: Option<AccountId>);
+
+    /// Sets which senders may trigger each `ft_on_transfer` operation, tokens from other senders are refunded.
+    fn set_ft_on_transfer_policy(&mut self, ft_on_transfer_policy: FtOnTransferPolicy);
+
+    /// Sets who can terminate lockups: any deposit whitelisted account, or only the lockup creator and the manager.
+    fn set_termination_policy(&mut self, termination_policy: TerminationPolicy);
+
+    /// Sets the total amount above which draft groups require the manager approval to be funded.
+    fn set_draft_group_approval_threshold(&mut self, draft_group_approval_threshold: Option<WrappedBalance>);
+
+    /// Sets the gas of the token calls and of the callbacks, e.g. after a change of the protocol gas costs.
+    /// Every value should stay within the bounds of `GasConfig::validate`.
+    fn set_gas_config(&mut self, gas_config: GasConfig);
+
+    /// Sets the cap on the total balance managed by the contract. `None` disables the cap.
+    fn set_max_managed_balance(&mut self, max_managed_balance: Option<WrappedBalance>);
+
+    /// Sets whether `get_top_upcoming_unlocks` hides the owners of the unlocking lockups.
+    fn set_anonymize_upcoming_unlocks(&mut self, anonymize: bool);
+
+    /// Enables or disables the privacy mode, see `PrivacyApi`.
+    fn set_privacy_mode(&mut self, enabled: bool);
+
+    /// Sets the largest share of the total balance new lockups and drafts can unlock at creation,
+    /// at most 10000 bps. `None` disables the check.
+    fn set_max_unlocked_at_creation(&mut self, max_unlocked_bps: Option<u16>);
+
+    /// Sets whether terminations and whitelist removals require a stated reason.
+    fn set_require_admin_reason(&mut self, required: bool);
+
+    /// Sets the cap on the total amount claimed within a period. `None` disables throttling.
+    fn set_claim_throttle(&mut self, claim_throttle: Option<ClaimThrottle>);
+
+    /// Sets the delay after funding a draft group, after which its payer can reclaim the unconverted drafts.
+    fn set_draft_group_conversion_deadline(&mut self, conversion_deadline_sec: Option<TimestampSec>);
+
+    /// Limits the number of draft groups an account can create per day. `None` disables the limit.
+    fn set_max_draft_groups_per_day(&mut self, max_draft_groups_per_day: Option<u32>);
+
+    /// Sets the templates of the transfer memos, `None` templates restore the defaults.
+    /// Templates with unknown placeholders or unbalanced braces are rejected.
+    fn set_memo_templates(&mut self, memo_templates: MemoTemplates);
+
+    /// Sets where the unvested balance of lockups created with `burn_on_termination` goes.
+    /// Terminations of such lockups fail while it's `None`.
+    fn set_termination_burn(&mut self, termination_burn: Option<TerminationBurn>);
+
+    /// Schedules a change of the protocol fee on claims, `None` to disable the fee.
+    /// The change takes effect after `CLAIM_FEE_CHANGE_DELAY_SEC`, replacing any pending change.
+    fn set_claim_fee(&mut self, claim_fee: Option<ClaimFee>);
+
+    fn cancel_claim_fee_change(&mut self);
+
+    /// Schedules the reassignment of all lockups of `old_account_id` to `new_account_id`, e.g. of a lost account
+    /// recovered under a new id. Only the manager can call it, replacing any remap scheduled for the account.
+    fn schedule_account_remap(&mut self, old_account_id: AccountId, new_account_id: AccountId);
+
+    fn cancel_account_remap(&mut self, old_account_id: AccountId);
+
+    /// Executes the remap scheduled at least `ACCOUNT_REMAP_DELAY_SEC` ago, the lockups keep their indices
+    /// and history. The payout address of the old account is dropped. Only the manager can call it.
+    fn remap_account(&mut self, old_account_id: AccountId, new_account_id: AccountId);
+
+    /// Schedules postponing the remaining part of every lockup and vesting schedule by `by_sec`, e.g. after
+    /// a token contract outage when beneficiaries couldn't claim. The extension can be applied
+    /// `SCHEDULE_EXTENSION_DELAY_SEC` after it's scheduled and is bounded by `MAX_SCHEDULE_EXTENSION_SEC`.
+    /// Only the manager can call it, a single extension can be scheduled at a time.
+    fn extend_all_schedules(&mut self, by_sec: TimestampSec);
+
+    /// Cancels the scheduled extension before it's applied to any lockup. Only the manager can call it.
+    fn cancel_schedule_extension(&mut self);
+
+    /// Applies the scheduled extension to the next `limit` lockups, postponing their schedules after the timestamp
+    /// the extension was scheduled at, so the balances unlocked until then are kept. Lockups with an unlock curve,
+    /// claimed past that balance or exceeding `MAX_SCHEDULE_CHECKPOINTS` once postponed keep their schedules
+    /// and are reported in the event, hashed vesting schedules are kept as well. Returns the index of the next
+    /// lockup to extend, `None` once the extension is applied to all lockups. Only the manager can call it.
+    fn apply_schedule_extension(&mut self, limit: u32) -> Option<LockupIndex>;
+
+    /// Hashes the next `limit` lockups into the merkle root of all lockups, so a mirror contract on another chain
+    /// can verify inclusion proofs of `LockupLeaf` records. Anyone can call it, a new computation starts once
+    /// the previous one is finished. Returns the index of the next lockup to hash, `None` once the root is computed
+    /// and returned by `get_lockups_merkle_root`.
+    fn compute_lockups_merkle_root(&mut self, limit: u32) -> Option<LockupIndex>;
+
     // preserving both options for API compatibility
     fn add_to_deposit_whitelist(&mut self, account_id: Option<AccountId>, account_ids: Option<Vec<AccountId>>);
 
     // preserving both options for API compatibility
-    fn remove_from_deposit_whitelist(&mut self, account_id: Option<AccountId>, account_ids: Option<Vec<AccountId>>);
+    fn remove_from_deposit_whitelist(
+        &mut self,
+        account_id: Option<AccountId>,
+        account_ids: Option<Vec<AccountId>>,
+        reason: Option<String>,
+    );
 
     fn add_to_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>);
 
-    fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>);
+    fn remove_from_draft_operators_whitelist(&mut self, account_ids: Vec<AccountId>, reason: Option<String>);
+
+    fn add_to_payer_whitelist(&mut self, account_ids: Vec<AccountId>);
+
+    fn remove_from_payer_whitelist(&mut self, account_ids: Vec<AccountId>, reason: Option<String>);
+
+    /// Pauses or resumes the creation, administration, funding and conversion of drafts and draft groups.
+    fn pause_drafts(&mut self, paused: bool);
+
+    /// Pauses or resumes claims, including the claims of keepers and lien holders and sweeps to cliff escrows.
+    fn pause_claims(&mut self, paused: bool);
+
+    /// Pauses or resumes terminations, unclaimed revocations and renouncements.
+    fn pause_terminations(&mut self, paused: bool);
+
+    /// Sets the attestor account, enabling the claim compliance gate. `None` disables the gate.
+    fn set_attestor(&mut self, attestor_id: Option<AccountId>);
+
+    /// Marks accounts as attested or not attested to claim. Only the attestor can call it.
+    fn set_attestation(&mut self, account_ids: Vec<AccountId>, attested: bool);
+
+    /// Sets the key of the email attestor signing the claims of lockups pending an email owner.
+    /// `None` leaves these lockups pending until the key is set.
+    fn set_email_attestor_key(&mut self, email_attestor_key: Option<PublicKey>);
+
+    /// Sets the account managing the blocklist. `None` leaves the blocklist as is, without a manager.
+    fn set_blocklist_manager(&mut self, blocklist_manager_id: Option<AccountId>);
+
+    /// Blocks or unblocks accounts. Blocked accounts can't receive lockups from drafts, deposits,
+    /// airdrops, pending lockups or remaps. Only the blocklist manager can call it.
+    fn set_blocked(&mut self, account_ids: Vec<AccountId>, blocked: bool);
+
+    /// Allows the contracts to report the vested balance of lockups with `VestingConditions::External`
+    /// at termination. Only the manager can call it.
+    fn add_to_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>);
+
+    fn remove_from_vesting_oracles_whitelist(&mut self, account_ids: Vec<AccountId>);
+
+    /// Removes the caller from the deposit whitelist, the draft operators whitelist and the payer whitelist.
+    fn resign(&mut self);
+
+    /// Creates lockups debited from the lockup pool of the caller, funded in advance with `LockupPoolFunding`,
+    /// without the review workflow of draft groups. Only the deposit whitelist can call it.
+    fn create_lockups(&mut self, lockups: Vec<LockupCreate>) -> Vec<LockupIndex>;
+
+    /// Returns the unallocated balance of the lockup pool of the caller.
+    fn withdraw_lockup_pool(&mut self) -> PromiseOrValue<WrappedBalance>;
 
     fn create_draft_group(&mut self) -> DraftGroupIndex;
 
+    /// Voids the drafts left unconverted after the conversion deadline and returns their funds to the payer.
+    /// Only the payer of the draft group can call it.
+    fn reclaim_draft_group(&mut self, draft_group_id: DraftGroupIndex) -> PromiseOrValue<WrappedBalance>;
+
     fn create_draft(&mut self, draft: Draft) -> DraftIndex;
 
     fn create_drafts(&mut self, drafts: Vec<Draft>) -> Vec<DraftIndex>;
 
+    /// Converts the draft into a lockup, or into a lockup per recipient of a multi-recipient draft.
+    /// Returns the first lockup, `convert_drafts` returns all of them.
     fn convert_draft(&mut self, draft_id: DraftIndex) -> LockupIndex;
 
+    /// Creates the draft owner account via the account factory, funded with the attached deposit,
+    /// and converts the draft. If the account creation fails, either reported by the factory or with a failed
+    /// call, the deposit is refunded to the caller and the draft is marked as pending, it can still be converted
+    /// with `convert_draft` later.
+    fn convert_draft_with_account_creation(
+        &mut self,
+        draft_id: DraftIndex,
+        public_key: PublicKey,
+    ) -> PromiseOrValue<Option<LockupIndex>>;
+
+    /// Marks the drafts of the group as final, so the group can be funded.
+    fn freeze_draft_group(&mut self, draft_group_id: DraftGroupIndex);
+
+    /// Reopens a frozen draft group to add drafts or to discard it.
+    fn unfreeze_draft_group(&mut self, draft_group_id: DraftGroupIndex);
+
+    /// Approves funding of a frozen draft group above the approval threshold. Only the manager can approve,
+    /// the approval is reset if the group is unfrozen.
+    fn approve_draft_group(&mut self, draft_group_id: DraftGroupIndex);
+
     fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex);
 
     fn delete_drafts(&mut self, draft_ids: Vec<DraftIndex>);
+
+    /// Deletes up to `limit` drafts of the discarded draft group in the order of their indices,
+    /// so a large group is cleaned up by repeated calls. Returns the number of drafts left,
+    /// the group is removed with its last draft.
+    fn delete_drafts_in_group(&mut self, draft_group_id: DraftGroupIndex, limit: u32) -> u32;
 }
@@ -0,0 +1,300 @@
+use near_sdk::{near, AccountId};
+
+use crate::{draft::DraftGroupIndex, lockup::LockupIndex, Balance};
+
+pub const MAX_MEMO_TEMPLATE_LENGTH: usize = 256;
+
+/// The default memo of a transfer and the placeholders allowed in its template.
+struct MemoKind {
+    name: &'static str,
+    default: &'static str,
+    placeholders: &'static [&'static str],
+}
+
+const LOCKUP_PLACEHOLDERS: &[&str] = &["amount", "lockup_index"];
+
+const CLAIM_MEMO: MemoKind = MemoKind {
+    name: "claim",
+    default: "Claiming unlocked {amount} balance from {contract_id}",
+    placeholders: &["amount", "contract_id"],
+};
+const TERMINATION_MEMO: MemoKind = MemoKind {
+    name: "termination",
+    default: "Terminated lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const REVOCATION_MEMO: MemoKind = MemoKind {
+    name: "revocation",
+    default: "Revoked unclaimed lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const RENUNCIATION_MEMO: MemoKind = MemoKind {
+    name: "renunciation",
+    default: "Renounced lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const RECLAIM_MEMO: MemoKind = MemoKind {
+    name: "reclaim",
+    default: "Reclaimed voided draft group #{draft_group_id}",
+    placeholders: &["amount", "draft_group_id"],
+};
+const LIEN_CLAIM_MEMO: MemoKind = MemoKind {
+    name: "lien_claim",
+    default: "Lien claim of lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const CLIFF_ESCROW_SWEEP_MEMO: MemoKind = MemoKind {
+    name: "cliff_escrow_sweep",
+    default: "Cliff escrow sweep of lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const CLAIM_ESCROW_MEMO: MemoKind = MemoKind {
+    name: "claim_escrow",
+    default: "Claim escrow of lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const CLAIM_FEE_MEMO: MemoKind = MemoKind {
+    name: "claim_fee",
+    default: "Claim fee for claiming lockups of {account_id}",
+    placeholders: &["amount", "account_id"],
+};
+const KEEPER_FEE_MEMO: MemoKind = MemoKind {
+    name: "keeper_fee",
+    default: "Keeper fee for claiming lockup #{lockup_index}",
+    placeholders: LOCKUP_PLACEHOLDERS,
+};
+const LOCKUP_POOL_WITHDRAWAL_MEMO: MemoKind = MemoKind {
+    name: "lockup_pool_withdrawal",
+    default: "Withdrawn lockup pool",
+    placeholders: &["amount"],
+};
+
+/// Templates of the `ft_transfer` memos replacing the default English ones, since wallets show memos to users.
+/// Every template accepts `{amount}`, the other placeholders are named after the value they stand for, e.g.
+/// `{lockup_index}`, `{contract_id}` in claim memos, `{draft_group_id}` in reclaim memos and `{account_id}`
+/// (the claiming account) in claim fee memos. A custom `claim` memo replaces the template.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MemoTemplates {
+    pub claim: Option<String>,
+    pub termination: Option<String>,
+    /// Revocations of dormant lockups
+    pub revocation: Option<String>,
+    pub renunciation: Option<String>,
+    /// Refunds of voided draft groups to the payer
+    pub reclaim: Option<String>,
+    pub lien_claim: Option<String>,
+    pub cliff_escrow_sweep: Option<String>,
+    pub claim_escrow: Option<String>,
+    pub claim_fee: Option<String>,
+    pub keeper_fee: Option<String>,
+    pub lockup_pool_withdrawal: Option<String>,
+}
+
+impl MemoTemplates {
+    fn templates(&self) -> [(&Option<String>, &MemoKind); 11] {
+        [
+            (&self.claim, &CLAIM_MEMO),
+            (&self.termination, &TERMINATION_MEMO),
+            (&self.revocation, &REVOCATION_MEMO),
+            (&self.renunciation, &RENUNCIATION_MEMO),
+            (&self.reclaim, &RECLAIM_MEMO),
+            (&self.lien_claim, &LIEN_CLAIM_MEMO),
+            (&self.cliff_escrow_sweep, &CLIFF_ESCROW_SWEEP_MEMO),
+            (&self.claim_escrow, &CLAIM_ESCROW_MEMO),
+            (&self.claim_fee, &CLAIM_FEE_MEMO),
+            (&self.keeper_fee, &KEEPER_FEE_MEMO),
+            (&self.lockup_pool_withdrawal, &LOCKUP_POOL_WITHDRAWAL_MEMO),
+        ]
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (template, kind) in self.templates() {
+            let Some(template) = template else {
+                continue;
+            };
+            if template.is_empty() || template.len() > MAX_MEMO_TEMPLATE_LENGTH {
+                return Err(format!(
+                    "{} memo template length must be between 1 and {MAX_MEMO_TEMPLATE_LENGTH} bytes",
+                    kind.name
+                ));
+            }
+            validate_placeholders(template, kind.placeholders)
+                .map_err(|error| format!("{} memo template: {error}", kind.name))?;
+        }
+        Ok(())
+    }
+
+    pub fn claim_memo(&self, amount: Balance, contract_id: &AccountId) -> String {
+        render(
+            &self.claim,
+            &CLAIM_MEMO,
+            &[("amount", &amount.to_string()), ("contract_id", contract_id.as_str())],
+        )
+    }
+
+    pub fn termination_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.termination, &TERMINATION_MEMO, amount, lockup_index)
+    }
+
+    pub fn revocation_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.revocation, &REVOCATION_MEMO, amount, lockup_index)
+    }
+
+    pub fn renunciation_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.renunciation, &RENUNCIATION_MEMO, amount, lockup_index)
+    }
+
+    pub fn reclaim_memo(&self, amount: Balance, draft_group_id: DraftGroupIndex) -> String {
+        render(
+            &self.reclaim,
+            &RECLAIM_MEMO,
+            &[
+                ("amount", &amount.to_string()),
+                ("draft_group_id", &draft_group_id.to_string()),
+            ],
+        )
+    }
+
+    pub fn lien_claim_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.lien_claim, &LIEN_CLAIM_MEMO, amount, lockup_index)
+    }
+
+    pub fn cliff_escrow_sweep_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.cliff_escrow_sweep, &CLIFF_ESCROW_SWEEP_MEMO, amount, lockup_index)
+    }
+
+    pub fn claim_escrow_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.claim_escrow, &CLAIM_ESCROW_MEMO, amount, lockup_index)
+    }
+
+    pub fn claim_fee_memo(&self, amount: Balance, account_id: &AccountId) -> String {
+        render(
+            &self.claim_fee,
+            &CLAIM_FEE_MEMO,
+            &[("amount", &amount.to_string()), ("account_id", account_id.as_str())],
+        )
+    }
+
+    pub fn keeper_fee_memo(&self, amount: Balance, lockup_index: LockupIndex) -> String {
+        render_lockup_memo(&self.keeper_fee, &KEEPER_FEE_MEMO, amount, lockup_index)
+    }
+
+    pub fn lockup_pool_withdrawal_memo(&self, amount: Balance) -> String {
+        render(
+            &self.lockup_pool_withdrawal,
+            &LOCKUP_POOL_WITHDRAWAL_MEMO,
+            &[("amount", &amount.to_string())],
+        )
+    }
+}
+
+/// Checks that every `{...}` of the template is one of the placeholders and that the braces are balanced.
+fn validate_placeholders(template: &str, placeholders: &[&str]) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("unmatched `}`".to_string());
+        }
+        let end = start + rest[start..].find('}').ok_or_else(|| "unclosed `{`".to_string())?;
+        let name = &rest[start + 1..end];
+        if !placeholders.contains(&name) {
+            return Err(format!(
+                "unknown placeholder `{{{name}}}`, expected one of {}",
+                placeholders
+                    .iter()
+                    .map(|placeholder| format!("`{{{placeholder}}}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+fn render_lockup_memo(
+    template: &Option<String>,
+    kind: &MemoKind,
+    amount: Balance,
+    lockup_index: LockupIndex,
+) -> String {
+    render(
+        template,
+        kind,
+        &[
+            ("amount", &amount.to_string()),
+            ("lockup_index", &lockup_index.to_string()),
+        ],
+    )
+}
+
+fn render(template: &Option<String>, kind: &MemoKind, values: &[(&str, &str)]) -> String {
+    let template = template.as_deref().unwrap_or(kind.default);
+    values.iter().fold(template.to_string(), |memo, (name, value)| {
+        memo.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_memo_templates() {
+        let contract_id = AccountId::from_str("lockup.near").unwrap();
+
+        let default = MemoTemplates::default();
+        assert_eq!(
+            default.claim_memo(100, &contract_id),
+            "Claiming unlocked 100 balance from lockup.near"
+        );
+        assert_eq!(default.termination_memo(100, 7), "Terminated lockup #7");
+
+        let templates = MemoTemplates {
+            claim: Some("Retiro de {amount} desde {contract_id}".to_string()),
+            termination: Some("Блокировка #{lockup_index} прекращена, {amount} возвращено".to_string()),
+            revocation: Some("Bloqueo #{lockup_index} revocado".to_string()),
+            ..MemoTemplates::default()
+        };
+        assert!(templates.validate().is_ok());
+        assert_eq!(
+            templates.claim_memo(100, &contract_id),
+            "Retiro de 100 desde lockup.near"
+        );
+        assert_eq!(
+            templates.termination_memo(100, 7),
+            "Блокировка #7 прекращена, 100 возвращено"
+        );
+        assert_eq!(templates.revocation_memo(100, 7), "Bloqueo #7 revocado");
+        assert_eq!(templates.reclaim_memo(100, 3), "Reclaimed voided draft group #3");
+
+        let empty = MemoTemplates {
+            claim: Some(String::new()),
+            ..MemoTemplates::default()
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn test_memo_template_placeholders() {
+        let with_reclaim = |template: &str| MemoTemplates {
+            reclaim: Some(template.to_string()),
+            ..MemoTemplates::default()
+        };
+
+        assert!(with_reclaim("Group {draft_group_id}: {amount}").validate().is_ok());
+        assert!(with_reclaim("No placeholders").validate().is_ok());
+
+        assert_eq!(
+            with_reclaim("Lockup {lockup_index}").validate(),
+            Err("reclaim memo template: unknown placeholder `{lockup_index}`, expected one of `{amount}`, `{draft_group_id}`".to_string())
+        );
+        assert!(with_reclaim("Group {draft_group_id").validate().is_err());
+        assert!(with_reclaim("Group draft_group_id}").validate().is_err());
+        assert!(with_reclaim("Group {{draft_group_id}}").validate().is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use near_sdk::{borsh, env, json_types::Base58CryptoHash, near, AccountId, CryptoHash};
+
+use crate::{
+    lockup::{Lockup, LockupIndex},
+    schedule::{Schedule, UnlockCurve},
+    util::u128_dec_format,
+    Balance, TimestampSec,
+};
+
+pub fn hash_pair(left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    let mut value = Vec::with_capacity(64);
+    value.extend_from_slice(left);
+    value.extend_from_slice(right);
+    env::sha256_array(&value)
+}
+
+/// Computes the root of a binary merkle tree. A node without a pair is promoted to the next level as is.
+pub fn merkle_root(mut leaves: Vec<CryptoHash>) -> CryptoHash {
+    if leaves.is_empty() {
+        return CryptoHash::default();
+    }
+    while leaves.len() > 1 {
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_pair(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    leaves[0]
+}
+
+/// Checks that the leaf at `index` of a tree of `num_leaves` leaves built by `merkle_root` belongs to `root`.
+/// `siblings` are the nodes paired with the path from the leaf to the root, the levels where the path
+/// is promoted without a pair have none.
+pub fn verify_merkle_proof(
+    leaf: CryptoHash,
+    index: u32,
+    num_leaves: u32,
+    siblings: &[CryptoHash],
+    root: &CryptoHash,
+) -> bool {
+    if index >= num_leaves {
+        return false;
+    }
+    let mut siblings = siblings.iter();
+    let mut node = leaf;
+    let (mut index, mut width) = (index, num_leaves);
+    while width > 1 {
+        if index % 2 == 1 {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            node = hash_pair(sibling, &node);
+        } else if index + 1 < width {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            node = hash_pair(&node, sibling);
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none() && &node == root
+}
+
+/// The roots of the complete subtrees of the leaves appended so far, one per level at most,
+/// so the root of the same tree as `merkle_root` is computed without keeping the leaves.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct MerkleFrontier {
+    #[cfg_attr(feature = "ts", ts(type = "Array<Array<number> | null>"))]
+    pub nodes: Vec<Option<CryptoHash>>,
+    pub num_leaves: u32,
+}
+
+impl MerkleFrontier {
+    pub fn append(&mut self, leaf: CryptoHash) {
+        let mut node = leaf;
+        let mut level = 0;
+        loop {
+            if level == self.nodes.len() {
+                self.nodes.push(Some(node));
+                break;
+            }
+            match self.nodes[level].take() {
+                Some(left) => {
+                    node = hash_pair(&left, &node);
+                    level += 1;
+                }
+                None => {
+                    self.nodes[level] = Some(node);
+                    break;
+                }
+            }
+        }
+        self.num_leaves += 1;
+    }
+
+    /// Combines the subtrees from the lowest level, the unpaired ones are promoted as in `merkle_root`.
+    pub fn root(&self) -> CryptoHash {
+        self.nodes
+            .iter()
+            .flatten()
+            .fold(None, |right, left| {
+                Some(right.map_or(*left, |right: CryptoHash| hash_pair(left, &right)))
+            })
+            .unwrap_or_default()
+    }
+
+    /// The number of levels above the leaves.
+    pub fn height(&self) -> u32 {
+        self.num_leaves.max(1).next_power_of_two().trailing_zeros()
+    }
+}
+
+/// The canonical record of a lockup in the merkle tree of all lockups, the leaf is the sha256 hash of its borsh.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupLeaf {
+    pub index: LockupIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub account_id: AccountId,
+    pub schedule: Schedule,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub claimed_balance: Balance,
+    pub curve: Option<UnlockCurve>,
+}
+
+impl LockupLeaf {
+    pub fn new(index: LockupIndex, lockup: &Lockup) -> Self {
+        Self {
+            index,
+            account_id: lockup.account_id.clone(),
+            schedule: lockup.schedule.clone(),
+            claimed_balance: lockup.claimed_balance,
+            curve: lockup.curve,
+        }
+    }
+
+    pub fn hash(&self) -> CryptoHash {
+        env::sha256_array(&borsh::to_vec(self).unwrap())
+    }
+}
+
+/// A computation of the merkle root of all lockups in progress.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupsMerkleComputation {
+    /// The leaves of the lockups hashed so far, `num_leaves` is the index of the next lockup to hash
+    pub frontier: MerkleFrontier,
+    pub started_at: TimestampSec,
+}
+
+/// The merkle root of all lockups computed by `compute_lockups_merkle_root`. The lockups are hashed in batches,
+/// so every leaf is the lockup record at the time its batch was computed, between `started_at` and `finished_at`.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupsMerkleRoot {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub root: Base58CryptoHash,
+    pub height: u32,
+    pub num_lockups: u32,
+    pub started_at: TimestampSec,
+    pub finished_at: TimestampSec,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_frontier_matches_merkle_root() {
+        let leaves: Vec<CryptoHash> = (0..9u8).map(|index| env::sha256_array(&[index])).collect();
+        let mut frontier = MerkleFrontier::default();
+        assert_eq!(frontier.root(), merkle_root(vec![]));
+        for (num_leaves, leaf) in leaves.iter().enumerate() {
+            frontier.append(*leaf);
+            assert_eq!(frontier.root(), merkle_root(leaves[..=num_leaves].to_vec()));
+        }
+        assert_eq!(frontier.num_leaves, 9);
+        assert_eq!(frontier.height(), 4);
+    }
+
+    #[test]
+    fn test_verify_merkle_proof() {
+        let leaves: Vec<CryptoHash> = (0..5u8).map(|index| env::sha256_array(&[index])).collect();
+        let root = merkle_root(leaves.clone());
+        let ab = hash_pair(&leaves[0], &leaves[1]);
+        let abcd = hash_pair(&ab, &hash_pair(&leaves[2], &leaves[3]));
+
+        assert!(verify_merkle_proof(leaves[2], 2, 5, &[leaves[3], ab, leaves[4]], &root));
+        // the last leaf is promoted twice
+        assert!(verify_merkle_proof(leaves[4], 4, 5, &[abcd], &root));
+
+        assert!(!verify_merkle_proof(
+            leaves[2],
+            3,
+            5,
+            &[leaves[3], ab, leaves[4]],
+            &root
+        ));
+        assert!(!verify_merkle_proof(leaves[2], 2, 5, &[leaves[3], ab], &root));
+        assert!(!verify_merkle_proof(leaves[4], 4, 5, &[abcd, abcd], &root));
+        assert!(!verify_merkle_proof(leaves[4], 5, 5, &[abcd], &root));
+    }
+}
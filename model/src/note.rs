@@ -0,0 +1,73 @@
+use near_sdk::{near, AccountId};
+
+use crate::TimestampSec;
+
+pub const MAX_NOTE_LENGTH: usize = 256;
+
+/// The number of the latest notes kept per lockup, the older ones are dropped.
+pub const MAX_NOTE_HISTORY: usize = 16;
+
+/// An internal note of operators on a lockup, e.g. an HR ticket number or a termination justification.
+/// Notes are not included in events.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupNote {
+    /// The note text, empty if the note was cleared
+    pub note: String,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub edited_by: AccountId,
+    pub edited_at: TimestampSec,
+}
+
+pub fn validate_note(note: &str) -> Result<(), String> {
+    if note.len() > MAX_NOTE_LENGTH {
+        return Err(format!("note length must not exceed {MAX_NOTE_LENGTH} bytes"));
+    }
+    if note.chars().any(char::is_control) {
+        return Err("note must not contain control characters".to_string());
+    }
+    Ok(())
+}
+
+/// Appends the note to the history of the lockup, the last entry is the current note.
+pub fn push_note(history: &mut Vec<LockupNote>, note: LockupNote) {
+    history.push(note);
+    if history.len() > MAX_NOTE_HISTORY {
+        history.drain(..history.len() - MAX_NOTE_HISTORY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_push_note_keeps_latest() {
+        let edited_by = AccountId::from_str("hr.near").unwrap();
+        let mut history = vec![];
+        for edited_at in 0..20 {
+            push_note(
+                &mut history,
+                LockupNote {
+                    note: format!("HR-{edited_at}"),
+                    edited_by: edited_by.clone(),
+                    edited_at,
+                },
+            );
+        }
+        assert_eq!(history.len(), MAX_NOTE_HISTORY);
+        assert_eq!(history[0].edited_at, 4);
+        assert_eq!(history.last().unwrap().note, "HR-19");
+    }
+
+    #[test]
+    fn test_validate_note() {
+        assert!(validate_note("Terminated per HR-1234, see the exit agreement").is_ok());
+        assert!(validate_note("").is_ok());
+        assert!(validate_note(&"a".repeat(MAX_NOTE_LENGTH + 1)).is_err());
+        assert!(validate_note("line\nbreak").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use near_sdk::{near, AccountId};
+
+use crate::TimestampSec;
+
+/// The delay before a payout address change takes effect, so a compromised owner key
+/// can't instantly redirect claims.
+pub const PAYOUT_ADDRESS_CHANGE_DELAY_SEC: TimestampSec = 48 * 60 * 60;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PayoutAddressChange {
+    /// The new payout address, `None` to receive claims to the owner account
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub payout_id: Option<AccountId>,
+    pub effective_at: TimestampSec,
+}
+
+/// The account receiving the claimed tokens of a lockup owner instead of the owner account.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PayoutAddress {
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub payout_id: Option<AccountId>,
+    pub pending_change: Option<PayoutAddressChange>,
+}
+
+impl PayoutAddress {
+    /// The payout address at the given timestamp, `None` for the owner account.
+    pub fn payout_id(&self, timestamp: TimestampSec) -> Option<&AccountId> {
+        match &self.pending_change {
+            Some(change) if change.effective_at <= timestamp => change.payout_id.as_ref(),
+            _ => self.payout_id.as_ref(),
+        }
+    }
+
+    /// Schedules the change replacing any pending one, returns the timestamp it takes effect.
+    pub fn change(&mut self, payout_id: Option<AccountId>, timestamp: TimestampSec) -> TimestampSec {
+        self.payout_id = self.payout_id(timestamp).cloned();
+        let effective_at = timestamp.saturating_add(PAYOUT_ADDRESS_CHANGE_DELAY_SEC);
+        self.pending_change = Some(PayoutAddressChange {
+            payout_id,
+            effective_at,
+        });
+        effective_at
+    }
+
+    pub fn cancel_change(&mut self, timestamp: TimestampSec) {
+        self.payout_id = self.payout_id(timestamp).cloned();
+        let change = self.pending_change.take();
+        assert!(
+            change.is_some_and(|change| change.effective_at > timestamp),
+            "no pending payout address change"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_payout_address_change_delay() {
+        let cold_id = AccountId::from_str("cold.near").unwrap();
+        let mut payout_address = PayoutAddress::default();
+
+        let effective_at = payout_address.change(Some(cold_id.clone()), 1_000);
+        assert_eq!(effective_at, 1_000 + PAYOUT_ADDRESS_CHANGE_DELAY_SEC);
+        assert_eq!(payout_address.payout_id(effective_at - 1), None);
+        assert_eq!(payout_address.payout_id(effective_at), Some(&cold_id));
+
+        // clearing the payout address is delayed as well
+        payout_address.change(None, effective_at);
+        assert_eq!(payout_address.payout_id(effective_at + 1), Some(&cold_id));
+
+        payout_address.cancel_change(effective_at + 1);
+        assert_eq!(payout_address.payout_id(u32::MAX), Some(&cold_id));
+    }
+}
@@ -0,0 +1,21 @@
+use near_sdk::{json_types::Base64VecU8, near, AccountId};
+
+use crate::lockup::LockupIndex;
+
+/// A proof of control over the key of a pending lockup.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct PendingLockupProof {
+    pub lockup_index: LockupIndex,
+    /// An ed25519 signature of `pending_lockup_message` with the lockup owner key,
+    /// or with `Config::email_attestor_key` for a lockup pending an email owner
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub signature: Base64VecU8,
+}
+
+/// The message signed to bind a pending lockup to an account,
+/// the contract id and the lockup index prevent replaying the signature for other lockups.
+pub fn pending_lockup_message(contract_id: &AccountId, lockup_index: LockupIndex, account_id: &AccountId) -> Vec<u8> {
+    format!("{contract_id}:{lockup_index}:{account_id}").into_bytes()
+}
@@ -0,0 +1,91 @@
+use near_sdk::near;
+
+use crate::{
+    provenance::{DepositIndex, FundingShare},
+    Balance,
+};
+
+/// The balance a whitelisted account deposited with `PoolFunding` and not allocated to lockups yet,
+/// see `create_lockups`. The unallocated parts of the deposits are kept to record the funding of the lockups.
+#[near(serializers=[borsh])]
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LockupPool {
+    /// The deposits with an unallocated balance, the oldest first
+    pub deposits: Vec<FundingShare>,
+}
+
+impl LockupPool {
+    pub fn balance(&self) -> Balance {
+        self.deposits.iter().map(|share| share.amount).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty()
+    }
+
+    pub fn fund(&mut self, deposit_id: DepositIndex, amount: Balance) {
+        self.deposits.push(FundingShare { deposit_id, amount });
+    }
+
+    /// Allocates the amount from the oldest deposits, returning the parts of the deposits funding it.
+    pub fn debit(&mut self, amount: Balance) -> Vec<FundingShare> {
+        assert!(
+            amount <= self.balance(),
+            "The lockup pool balance {} is less than {amount}",
+            self.balance()
+        );
+        let mut shares = vec![];
+        let mut remaining = amount;
+        while remaining > 0 {
+            let deposit = &mut self.deposits[0];
+            let share = std::cmp::min(deposit.amount, remaining);
+            shares.push(FundingShare {
+                deposit_id: deposit.deposit_id,
+                amount: share,
+            });
+            deposit.amount -= share;
+            remaining -= share;
+            if deposit.amount == 0 {
+                self.deposits.remove(0);
+            }
+        }
+        shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockup_pool_debit_oldest_first() {
+        let mut pool = LockupPool::default();
+        pool.fund(3, 100);
+        pool.fund(5, 50);
+
+        assert_eq!(
+            pool.debit(120),
+            vec![
+                FundingShare {
+                    deposit_id: 3,
+                    amount: 100,
+                },
+                FundingShare {
+                    deposit_id: 5,
+                    amount: 20,
+                },
+            ]
+        );
+        assert_eq!(pool.balance(), 30);
+        assert_eq!(pool.debit(30).len(), 1);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "The lockup pool balance 100 is less than 101")]
+    fn test_lockup_pool_overdraft() {
+        let mut pool = LockupPool::default();
+        pool.fund(0, 100);
+        pool.debit(101);
+    }
+}
@@ -0,0 +1,74 @@
+use near_sdk::{json_types::Base64VecU8, near, AccountId, PublicKey};
+use nitka::make_integration_version;
+
+use crate::TimestampSec;
+
+/// The longest validity of a view authorization, so a leaked signature exposes the lockups only briefly.
+pub const MAX_VIEW_AUTH_TTL_SEC: TimestampSec = 24 * 60 * 60;
+
+/// An authorization of a signed view while `Config::privacy_mode` is set. The viewer signs
+/// `view_auth_message` off-chain with the viewing key registered by `set_viewing_key`.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ViewAuth {
+    /// The lockup owner or a whitelisted privacy viewer
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub viewer_id: AccountId,
+    /// The timestamp the authorization expires at, at most `MAX_VIEW_AUTH_TTL_SEC` ahead
+    pub expires_at: TimestampSec,
+    /// An ed25519 signature of `view_auth_message` with the viewing key of the viewer
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub signature: Base64VecU8,
+}
+
+impl ViewAuth {
+    pub fn validate(&self, timestamp: TimestampSec) -> Result<(), String> {
+        if self.expires_at < timestamp {
+            return Err("view authorization expired".to_string());
+        }
+        if self.expires_at > timestamp.saturating_add(MAX_VIEW_AUTH_TTL_SEC) {
+            return Err(format!(
+                "view authorization should expire within {MAX_VIEW_AUTH_TTL_SEC} seconds"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The message signed to authorize views, the contract id prevents replaying the signature on other contracts.
+pub fn view_auth_message(contract_id: &AccountId, viewer_id: &AccountId, expires_at: TimestampSec) -> Vec<u8> {
+    format!("{contract_id}:view:{viewer_id}:{expires_at}").into_bytes()
+}
+
+/// Restricts the detailed lockup views to the lockup owners and whitelisted viewers while
+/// `Config::privacy_mode` is set. The contract state is still readable through the RPC,
+/// so the privacy mode only hides the amounts from the contract API and the web4 pages.
+#[make_integration_version]
+pub trait PrivacyApi {
+    /// Registers the ed25519 key the caller signs `ViewAuth` with, or removes it.
+    fn set_viewing_key(&mut self, public_key: Option<PublicKey>);
+
+    /// Allows the accounts to view the details of all lockups with a signed view, e.g. auditors or payroll operators.
+    fn add_to_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>);
+
+    fn remove_from_privacy_viewers_whitelist(&mut self, account_ids: Vec<AccountId>);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_auth_expiration() {
+        let auth = ViewAuth {
+            viewer_id: "alice.near".parse().unwrap(),
+            expires_at: 1_000,
+            signature: Base64VecU8(vec![]),
+        };
+
+        assert!(auth.validate(1_000).is_ok());
+        assert_eq!(auth.validate(1_001), Err("view authorization expired".to_string()));
+        assert!(auth.validate(1_000 - MAX_VIEW_AUTH_TTL_SEC - 1).is_err());
+    }
+}
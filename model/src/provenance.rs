@@ -0,0 +1,52 @@
+use near_sdk::{near, AccountId};
+
+use crate::{util::u128_dec_format, Balance, TimestampSec};
+
+pub type DepositIndex = u32;
+
+/// An `ft_on_transfer` deposit funding a lockup, a grant or a draft group.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Deposit {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub sender_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: Balance,
+    pub timestamp: TimestampSec,
+}
+
+/// The part of a deposit funding a lockup.
+#[near(serializers=[borsh])]
+#[derive(Debug, PartialEq, Clone)]
+pub struct FundingShare {
+    pub deposit_id: DepositIndex,
+    pub amount: Balance,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct FundingSource {
+    pub deposit_id: DepositIndex,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub sender_id: AccountId,
+    /// The part of the deposit funding the lockup or the draft group
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: Balance,
+    /// The timestamp of the deposit
+    pub timestamp: TimestampSec,
+}
+
+impl FundingSource {
+    pub fn new(deposit_id: DepositIndex, deposit: Deposit, amount: Balance) -> Self {
+        Self {
+            deposit_id,
+            sender_id: deposit.sender_id,
+            amount,
+            timestamp: deposit.timestamp,
+        }
+    }
+}
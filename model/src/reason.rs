@@ -0,0 +1,29 @@
+/// The longest reason stated for an administrative action.
+pub const MAX_ADMIN_REASON_LENGTH: usize = 256;
+
+/// A reason is recorded in the events and the termination records, e.g. the governance decision behind a clawback.
+pub fn validate_admin_reason(reason: &str) -> Result<(), String> {
+    if reason.trim().is_empty() || reason.len() > MAX_ADMIN_REASON_LENGTH {
+        return Err(format!("reason length must be between 1 and {MAX_ADMIN_REASON_LENGTH}"));
+    }
+    if reason.chars().any(char::is_control) {
+        return Err("reason must not contain control characters".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_admin_reason() {
+        assert!(validate_admin_reason("Board resolution 2024-07: clawback of unvested grant").is_ok());
+        assert!(validate_admin_reason(" ").is_err());
+        assert!(validate_admin_reason(&"a".repeat(MAX_ADMIN_REASON_LENGTH + 1)).is_err());
+        assert_eq!(
+            validate_admin_reason("line\nbreak"),
+            Err("reason must not contain control characters".to_string())
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use near_sdk::{near, AccountId};
+
+use crate::TimestampSec;
+
+/// The delay before a scheduled account remap can be executed, so the owner of the old account
+/// or the community can react to a remap from a compromised manager key.
+pub const ACCOUNT_REMAP_DELAY_SEC: TimestampSec = 7 * 24 * 60 * 60;
+
+/// A scheduled reassignment of all lockups of an account to a new account, e.g. of a lost account.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct AccountRemap {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub new_account_id: AccountId,
+    /// The timestamp the remap can be executed since
+    pub executable_at: TimestampSec,
+}
+
+impl AccountRemap {
+    pub fn new(new_account_id: AccountId, timestamp: TimestampSec) -> Self {
+        Self {
+            new_account_id,
+            executable_at: timestamp.saturating_add(ACCOUNT_REMAP_DELAY_SEC),
+        }
+    }
+
+    pub fn assert_executable(&self, new_account_id: &AccountId, timestamp: TimestampSec) {
+        assert_eq!(
+            &self.new_account_id, new_account_id,
+            "the new account doesn't match the scheduled remap"
+        );
+        assert!(
+            self.executable_at <= timestamp,
+            "the account remap can't be executed before {}",
+            self.executable_at
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_account_remap_delay() {
+        let new_account_id = AccountId::from_str("alice-recovered.near").unwrap();
+        let remap = AccountRemap::new(new_account_id.clone(), 1_000);
+
+        assert_eq!(remap.executable_at, 1_000 + ACCOUNT_REMAP_DELAY_SEC);
+        remap.assert_executable(&new_account_id, remap.executable_at);
+    }
+
+    #[test]
+    #[should_panic(expected = "the account remap can't be executed before")]
+    fn test_account_remap_before_delay() {
+        let new_account_id = AccountId::from_str("alice-recovered.near").unwrap();
+        let remap = AccountRemap::new(new_account_id.clone(), 1_000);
+
+        remap.assert_executable(&new_account_id, remap.executable_at - 1);
+    }
+}
@@ -0,0 +1,72 @@
+use near_sdk::{near, AccountId};
+
+use crate::TimestampSec;
+
+/// The time between the warning and the earliest revocation, giving the owner a chance to claim.
+pub const UNCLAIMED_REVOCATION_WARNING_SEC: TimestampSec = 30 * 24 * 60 * 60;
+
+/// Allows to revoke the unclaimed balance of a fully unlocked lockup if its owner stays inactive,
+/// e.g. dormant grants to lost keys.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct UnclaimedRevocation {
+    /// The account receiving the revoked balance, the payer of the lockup
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
+    /// The inactivity period after the full unlock or the last claim, whichever is later
+    pub after_sec: TimestampSec,
+    pub last_claim_timestamp: Option<TimestampSec>,
+    /// Set by the warning crank, cleared by a claim
+    pub warning_timestamp: Option<TimestampSec>,
+}
+
+impl UnclaimedRevocation {
+    pub fn new(beneficiary_id: AccountId, after_sec: TimestampSec) -> Self {
+        Self {
+            beneficiary_id,
+            after_sec,
+            last_claim_timestamp: None,
+            warning_timestamp: None,
+        }
+    }
+
+    /// The timestamp after which the owner is considered inactive.
+    pub fn inactive_since(&self, unlock_finish_timestamp: TimestampSec) -> TimestampSec {
+        unlock_finish_timestamp
+            .max(self.last_claim_timestamp.unwrap_or_default())
+            .saturating_add(self.after_sec)
+    }
+
+    /// The timestamp after which the balance can be revoked, if the warning has been issued.
+    pub fn revocable_at(&self, unlock_finish_timestamp: TimestampSec) -> Option<TimestampSec> {
+        self.warning_timestamp.map(|warning_timestamp| {
+            self.inactive_since(unlock_finish_timestamp)
+                .max(warning_timestamp.saturating_add(UNCLAIMED_REVOCATION_WARNING_SEC))
+        })
+    }
+
+    pub fn on_claim(&mut self, timestamp: TimestampSec) {
+        self.last_claim_timestamp = Some(timestamp);
+        self.warning_timestamp = None;
+    }
+
+    pub fn warn(&mut self, timestamp: TimestampSec, unlock_finish_timestamp: TimestampSec) {
+        assert!(
+            timestamp >= self.inactive_since(unlock_finish_timestamp),
+            "lockup owner is not inactive yet"
+        );
+        assert!(self.warning_timestamp.is_none(), "revocation warning is already issued");
+        self.warning_timestamp = Some(timestamp);
+    }
+
+    pub fn assert_revocable(&self, timestamp: TimestampSec, unlock_finish_timestamp: TimestampSec) {
+        let revocable_at = self
+            .revocable_at(unlock_finish_timestamp)
+            .expect("revocation warning is not issued");
+        assert!(
+            timestamp >= revocable_at,
+            "lockup is not revocable until {revocable_at}"
+        );
+    }
+}
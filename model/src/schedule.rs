@@ -1,20 +1,83 @@
 use near_sdk::{borsh::to_vec, env, near, CryptoHash};
 
-use crate::{u256::U256, util::u128_dec_format, Balance, TimestampSec};
+use crate::{
+    u256::U256,
+    util::{format_balance, format_timestamp, u128_dec_format},
+    Balance, TimestampSec,
+};
+
+const SECONDS_PER_DAY: u128 = 24 * 60 * 60;
+
+/// The largest number of checkpoints of a new schedule, e.g. enough for bi-weekly payroll steps over 4 years
+/// with two checkpoints per step. The gas of creating and claiming such lockups is measured in the integration tests.
+pub const MAX_SCHEDULE_CHECKPOINTS: usize = 256;
 
 #[near(serializers=[borsh, json])]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Checkpoint {
     /// The unix-timestamp in seconds since the epoch.
     pub timestamp: TimestampSec,
     #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub balance: Balance,
 }
 
 #[near(serializers=[borsh, json])]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct Schedule(pub Vec<Checkpoint>);
 
+/// The fixed-point scale of the exponential decay.
+const DECAY_SCALE: u128 = 1 << 64;
+
+/// The shape of the unlocks between every two checkpoints of a schedule, the unlocks are linear without a curve.
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum UnlockCurve {
+    /// Backweighted: the unlocked share of the segment is the square of its elapsed share.
+    Quadratic,
+    /// Frontweighted: the balance left to unlock in the segment halves every `half_life_sec`,
+    /// scaled so the whole segment balance is unlocked at its end.
+    ExponentialDecay { half_life_sec: TimestampSec },
+}
+
+impl UnlockCurve {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Self::ExponentialDecay { half_life_sec: 0 } = self {
+            return Err("exponential decay half-life must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// The part of the segment `balance` unlocked after `passed_duration` of its `total_duration`.
+    fn unlocked(&self, balance: Balance, passed_duration: TimestampSec, total_duration: TimestampSec) -> Balance {
+        let (unlocked_share, total_share) = match *self {
+            Self::Quadratic => (
+                U256::from(passed_duration) * U256::from(passed_duration),
+                U256::from(total_duration) * U256::from(total_duration),
+            ),
+            Self::ExponentialDecay { half_life_sec } => (
+                U256::from(DECAY_SCALE - decay(passed_duration, half_life_sec)),
+                U256::from(DECAY_SCALE - decay(total_duration, half_life_sec)),
+            ),
+        };
+        (U256::from(balance) * unlocked_share / total_share).as_u128()
+    }
+}
+
+/// `DECAY_SCALE * 2^(-duration / half_life_sec)`, interpolated linearly between the halvings.
+fn decay(duration: TimestampSec, half_life_sec: TimestampSec) -> u128 {
+    let halvings = duration / half_life_sec;
+    if halvings >= 64 {
+        return 0;
+    }
+    let half_life_sec = u128::from(half_life_sec);
+    let remainder = u128::from(duration) % half_life_sec;
+    (DECAY_SCALE >> halvings) * (2 * half_life_sec - remainder) / (2 * half_life_sec)
+}
+
 impl Schedule {
     pub fn new_zero_balance_from_to(start_timestamp: TimestampSec, finish_timestamp: TimestampSec) -> Self {
         assert!(finish_timestamp > start_timestamp, "Invariant");
@@ -50,53 +113,129 @@ impl Schedule {
         Self::new_unlocked_since(total_balance, 1)
     }
 
-    pub fn assert_valid(&self, total_balance: Balance) {
-        assert!(self.0.len() >= 2, "At least two checkpoints is required");
-        assert_eq!(
-            self.0.first().unwrap().balance,
-            0,
-            "The first checkpoint balance should be 0"
+    /// Creates a schedule that unlocks `initial_balance` immediately at `start_timestamp` (TGE),
+    /// keeps it flat until `cliff_timestamp` and then unlocks the rest linearly until `finish_timestamp`.
+    /// The instant unlock is expressed as a zero-balance checkpoint one second before the start,
+    /// so the initial balance is claimable exactly at `start_timestamp`.
+    pub fn new_with_cliff(
+        total_balance: Balance,
+        initial_balance: Balance,
+        start_timestamp: TimestampSec,
+        cliff_timestamp: TimestampSec,
+        finish_timestamp: TimestampSec,
+    ) -> Self {
+        assert!(start_timestamp > 0, "The start timestamp should be positive");
+        assert!(
+            start_timestamp <= cliff_timestamp,
+            "The cliff timestamp should be not less than the start timestamp"
         );
+        assert!(
+            cliff_timestamp < finish_timestamp,
+            "The finish timestamp should be greater than the cliff timestamp"
+        );
+        assert!(
+            initial_balance <= total_balance,
+            "The initial balance should be not greater than the total balance"
+        );
+
+        let mut checkpoints = vec![Checkpoint {
+            timestamp: start_timestamp - 1,
+            balance: 0,
+        }];
+        if initial_balance > 0 {
+            checkpoints.push(Checkpoint {
+                timestamp: start_timestamp,
+                balance: initial_balance,
+            });
+        }
+        if cliff_timestamp > checkpoints.last().unwrap().timestamp {
+            checkpoints.push(Checkpoint {
+                timestamp: cliff_timestamp,
+                balance: initial_balance,
+            });
+        }
+        checkpoints.push(Checkpoint {
+            timestamp: finish_timestamp,
+            balance: total_balance,
+        });
+
+        let schedule = Self(checkpoints);
+        schedule.assert_valid(total_balance);
+        schedule
+    }
+
+    pub fn assert_valid(&self, total_balance: Balance) {
+        self.validate(total_balance).unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate(&self, total_balance: Balance) -> Result<(), String> {
+        if self.0.len() < 2 {
+            return Err("At least two checkpoints is required".to_string());
+        }
+        if self.0.len() > MAX_SCHEDULE_CHECKPOINTS {
+            return Err(format!(
+                "The schedule can't have more than {MAX_SCHEDULE_CHECKPOINTS} checkpoints"
+            ));
+        }
+        if self.0.first().unwrap().balance != 0 {
+            return Err("The first checkpoint balance should be 0".to_string());
+        }
         for i in 1..self.0.len() {
-            assert!(
-                self.0[i - 1].timestamp < self.0[i].timestamp,
-                "The timestamp of checkpoint #{} should be less than the timestamp of the next checkpoint",
-                i - 1
-            );
-            assert!(
-                self.0[i - 1].balance <= self.0[i].balance,
-                "The balance of checkpoint #{} should be not greater than the balance of the next checkpoint",
-                i - 1
-            );
+            if self.0[i - 1].timestamp >= self.0[i].timestamp {
+                return Err(format!(
+                    "The timestamp of checkpoint #{} should be less than the timestamp of the next checkpoint",
+                    i - 1
+                ));
+            }
+            if self.0[i - 1].balance > self.0[i].balance {
+                return Err(format!(
+                    "The balance of checkpoint #{} should be not greater than the balance of the next checkpoint",
+                    i - 1
+                ));
+            }
         }
-        assert!(self.total_balance() > 0, "expected total balance to be positive",);
-        assert_eq!(
-            self.total_balance(),
-            total_balance,
-            "The schedule's total balance doesn't match the transferred balance"
-        );
+        if self.total_balance() == 0 {
+            return Err("expected total balance to be positive".to_string());
+        }
+        if self.total_balance() != total_balance {
+            return Err("The schedule's total balance doesn't match the transferred balance".to_string());
+        }
+        Ok(())
     }
 
     /// Verifies that this schedule is ahead of the given termination schedule at any point of time.
     /// Assumes they have equal total balance and both schedules are valid.
     pub fn assert_valid_termination_schedule(&self, termination_schedule: &Schedule) {
+        self.validate_termination_schedule(termination_schedule)
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    pub fn validate_termination_schedule(&self, termination_schedule: &Schedule) -> Result<(), String> {
         for checkpoint in &self.0 {
-            assert!(
-                checkpoint.balance <= termination_schedule.unlocked_balance(checkpoint.timestamp),
-                "The lockup schedule is ahead of the termination schedule at timestamp {}",
-                checkpoint.timestamp
-            );
+            if checkpoint.balance > termination_schedule.unlocked_balance(checkpoint.timestamp) {
+                return Err(format!(
+                    "The lockup schedule is ahead of the termination schedule at timestamp {}",
+                    checkpoint.timestamp
+                ));
+            }
         }
         for checkpoint in &termination_schedule.0 {
-            assert!(
-                checkpoint.balance >= self.unlocked_balance(checkpoint.timestamp),
-                "The lockup schedule is ahead of the termination schedule at timestamp {}",
-                checkpoint.timestamp
-            );
+            if checkpoint.balance < self.unlocked_balance(checkpoint.timestamp) {
+                return Err(format!(
+                    "The lockup schedule is ahead of the termination schedule at timestamp {}",
+                    checkpoint.timestamp
+                ));
+            }
         }
+        Ok(())
     }
 
     pub fn unlocked_balance(&self, current_timestamp: TimestampSec) -> Balance {
+        self.curved_unlocked_balance(current_timestamp, None)
+    }
+
+    /// The unlocked balance with the unlocks between the checkpoints shaped by the curve.
+    pub fn curved_unlocked_balance(&self, current_timestamp: TimestampSec, curve: Option<UnlockCurve>) -> Balance {
         // Using binary search by time to find the current checkpoint.
         let index = match self
             .0
@@ -122,16 +261,156 @@ impl Schedule {
 
         let total_duration = next_checkpoint.timestamp - checkpoint.timestamp;
         let passed_duration = current_timestamp - checkpoint.timestamp;
+        let balance = next_checkpoint.balance - checkpoint.balance;
         checkpoint.balance
-            + (U256::from(passed_duration) * U256::from(next_checkpoint.balance - checkpoint.balance)
-                / U256::from(total_duration))
-            .as_u128()
+            + match curve {
+                Some(curve) => curve.unlocked(balance, passed_duration, total_duration),
+                None => (U256::from(passed_duration) * U256::from(balance) / U256::from(total_duration)).as_u128(),
+            }
+    }
+
+    /// The current unlock rate in tokens per day and the timestamp of the next rate change.
+    /// There is no next rate change once the schedule is fully unlocked.
+    pub fn unlock_rate(&self, current_timestamp: TimestampSec) -> (Balance, Option<TimestampSec>) {
+        self.curved_unlock_rate(current_timestamp, None)
+    }
+
+    /// The unlock rate with the unlocks shaped by the curve, which is the balance unlocking
+    /// over the next day of the current segment for a curved schedule.
+    pub fn curved_unlock_rate(
+        &self,
+        current_timestamp: TimestampSec,
+        curve: Option<UnlockCurve>,
+    ) -> (Balance, Option<TimestampSec>) {
+        let next_index = self
+            .0
+            .partition_point(|checkpoint| checkpoint.timestamp <= current_timestamp);
+        if next_index == 0 {
+            // Not started
+            return (0, Some(self.0[0].timestamp));
+        }
+        if next_index == self.0.len() {
+            return (0, None);
+        }
+        let checkpoint = &self.0[next_index - 1];
+        let next_checkpoint = &self.0[next_index];
+        if curve.is_some() {
+            let day_end = u128::from(current_timestamp) + SECONDS_PER_DAY;
+            let day_end = TimestampSec::try_from(day_end).map_or(next_checkpoint.timestamp, |day_end| {
+                day_end.min(next_checkpoint.timestamp)
+            });
+            let rate =
+                self.curved_unlocked_balance(day_end, curve) - self.curved_unlocked_balance(current_timestamp, curve);
+            return (rate, Some(next_checkpoint.timestamp));
+        }
+
+        let rate = U256::from(next_checkpoint.balance - checkpoint.balance) * U256::from(SECONDS_PER_DAY)
+            / U256::from(next_checkpoint.timestamp - checkpoint.timestamp);
+        let rate = if rate > U256::from(Balance::MAX) {
+            Balance::MAX
+        } else {
+            rate.as_u128()
+        };
+        (rate, Some(next_checkpoint.timestamp))
     }
 
     pub fn total_balance(&self) -> Balance {
         self.0.last().unwrap().balance
     }
 
+    pub fn finish_timestamp(&self) -> TimestampSec {
+        self.0.last().unwrap().timestamp
+    }
+
+    /// The timestamp of the last checkpoint with zero balance, the balance unlocks only after it.
+    pub fn unlock_start(&self) -> TimestampSec {
+        self.0
+            .iter()
+            .take_while(|checkpoint| checkpoint.balance == 0)
+            .last()
+            .map_or(0, |checkpoint| checkpoint.timestamp)
+    }
+
+    /// Postpones the part of the schedule after `current_timestamp` by `duration` seconds.
+    /// The balance unlocked at `current_timestamp` is preserved.
+    pub fn postpone(&mut self, current_timestamp: TimestampSec, duration: TimestampSec) {
+        assert!(duration > 0, "The duration should be positive");
+        let unlocked_balance = self.unlocked_balance(current_timestamp);
+        let split_index = self
+            .0
+            .partition_point(|checkpoint| checkpoint.timestamp <= current_timestamp);
+        assert!(split_index < self.0.len(), "The schedule is already fully unlocked");
+
+        let mut checkpoints = self.0[..split_index].to_vec();
+        if let Some(last_checkpoint) = checkpoints.last() {
+            // The current timestamp is in the middle of a linear unlock, fixing the current balance.
+            if last_checkpoint.balance < unlocked_balance {
+                checkpoints.push(Checkpoint {
+                    timestamp: current_timestamp,
+                    balance: unlocked_balance,
+                });
+            }
+        }
+        for checkpoint in &self.0[split_index..] {
+            checkpoints.push(Checkpoint {
+                timestamp: checkpoint
+                    .timestamp
+                    .checked_add(duration)
+                    .expect("attempt to add with overflow"),
+                balance: checkpoint.balance,
+            });
+        }
+        self.0 = checkpoints;
+
+        assert!(self.unlocked_balance(current_timestamp) >= unlocked_balance, "Invariant");
+    }
+
+    /// Converts large step unlocks into linear unlocks over `window_sec` seconds.
+    /// Every checkpoint reached sooner than `window_sec` after the previous one is postponed
+    /// to the end of the window, but not past the next checkpoint,
+    /// so the smoothed schedule never unlocks ahead of the original one.
+    pub fn smoothed(&self, window_sec: TimestampSec) -> Self {
+        let mut checkpoints: Vec<Checkpoint> = Vec::with_capacity(self.0.len());
+        for (index, checkpoint) in self.0.iter().enumerate() {
+            let mut checkpoint = checkpoint.clone();
+            if let Some(prev_checkpoint) = checkpoints.last() {
+                let window_end = prev_checkpoint.timestamp.saturating_add(window_sec);
+                if checkpoint.balance > prev_checkpoint.balance && checkpoint.timestamp < window_end {
+                    checkpoint.timestamp = match self.0.get(index + 1) {
+                        Some(next_checkpoint) => window_end.min(next_checkpoint.timestamp.saturating_sub(1)),
+                        None => window_end,
+                    };
+                }
+            }
+            checkpoints.push(checkpoint);
+        }
+        Self(checkpoints)
+    }
+
+    /// The shares of the schedule by `weights`. Every checkpoint balance is floored for all shares
+    /// but the last one, which gets the remainder, so the shares sum up to the schedule.
+    /// The shares aren't validated, e.g. tiny balances may leave a share empty.
+    pub fn split(&self, weights: &[u32]) -> Vec<Self> {
+        let total_weight = U256::from(weights.iter().copied().map(u64::from).sum::<u64>());
+        let mut shares = vec![Vec::with_capacity(self.0.len()); weights.len()];
+        for checkpoint in &self.0 {
+            let mut remaining_balance = checkpoint.balance;
+            for (index, weight) in weights.iter().enumerate() {
+                let balance = if index + 1 == weights.len() {
+                    remaining_balance
+                } else {
+                    (U256::from(checkpoint.balance) * U256::from(*weight) / total_weight).as_u128()
+                };
+                remaining_balance -= balance;
+                shares[index].push(Checkpoint {
+                    timestamp: checkpoint.timestamp,
+                    balance,
+                });
+            }
+        }
+        shares.into_iter().map(Self).collect()
+    }
+
     /// Terminates the lockup schedule earlier.
     /// Assumes `new_total_balance` is not greater than the current total balance.
     pub fn terminate(&mut self, new_total_balance: Balance, finish_timestamp: TimestampSec) {
@@ -178,3 +457,271 @@ impl Schedule {
         res
     }
 }
+
+/// A checkpoint with the timestamp and the balance formatted for humans, if requested.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CheckpointView {
+    pub timestamp: TimestampSec,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub balance: Balance,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humanized: Option<CheckpointHumanizedView>,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct CheckpointHumanizedView {
+    /// ISO-8601 UTC date-time
+    pub timestamp: String,
+    pub balance: String,
+}
+
+impl CheckpointView {
+    pub fn humanize(mut self, decimals: u8) -> Self {
+        self.humanized = Some(CheckpointHumanizedView {
+            timestamp: format_timestamp(self.timestamp),
+            balance: format_balance(self.balance, decimals),
+        });
+        self
+    }
+}
+
+impl From<Checkpoint> for CheckpointView {
+    fn from(checkpoint: Checkpoint) -> Self {
+        Self {
+            timestamp: checkpoint.timestamp,
+            balance: checkpoint.balance,
+            humanized: None,
+        }
+    }
+}
+
+/// The checkpoints of the lockup schedule and of the vesting schedule, unless it's hashed or the same.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ScheduleView {
+    pub schedule: Vec<CheckpointView>,
+    /// The shape of the unlocks between the checkpoints, linear if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<UnlockCurve>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vesting_schedule: Option<Vec<CheckpointView>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_checkpoints_limit() {
+        let schedule = |num_checkpoints: usize| {
+            Schedule(
+                (0..TimestampSec::try_from(num_checkpoints).unwrap())
+                    .map(|i| Checkpoint {
+                        timestamp: i,
+                        balance: i.into(),
+                    })
+                    .collect(),
+            )
+        };
+
+        let total_balance = Balance::try_from(MAX_SCHEDULE_CHECKPOINTS - 1).unwrap();
+        assert!(schedule(MAX_SCHEDULE_CHECKPOINTS).validate(total_balance).is_ok());
+        assert_eq!(
+            schedule(MAX_SCHEDULE_CHECKPOINTS + 1).validate(total_balance + 1),
+            Err(format!(
+                "The schedule can't have more than {MAX_SCHEDULE_CHECKPOINTS} checkpoints"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_schedule_split() {
+        let schedule = Schedule::new_with_cliff(1_000, 100, 1_000_000, 1_000_100, 1_000_200);
+
+        let shares = schedule.split(&[1, 3]);
+        assert_eq!(shares[0].total_balance(), 250);
+        assert_eq!(shares[0].unlocked_balance(1_000_000), 25);
+        assert_eq!(shares[0].unlocked_balance(1_000_150), 137);
+        assert_eq!(shares[1].total_balance(), 750);
+    }
+
+    #[test]
+    fn test_schedule_split_not_divisible() {
+        let schedule = Schedule::new_with_cliff(1_000, 100, 1_000_000, 1_000_100, 1_000_200);
+
+        let shares = schedule.split(&[1, 1, 1]);
+        assert_eq!(
+            shares.iter().map(Schedule::total_balance).collect::<Vec<_>>(),
+            vec![333, 333, 334]
+        );
+        for (index, checkpoint) in schedule.0.iter().enumerate() {
+            let balance: Balance = shares.iter().map(|share| share.0[index].balance).sum();
+            assert_eq!(balance, checkpoint.balance);
+        }
+        for share in &shares {
+            share.assert_valid(share.total_balance());
+        }
+
+        let shares = schedule.split(&[2, 5]);
+        assert_eq!(shares[0].total_balance(), 285);
+        assert_eq!(shares[1].total_balance(), 715);
+    }
+
+    #[test]
+    fn test_schedule_with_cliff_unlocks_initial_balance_at_start() {
+        let schedule = Schedule::new_with_cliff(1_000, 100, 1_000_000, 1_000_100, 1_000_200);
+
+        assert_eq!(schedule.unlocked_balance(999_999), 0);
+        assert_eq!(schedule.unlocked_balance(1_000_000), 100);
+        assert_eq!(schedule.unlocked_balance(1_000_100), 100);
+        assert_eq!(schedule.unlocked_balance(1_000_150), 550);
+        assert_eq!(schedule.unlocked_balance(1_000_200), 1_000);
+        assert_eq!(schedule.unlock_start(), 999_999);
+    }
+
+    #[test]
+    fn test_schedule_with_cliff_at_start() {
+        let schedule = Schedule::new_with_cliff(1_000, 100, 1_000_000, 1_000_000, 1_000_100);
+
+        assert_eq!(schedule.0.len(), 3);
+        assert_eq!(schedule.unlocked_balance(1_000_000), 100);
+        assert_eq!(schedule.unlocked_balance(1_000_050), 550);
+    }
+
+    #[test]
+    fn test_schedule_with_cliff_without_initial_balance() {
+        let schedule = Schedule::new_with_cliff(1_000, 0, 1_000_000, 1_000_100, 1_000_200);
+
+        assert_eq!(schedule.unlocked_balance(1_000_000), 0);
+        assert_eq!(schedule.unlocked_balance(1_000_100), 0);
+        assert_eq!(schedule.unlocked_balance(1_000_200), 1_000);
+    }
+
+    #[test]
+    fn test_postpone_preserves_unlocked_balance() {
+        let mut schedule = Schedule::new_with_cliff(1_000, 0, 1_000_000, 1_000_000, 1_000_100);
+
+        schedule.postpone(1_000_050, 100);
+
+        assert_eq!(schedule.unlocked_balance(1_000_050), 500);
+        assert_eq!(schedule.unlocked_balance(1_000_125), 750);
+        assert_eq!(schedule.unlocked_balance(1_000_200), 1_000);
+        assert_eq!(schedule.finish_timestamp(), 1_000_200);
+        schedule.assert_valid(1_000);
+    }
+
+    #[test]
+    fn test_unlock_rate() {
+        let schedule = Schedule::new_with_cliff(1_000, 100, 1_000_000, 1_086_400, 1_259_200);
+
+        assert_eq!(schedule.unlock_rate(999_998), (0, Some(999_999)));
+        assert_eq!(schedule.unlock_rate(1_000_000), (0, Some(1_086_400)));
+        assert_eq!(schedule.unlock_rate(1_100_000), (450, Some(1_259_200)));
+        assert_eq!(schedule.unlock_rate(1_259_200), (0, None));
+    }
+
+    #[test]
+    fn test_curved_unlocked_balance() {
+        let schedule = Schedule::new_with_cliff(1_000, 0, 1_000_000, 1_000_000, 1_000_100);
+        let quadratic = Some(UnlockCurve::Quadratic);
+        let exponential = Some(UnlockCurve::ExponentialDecay { half_life_sec: 50 });
+
+        assert_eq!(schedule.curved_unlocked_balance(1_000_050, quadratic), 250);
+        assert_eq!(schedule.curved_unlocked_balance(1_000_025, exponential), 333);
+        assert_eq!(schedule.curved_unlocked_balance(1_000_050, exponential), 666);
+        for timestamp in 999_999..1_000_101 {
+            let linear = schedule.unlocked_balance(timestamp);
+            assert!(schedule.curved_unlocked_balance(timestamp, quadratic) <= linear);
+            assert!(schedule.curved_unlocked_balance(timestamp, exponential) >= linear);
+            assert!(
+                schedule.curved_unlocked_balance(timestamp, exponential)
+                    <= schedule.curved_unlocked_balance(timestamp + 1, exponential)
+            );
+        }
+        assert_eq!(schedule.curved_unlocked_balance(1_000_100, quadratic), 1_000);
+        assert_eq!(schedule.curved_unlocked_balance(1_000_100, exponential), 1_000);
+
+        assert_eq!(
+            schedule.curved_unlock_rate(1_000_050, quadratic),
+            (750, Some(1_000_100))
+        );
+    }
+
+    #[test]
+    fn test_smoothed_steps() {
+        let schedule = Schedule(vec![
+            Checkpoint {
+                timestamp: 999,
+                balance: 0,
+            },
+            Checkpoint {
+                timestamp: 1_000,
+                balance: 100,
+            },
+            Checkpoint {
+                timestamp: 1_999,
+                balance: 100,
+            },
+            Checkpoint {
+                timestamp: 2_000,
+                balance: 200,
+            },
+        ]);
+
+        let smoothed = schedule.smoothed(100);
+
+        smoothed.assert_valid(200);
+        assert_eq!(smoothed.unlocked_balance(1_000), 1);
+        assert_eq!(smoothed.unlocked_balance(1_049), 50);
+        assert_eq!(smoothed.unlocked_balance(1_099), 100);
+        assert_eq!(smoothed.unlocked_balance(2_049), 150);
+        assert_eq!(smoothed.finish_timestamp(), 2_099);
+    }
+
+    #[test]
+    fn test_smoothed_step_before_next_checkpoint() {
+        let schedule = Schedule(vec![
+            Checkpoint {
+                timestamp: 999,
+                balance: 0,
+            },
+            Checkpoint {
+                timestamp: 1_000,
+                balance: 100,
+            },
+            Checkpoint {
+                timestamp: 1_050,
+                balance: 200,
+            },
+        ]);
+
+        let smoothed = schedule.smoothed(100);
+
+        smoothed.assert_valid(200);
+        assert_eq!(smoothed.0[1].timestamp, 1_049);
+        assert_eq!(smoothed.finish_timestamp(), 1_149);
+        for timestamp in 999..1_150 {
+            assert!(smoothed.unlocked_balance(timestamp) <= schedule.unlocked_balance(timestamp));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The schedule is already fully unlocked")]
+    fn test_postpone_fully_unlocked() {
+        let mut schedule = Schedule::new_unlocked_since(1_000, 1_000_000);
+
+        schedule.postpone(1_000_000, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "The initial balance should be not greater than the total balance")]
+    fn test_schedule_with_cliff_initial_balance_exceeds_total() {
+        Schedule::new_with_cliff(1_000, 1_001, 1_000_000, 1_000_100, 1_000_200);
+    }
+}
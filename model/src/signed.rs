@@ -0,0 +1,92 @@
+use near_sdk::{borsh, json_types::Base64VecU8, near, AccountId, PromiseOrValue, PublicKey};
+use nitka::make_integration_version;
+
+use crate::{lockup::LockupIndex, TimestampSec, WrappedBalance};
+
+/// An admin operation the manager authorizes off-chain, see `execute_signed`.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum AdminOperation {
+    AddToDepositWhitelist {
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        account_ids: Vec<AccountId>,
+    },
+    RemoveFromDepositWhitelist {
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    },
+    AddToDraftOperatorsWhitelist {
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        account_ids: Vec<AccountId>,
+    },
+    RemoveFromDraftOperatorsWhitelist {
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    },
+    /// Same as `terminate`, allowed under any termination policy
+    Terminate {
+        lockup_index: LockupIndex,
+        termination_timestamp: Option<TimestampSec>,
+        #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+        expected_unvested: Option<WrappedBalance>,
+        reason: Option<String>,
+    },
+}
+
+/// An admin operation signed by the manager signing key and relayed by any account.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SignedAction {
+    /// Should exceed the nonce of the previous signed action of the manager, so each action is executed once
+    pub nonce: u64,
+    /// The action can't be relayed after this timestamp
+    pub expires_at: TimestampSec,
+    pub operation: AdminOperation,
+}
+
+/// The message signed to authorize the action, the contract id prevents replaying it on other contracts.
+pub fn signed_action_message(contract_id: &AccountId, action: &SignedAction) -> Vec<u8> {
+    let mut message = format!("{contract_id}:signed_action:").into_bytes();
+    message.extend(borsh::to_vec(action).unwrap());
+    message
+}
+
+/// Admin operations authorized by an offline manager key and relayed by an operational account.
+#[make_integration_version]
+pub trait SignedApi {
+    /// Sets the ed25519 key signing the actions of `execute_signed`, or removes it. Only the manager can call it.
+    fn set_manager_signing_key(&mut self, public_key: Option<PublicKey>);
+
+    /// Executes an action signed with the manager signing key over `signed_action_message`,
+    /// the nonce of the manager is advanced to the action nonce. Anyone can relay the action.
+    /// Returns the unvested balance of terminations, 0 for other operations.
+    fn execute_signed(&mut self, action: SignedAction, signature: Base64VecU8) -> PromiseOrValue<WrappedBalance>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_action_message_binds_contract() {
+        let action = SignedAction {
+            nonce: 1,
+            expires_at: 1_000,
+            operation: AdminOperation::AddToDepositWhitelist {
+                account_ids: vec!["alice.near".parse().unwrap()],
+            },
+        };
+        let message = signed_action_message(&"lockup.near".parse().unwrap(), &action);
+
+        assert!(message.starts_with(b"lockup.near:signed_action:"));
+        assert_ne!(message, signed_action_message(&"other.near".parse().unwrap(), &action));
+        assert_ne!(
+            message,
+            signed_action_message(&"lockup.near".parse().unwrap(), &SignedAction { nonce: 2, ..action })
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use near_sdk::{near, AccountId, Gas};
+
+use crate::{
+    draft::{DraftGroupIndex, DraftIndex},
+    lockup::LockupIndex,
+    u256::U256,
+    util::u128_dec_format,
+    Balance, TimestampSec,
+};
+
+/// Lifetime token flows of the contract, used to check that the contract token balance
+/// equals `total_deposited - total_claimed - total_refunded - total_burned - total_revoked`.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TokenFlows {
+    /// Total amount received via `ft_on_transfer`
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_deposited: Balance,
+    /// Total amount transferred to lockup owners via claims
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_claimed: Balance,
+    /// Total amount returned to termination beneficiaries
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_refunded: Balance,
+    /// Total amount of terminated lockups burned
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_burned: Balance,
+    /// Total amount of protocol fees transferred to the fee collector, included in `total_claimed`
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_fees: Balance,
+    /// Total amount of dormant lockups revoked to the revocation beneficiaries
+    #[serde(default, with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_revoked: Balance,
+}
+
+impl TokenFlows {
+    /// The balance held by the contract for lockups and draft groups.
+    /// It's counted since the upgrade to this version, so it excludes lockups created before the upgrade.
+    pub fn managed_balance(&self) -> Balance {
+        self.total_deposited
+            .saturating_sub(self.total_claimed)
+            .saturating_sub(self.total_refunded)
+            .saturating_sub(self.total_burned)
+            .saturating_sub(self.total_revoked)
+    }
+
+    pub fn deposit(&mut self, amount: Balance) {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+    }
+
+    pub fn claim(&mut self, amount: Balance) {
+        self.total_claimed = self.total_claimed.checked_add(amount).expect("attempt to add with overflow");
+    }
+
+    pub fn refund(&mut self, amount: Balance) {
+        self.total_refunded = self
+            .total_refunded
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+    }
+
+    pub fn burn(&mut self, amount: Balance) {
+        self.total_burned = self
+            .total_burned
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+    }
+
+    pub fn revoke(&mut self, amount: Balance) {
+        self.total_revoked = self
+            .total_revoked
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+    }
+
+    pub fn collect_fee(&mut self, amount: Balance) {
+        self.total_fees = self
+            .total_fees
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+    }
+}
+
+/// A cheap summary of the contract state for monitoring probes.
+/// The JSON shape is stable: fields are never renamed or removed, only added.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Health {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub token_account_id: AccountId,
+    /// Whether any of `Config::pause_flags` is set
+    pub paused: bool,
+    /// The contract package version, `CARGO_PKG_VERSION` of the deployed code. It's bumped by releases,
+    /// so it tells the state layout apart only between released versions.
+    pub version: String,
+    pub num_lockups: u32,
+    pub num_grants: u32,
+    pub num_drafts: u32,
+    pub num_draft_groups: u32,
+    pub num_terminations: u32,
+}
+
+/// The claims of a lockup, a claim of several lockups is charged to each of them by its claimed amount.
+#[near(serializers=[borsh, json])]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct LockupClaimStats {
+    /// The number of claims including the lockup
+    pub claims_count: u32,
+    /// The part of the gas of these claims charged to the lockup
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub gas_charged: Gas,
+}
+
+impl LockupClaimStats {
+    pub fn record(&mut self, gas: Gas) {
+        self.claims_count = self.claims_count.saturating_add(1);
+        self.gas_charged = self.gas_charged.saturating_add(gas);
+    }
+
+    /// Splits the gas of a claim between its lockups proportionally to `amounts`, flooring every share
+    /// but the last one, which gets the remainder.
+    pub fn gas_shares(gas: Gas, amounts: &[Balance]) -> Vec<Gas> {
+        let total_amount = amounts
+            .iter()
+            .fold(U256::zero(), |total, amount| total + U256::from(*amount));
+        let mut remaining_gas = gas.as_gas();
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(index, amount)| {
+                let share = if index + 1 == amounts.len() || total_amount.is_zero() {
+                    remaining_gas
+                } else {
+                    (U256::from(gas.as_gas()) * U256::from(*amount) / total_amount).as_u64()
+                };
+                remaining_gas -= share;
+                Gas::from_gas(share)
+            })
+            .collect()
+    }
+}
+
+/// Indices that will be assigned to the next created entities.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct NextIndices {
+    pub lockup_id: LockupIndex,
+    pub draft_id: DraftIndex,
+    pub draft_group_id: DraftGroupIndex,
+}
+
+/// Aggregated balances of all lockups with a tag.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TagStats {
+    pub num_lockups: u32,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub total_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub claimed_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub unclaimed_balance: Balance,
+}
+
+/// The outstanding balances of the contract compared with its token balance, see `check_solvency`.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct SolvencyReport {
+    /// The unclaimed balance of all lockups, including the pending ones
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub lockups_balance: Balance,
+    /// The funded balance of draft groups, merkle airdrops and lockup pools not converted into lockups yet
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub unconverted_balance: Balance,
+    /// The token balance of the contract provided by the caller
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub contract_balance: Balance,
+    /// The part of the contract balance exceeding the outstanding balance
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub surplus: Balance,
+    /// The part of the outstanding balance not covered by the contract balance
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub deficit: Balance,
+}
+
+impl SolvencyReport {
+    pub fn new(lockups_balance: Balance, unconverted_balance: Balance, contract_balance: Balance) -> Self {
+        let outstanding_balance = lockups_balance
+            .checked_add(unconverted_balance)
+            .expect("attempt to add with overflow");
+        Self {
+            lockups_balance,
+            unconverted_balance,
+            contract_balance,
+            surplus: contract_balance.saturating_sub(outstanding_balance),
+            deficit: outstanding_balance.saturating_sub(contract_balance),
+        }
+    }
+
+    pub fn is_solvent(&self) -> bool {
+        self.deficit == 0
+    }
+}
+
+/// The number of owners returned by `get_top_upcoming_unlocks` by default.
+pub const DEFAULT_UPCOMING_UNLOCKS_LIMIT: u32 = 10;
+
+/// The balance unlocking for an owner within the window of `get_top_upcoming_unlocks`.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct UpcomingUnlock {
+    /// The owner of the unlocking lockups, `None` while `Config::anonymize_upcoming_unlocks` is set
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub account_id: Option<AccountId>,
+    /// The total balance unlocking within the window over all lockups of the owner
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: Balance,
+    pub num_lockups: u32,
+}
+
+impl UpcomingUnlock {
+    /// Sums the unlocks of lockups by owner and returns the `limit` largest ones, sorted by the amount
+    /// in descending order. Owners are dropped from the result when `anonymize` is set.
+    pub fn top(
+        unlocks: impl IntoIterator<Item = (AccountId, Balance)>,
+        limit: usize,
+        anonymize: bool,
+    ) -> Vec<UpcomingUnlock> {
+        let mut totals: HashMap<AccountId, (Balance, u32)> = HashMap::new();
+        for (account_id, amount) in unlocks.into_iter().filter(|(_, amount)| *amount > 0) {
+            let total = totals.entry(account_id).or_default();
+            total.0 += amount;
+            total.1 += 1;
+        }
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|(a_id, (a, _)), (b_id, (b, _))| b.cmp(a).then_with(|| a_id.cmp(b_id)));
+        totals
+            .into_iter()
+            .take(limit)
+            .map(|(account_id, (amount, num_lockups))| UpcomingUnlock {
+                account_id: (!anonymize).then_some(account_id),
+                amount,
+                num_lockups,
+            })
+            .collect()
+    }
+}
+
+/// The largest number of buckets returned by `get_emission_schedule`.
+pub const MAX_EMISSION_BUCKETS: usize = 512;
+
+/// The balance unlocking across all lockups within `[start, end)`, see `get_emission_schedule`.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct EmissionBucket {
+    pub start: TimestampSec,
+    pub end: TimestampSec,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub amount: Balance,
+}
+
+impl EmissionBucket {
+    /// Splits `[from_ts, to_ts)` into empty buckets of `bucket_sec` seconds, the last one may be shorter.
+    pub fn split(bucket_sec: TimestampSec, from_ts: TimestampSec, to_ts: TimestampSec) -> Result<Vec<Self>, String> {
+        if bucket_sec == 0 {
+            return Err("bucket_sec should be positive".to_string());
+        }
+        if from_ts >= to_ts {
+            return Err("from_ts should be before to_ts".to_string());
+        }
+        let num_buckets = (to_ts - from_ts).div_ceil(bucket_sec);
+        if usize::try_from(num_buckets).unwrap() > MAX_EMISSION_BUCKETS {
+            return Err(format!(
+                "The emission schedule can't have more than {MAX_EMISSION_BUCKETS} buckets"
+            ));
+        }
+        Ok((0..num_buckets)
+            .map(|bucket| {
+                let start = from_ts + bucket * bucket_sec;
+                Self {
+                    start,
+                    end: std::cmp::min(start.saturating_add(bucket_sec), to_ts),
+                    amount: 0,
+                }
+            })
+            .collect())
+    }
+
+    /// Adds the unlocks of a lockup to the buckets, `unlocked_balance` returns its unlocked balance at a timestamp.
+    pub fn add_unlocks(buckets: &mut [Self], unlocked_balance: impl Fn(TimestampSec) -> Balance) {
+        let Some(first) = buckets.first() else {
+            return;
+        };
+        let mut unlocked = unlocked_balance(first.start);
+        for bucket in buckets {
+            let unlocked_at_end = unlocked_balance(bucket.end);
+            bucket.amount += unlocked_at_end - unlocked;
+            unlocked = unlocked_at_end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockup_claim_gas_shares() {
+        let shares = LockupClaimStats::gas_shares(Gas::from_gas(1_000), &[100, 200, 0, 300]);
+        assert_eq!(
+            shares.iter().map(Gas::as_gas).collect::<Vec<_>>(),
+            vec![166, 333, 0, 501]
+        );
+
+        let mut stats = LockupClaimStats::default();
+        stats.record(shares[0]);
+        stats.record(shares[0]);
+        assert_eq!(stats.claims_count, 2);
+        assert_eq!(stats.gas_charged, Gas::from_gas(332));
+    }
+
+    #[test]
+    fn test_token_flows_managed_balance() {
+        let mut token_flows = TokenFlows::default();
+        token_flows.deposit(100);
+        token_flows.claim(30);
+        token_flows.refund(20);
+        token_flows.burn(10);
+        token_flows.revoke(15);
+        assert_eq!(token_flows.total_refunded, 20);
+        assert_eq!(token_flows.total_revoked, 15);
+        assert_eq!(token_flows.managed_balance(), 25);
+    }
+
+    #[test]
+    fn test_solvency_report() {
+        let report = SolvencyReport::new(70, 20, 100);
+        assert_eq!((report.surplus, report.deficit), (10, 0));
+        assert!(report.is_solvent());
+
+        let report = SolvencyReport::new(70, 20, 85);
+        assert_eq!((report.surplus, report.deficit), (0, 5));
+        assert!(!report.is_solvent());
+    }
+
+    #[test]
+    fn test_top_upcoming_unlocks() {
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let bob: AccountId = "bob.near".parse().unwrap();
+        let carol: AccountId = "carol.near".parse().unwrap();
+        let unlocks = vec![
+            (alice.clone(), 30),
+            (bob.clone(), 50),
+            (alice.clone(), 40),
+            (carol.clone(), 0),
+            (carol, 50),
+        ];
+
+        let top = UpcomingUnlock::top(unlocks.clone(), 2, false);
+        assert_eq!(
+            top,
+            vec![
+                UpcomingUnlock {
+                    account_id: Some(alice),
+                    amount: 70,
+                    num_lockups: 2,
+                },
+                UpcomingUnlock {
+                    account_id: Some(bob),
+                    amount: 50,
+                    num_lockups: 1,
+                },
+            ]
+        );
+
+        let anonymized = UpcomingUnlock::top(unlocks, 10, true);
+        assert_eq!(anonymized.len(), 3);
+        assert!(anonymized.iter().all(|unlock| unlock.account_id.is_none()));
+        assert_eq!(anonymized[2].amount, 50);
+    }
+
+    #[test]
+    fn test_emission_buckets() {
+        let mut buckets = EmissionBucket::split(100, 1_000, 1_250).unwrap();
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|bucket| (bucket.start, bucket.end))
+                .collect::<Vec<_>>(),
+            vec![(1_000, 1_100), (1_100, 1_200), (1_200, 1_250)]
+        );
+
+        // linear unlock of 1 token per second from 1050 to 1150
+        EmissionBucket::add_unlocks(&mut buckets, |timestamp| {
+            u128::from(timestamp.clamp(1_050, 1_150) - 1_050)
+        });
+        // cliff of 30 tokens at 1220
+        EmissionBucket::add_unlocks(&mut buckets, |timestamp| if timestamp >= 1_220 { 30 } else { 0 });
+        assert_eq!(
+            buckets.iter().map(|bucket| bucket.amount).collect::<Vec<_>>(),
+            vec![50, 50, 30]
+        );
+
+        assert!(EmissionBucket::split(0, 1_000, 1_250).is_err());
+        assert!(EmissionBucket::split(100, 1_250, 1_250).is_err());
+        assert_eq!(
+            EmissionBucket::split(1, 0, 513),
+            Err(format!(
+                "The emission schedule can't have more than {MAX_EMISSION_BUCKETS} buckets"
+            ))
+        );
+    }
+}
@@ -1,38 +1,184 @@
 use near_sdk::{json_types::Base58CryptoHash, near, AccountId, CryptoHash};
 
-use crate::{lockup::Lockup, schedule::Schedule, Balance, TimestampSec};
+use crate::{
+    lockup::{Lockup, LockupIndex},
+    schedule::Schedule,
+    util::u128_dec_format,
+    Balance, TimestampSec,
+};
 
 #[near(serializers=[borsh, json])]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub enum VestingConditions {
     SameAsLockupSchedule,
-    //Hash(Base58CryptoHash),
     Schedule(Schedule),
+    /// The hash of a private vesting schedule, revealed only to terminate the lockup.
+    /// The claimable balance follows the lockup schedule until then.
+    Hash(#[cfg_attr(feature = "ts", ts(type = "string"))] Base58CryptoHash),
+    /// The vested balance is fetched at termination from a whitelisted vesting oracle contract, e.g. for
+    /// milestone-based vesting. The view `method` is called with `lockup_index`, `account_id` and
+    /// `termination_timestamp`, and returns the vested balance as a string.
+    /// The claimable balance follows the lockup schedule until the termination.
+    External {
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        contract_id: AccountId,
+        method: String,
+    },
+}
+
+impl VestingConditions {
+    pub fn kind(&self) -> VestingConditionsKind {
+        match self {
+            VestingConditions::SameAsLockupSchedule => VestingConditionsKind::SameAsLockupSchedule,
+            VestingConditions::Schedule(_) => VestingConditionsKind::Schedule,
+            VestingConditions::Hash(_) => VestingConditionsKind::Hash,
+            VestingConditions::External { .. } => VestingConditionsKind::External,
+        }
+    }
+}
+
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum VestingConditionsKind {
+    SameAsLockupSchedule,
+    Schedule,
+    Hash,
+    External,
 }
 
 #[near(serializers=[borsh, json])]
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
 pub struct TerminationConfig {
     /// The account ID who paid for the lockup creation
     /// and will receive unvested balance upon termination
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub beneficiary_id: AccountId,
     /// An optional vesting schedule
     pub vesting_schedule: VestingConditions,
 }
 
+/// Where the unvested balance of lockups created with `burn_on_termination` goes upon termination.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum TerminationBurn {
+    /// Transfers the unvested balance to an account nobody controls
+    Address(#[cfg_attr(feature = "ts", ts(type = "string"))] AccountId),
+    /// Burns the unvested balance with `ft_burn` of the token, if the token supports it
+    FtBurn,
+}
+
+/// Who can revoke the unvested balance of a lockup, for owner dashboards.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TerminationSummary {
+    /// The account receiving the unvested balance upon termination
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
+    pub vesting_conditions_kind: VestingConditionsKind,
+}
+
+impl From<&TerminationConfig> for TerminationSummary {
+    fn from(termination_config: &TerminationConfig) -> Self {
+        Self {
+            beneficiary_id: termination_config.beneficiary_id.clone(),
+            vesting_conditions_kind: termination_config.vesting_schedule.kind(),
+        }
+    }
+}
+
+/// A persistent record of a lockup termination, kept for clawback reconciliation.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct TerminationRecord {
+    pub lockup_index: LockupIndex,
+    pub termination_timestamp: TimestampSec,
+    #[serde(with = "u128_dec_format")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub unvested_balance: Balance,
+    /// The account ID that received the unvested balance
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub beneficiary_id: AccountId,
+    /// The account ID that called the termination
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub terminated_by: AccountId,
+    /// The reason stated for the termination
+    pub reason: Option<String>,
+}
+
 impl Lockup {
-    pub fn terminate(
-        &mut self,
-        termination_timestamp: TimestampSec,
-    ) -> (Balance, AccountId) {
-        let termination_config = self.termination_config.take().expect("No termination config");
+    /// The vested balance at the termination. The vesting schedule of `VestingConditions::Hash` should be
+    /// revealed before, see `reveal_vesting_schedule`. The vested balance of `VestingConditions::External`
+    /// is fetched from its oracle.
+    pub fn vested_balance(&self, termination_timestamp: TimestampSec) -> Balance {
+        let termination_config = self.termination_config.as_ref().expect("No termination config");
+        match &termination_config.vesting_schedule {
+            VestingConditions::SameAsLockupSchedule => self.schedule.unlocked_balance(termination_timestamp),
+            VestingConditions::Schedule(schedule) => schedule.unlocked_balance(termination_timestamp),
+            VestingConditions::Hash(_) => {
+                panic!("The hashed vesting schedule should be revealed with `reveal_vesting_schedule`")
+            }
+            VestingConditions::External { .. } => {
+                panic!("The vested balance of an external vesting is fetched from its oracle")
+            }
+        }
+    }
+
+    /// The oracle contract and its view method of `VestingConditions::External`.
+    pub fn vesting_oracle(&self) -> Option<(&AccountId, &str)> {
+        match &self.termination_config {
+            Some(TerminationConfig {
+                vesting_schedule: VestingConditions::External { contract_id, method },
+                ..
+            }) => Some((contract_id, method)),
+            _ => None,
+        }
+    }
+
+    /// Replaces the hashed vesting schedule with the revealed one, validated the same way as
+    /// the vesting schedule of a new lockup.
+    pub fn reveal_vesting_schedule(&mut self, schedule: Schedule) -> Result<(), String> {
         let total_balance = self.schedule.total_balance();
-        let vested_balance = match &termination_config.vesting_schedule {
-            VestingConditions::SameAsLockupSchedule => &self.schedule,
-            VestingConditions::Schedule(schedule) => schedule,
+        let Some(termination_config) = self.termination_config.as_mut() else {
+            return Err("The lockup has no hashed vesting schedule".to_string());
+        };
+        let VestingConditions::Hash(hash) = &termination_config.vesting_schedule else {
+            return Err("The lockup has no hashed vesting schedule".to_string());
+        };
+        if &Base58CryptoHash::from(schedule.hash()) != hash {
+            return Err("The revealed vesting schedule doesn't match the hash".to_string());
         }
-        .unlocked_balance(termination_timestamp);
-        let unvested_balance = total_balance - vested_balance;
+        schedule.validate(total_balance)?;
+        self.schedule.validate_termination_schedule(&schedule)?;
+        termination_config.vesting_schedule = VestingConditions::Schedule(schedule);
+        Ok(())
+    }
+
+    pub fn validate_vested_balance(&self, vested_balance: Balance) -> Result<(), String> {
+        if vested_balance < self.claimed_balance {
+            return Err("The vested balance is behind the claimed balance".to_string());
+        }
+        let total_balance = self.schedule.total_balance();
+        if vested_balance > total_balance {
+            return Err(format!(
+                "The vested balance {vested_balance} exceeds the lockup balance {total_balance}"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Terminates the lockup at the vested balance, see `vested_balance`.
+    /// Returns the unvested balance and the beneficiary of the termination.
+    pub fn terminate(&mut self, termination_timestamp: TimestampSec, vested_balance: Balance) -> (Balance, AccountId) {
+        let termination_config = self.termination_config.take().expect("No termination config");
+        self.validate_vested_balance(vested_balance)
+            .unwrap_or_else(|error| panic!("{error}"));
+        let unvested_balance = self.schedule.total_balance() - vested_balance;
         if unvested_balance > 0 {
             self.schedule.terminate(vested_balance, termination_timestamp);
         }
@@ -0,0 +1,101 @@
+use near_sdk::near;
+
+use crate::{Balance, TimestampSec, WrappedBalance};
+
+const HOUR_SEC: TimestampSec = 60 * 60;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub enum ClaimThrottlePeriod {
+    Block,
+    Hour,
+}
+
+/// A cap on the total amount transferred out by claims within a period,
+/// so large unlocks can't be claimed and dumped in a single burst.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct ClaimThrottle {
+    pub period: ClaimThrottlePeriod,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub max_amount: WrappedBalance,
+}
+
+impl ClaimThrottle {
+    /// The number of the period containing the given block.
+    pub fn period_number(&self, block_height: u64, timestamp: TimestampSec) -> u64 {
+        match self.period {
+            ClaimThrottlePeriod::Block => block_height,
+            ClaimThrottlePeriod::Hour => u64::from(timestamp / HOUR_SEC),
+        }
+    }
+}
+
+/// The amount claimed within the current throttling period.
+/// The quota of failed claim transfers is not restored.
+#[near(serializers=[borsh])]
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct ClaimQuota {
+    pub period_number: u64,
+    pub claimed_amount: Balance,
+}
+
+impl ClaimQuota {
+    pub fn remaining(&self, throttle: &ClaimThrottle, period_number: u64) -> Balance {
+        if self.period_number == period_number {
+            throttle.max_amount.0.saturating_sub(self.claimed_amount)
+        } else {
+            throttle.max_amount.0
+        }
+    }
+
+    pub fn consume(&mut self, throttle: &ClaimThrottle, period_number: u64, amount: Balance) {
+        let remaining = self.remaining(throttle, period_number);
+        assert!(
+            amount <= remaining,
+            "Claim of {amount} exceeds the remaining claim quota of {remaining}, try later"
+        );
+        if self.period_number != period_number {
+            self.period_number = period_number;
+            self.claimed_amount = 0;
+        }
+        self.claimed_amount += amount;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_quota() {
+        let throttle = ClaimThrottle {
+            period: ClaimThrottlePeriod::Hour,
+            max_amount: 100.into(),
+        };
+        assert_eq!(throttle.period_number(10, 3 * HOUR_SEC + 5), 3);
+
+        let mut quota = ClaimQuota::default();
+        quota.consume(&throttle, 3, 60);
+        assert_eq!(quota.remaining(&throttle, 3), 40);
+        quota.consume(&throttle, 3, 40);
+        assert_eq!(quota.remaining(&throttle, 3), 0);
+        assert_eq!(quota.remaining(&throttle, 4), 100);
+        quota.consume(&throttle, 4, 10);
+        assert_eq!(quota.remaining(&throttle, 4), 90);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the remaining claim quota of 40, try later")]
+    fn test_claim_quota_exceeded() {
+        let throttle = ClaimThrottle {
+            period: ClaimThrottlePeriod::Block,
+            max_amount: 100.into(),
+        };
+        let mut quota = ClaimQuota::default();
+        quota.consume(&throttle, 7, 60);
+        quota.consume(&throttle, 7, 41);
+    }
+}
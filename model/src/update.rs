@@ -1,9 +1,23 @@
-use near_sdk::{AccountId, PromiseOrValue};
+use near_sdk::{json_types::Base58CryptoHash, AccountId, PromiseOrValue};
 use nitka::make_integration_version;
 
 #[make_integration_version]
 pub trait UpdateApi {
+    /// Deploys the code passed as the raw input and migrates the state, can be called only by the multisig.
+    /// The sha256 hash of the code must be approved by the update guardians, so no update is possible
+    /// until the manager appoints the first guardians.
     #[update]
     fn update_contract(&mut self) -> PromiseOrValue<()>;
     fn set_multisig(&mut self, multisig: AccountId);
+
+    /// Adds accounts approving the code of updates, e.g. a DAO. The manager can add the first guardians,
+    /// afterwards only guardians can change the set.
+    fn add_update_guardians(&mut self, account_ids: Vec<AccountId>);
+
+    fn remove_update_guardians(&mut self, account_ids: Vec<AccountId>);
+
+    /// Approves the sha256 hash of the code for a single `update_contract` call.
+    fn approve_code_hash(&mut self, code_hash: Base58CryptoHash);
+
+    fn revoke_code_hash(&mut self, code_hash: Base58CryptoHash);
 }
@@ -1,6 +1,6 @@
 use near_sdk::{env, Timestamp};
 
-use crate::TimestampSec;
+use crate::{Balance, TimestampSec};
 
 pub fn nano_to_sec(timestamp: Timestamp) -> TimestampSec {
     (timestamp / 10u64.pow(9)).try_into().unwrap()
@@ -10,6 +10,46 @@ pub fn current_timestamp_sec() -> TimestampSec {
     nano_to_sec(env::block_timestamp())
 }
 
+/// Formats a raw token amount as a decimal string using the token decimals, e.g. `1500000` with 6 decimals is `1.5`.
+pub fn format_balance(balance: Balance, decimals: u8) -> String {
+    let divisor = 10u128
+        .checked_pow(u32::from(decimals))
+        .expect("Token decimals are too big");
+    let integer = balance / divisor;
+    let fraction = balance % divisor;
+    if fraction == 0 {
+        return integer.to_string();
+    }
+    let fraction = format!("{:0width$}", fraction, width = usize::from(decimals));
+    format!("{integer}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Formats a unix timestamp in seconds as an ISO-8601 UTC date-time, e.g. `0` is `1970-01-01T00:00:00Z`.
+pub fn format_timestamp(timestamp: TimestampSec) -> String {
+    let days = i64::from(timestamp / 86_400);
+    let seconds = timestamp % 86_400;
+    // the civil date from the days since the epoch, https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
 pub mod u128_dec_format {
     use near_sdk::serde::{de, Deserialize, Deserializer, Serializer};
 
@@ -27,3 +67,26 @@ pub mod u128_dec_format {
         String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_balance() {
+        assert_eq!(format_balance(0, 6), "0");
+        assert_eq!(format_balance(1_500_000, 6), "1.5");
+        assert_eq!(format_balance(1_000_000, 6), "1");
+        assert_eq!(format_balance(1, 6), "0.000001");
+        assert_eq!(format_balance(123, 0), "123");
+        assert_eq!(format_balance(10u128.pow(24) + 10u128.pow(18), 24), "1.000001");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1_704_067_199), "2023-12-31T23:59:59Z");
+        assert_eq!(format_timestamp(u32::MAX), "2106-02-07T06:28:15Z");
+    }
+}
@@ -1,21 +1,141 @@
-use near_sdk::{json_types::Base58CryptoHash, AccountId};
+use std::collections::HashMap;
+
+use near_sdk::{
+    json_types::{Base58CryptoHash, Base64VecU8},
+    AccountId, PublicKey,
+};
 use nitka::make_integration_version;
 
 use crate::{
-    draft::{DraftGroupIndex, DraftGroupView, DraftIndex, DraftView},
-    lockup::{LockupIndex, LockupView},
-    schedule::Schedule,
-    WrappedBalance,
+    airdrop::{Airdrop, AirdropIndex},
+    config::Config,
+    draft::{
+        DraftGroupConversionReport, DraftGroupIndex, DraftGroupStatusView, DraftGroupView, DraftIndex, DraftView,
+        PendingOperatorActions,
+    },
+    extension::ScheduleExtension,
+    fee::FeeConfig,
+    ft_message::{DraftGroupFunding, FtMessage},
+    gas::GasConfig,
+    grant::{GrantIndex, GrantView},
+    lockup::{
+        AccountLockupsPage, LockupCreate, LockupId, LockupIndex, LockupSortBy, LockupStatus, LockupView, UnlockRate,
+    },
+    merkle::{LockupLeaf, LockupsMerkleRoot},
+    note::LockupNote,
+    payout::PayoutAddress,
+    privacy::ViewAuth,
+    provenance::{Deposit, DepositIndex, FundingSource},
+    remap::AccountRemap,
+    revocation::UnclaimedRevocation,
+    schedule::{Schedule, ScheduleView},
+    signed::SignedAction,
+    stats::{EmissionBucket, Health, NextIndices, SolvencyReport, TagStats, TokenFlows, UpcomingUnlock},
+    termination::TerminationRecord,
+    TimestampSec, WrappedBalance,
 };
 
 #[make_integration_version]
 pub trait LockupViewApi {
     fn get_token_account_id(&self) -> AccountId;
 
-    fn get_account_lockups(&self, account_id: AccountId) -> Vec<(LockupIndex, LockupView)>;
+    fn get_token_decimals(&self) -> Option<u8>;
+
+    /// Views computing balances accept an optional `at_timestamp` to query past or future positions,
+    /// the current timestamp by default. The claimed balance is always the current one.
+    fn get_account_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    /// The account lockups ordered by `sort_by`, the creation order by default,
+    /// `from_index` and `limit` refer to the position in this order rather than the lockup index.
+    fn get_account_lockups_paged(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        sort_by: Option<LockupSortBy>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> AccountLockupsPage;
+
+    /// Lockups of the account emptied by a termination or an unclaimed revocation, ordered by index.
+    /// They aren't returned by `get_account_lockups` anymore, but still can be viewed with `get_lockup`.
+    fn get_account_terminated_lockups(
+        &self,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    fn get_accounts_lockups(
+        &self,
+        account_ids: Vec<AccountId>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> HashMap<AccountId, Vec<(LockupIndex, LockupView)>>;
+
+    fn get_lockup(
+        &self,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<LockupView>;
+
+    /// Returns the lockups of the account while `Config::privacy_mode` hides `get_account_lockups`.
+    /// Authorized for the account itself, the whitelisted privacy viewers and the manager, see `ViewAuth`.
+    fn get_account_lockups_signed(
+        &self,
+        auth: ViewAuth,
+        account_id: AccountId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    /// Returns the lockup while `Config::privacy_mode` hides `get_lockup`, authorized as `get_account_lockups_signed`.
+    fn get_lockup_signed(
+        &self,
+        auth: ViewAuth,
+        index: LockupIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<LockupView>;
+
+    fn get_lockups(
+        &self,
+        indices: Vec<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    /// Returns the id of the lockup, equal to its index.
+    fn get_lockup_id(&self, index: LockupIndex) -> Option<LockupId>;
+
+    /// Returns the latest notes of the operators on the lockup, the last one is the current note.
+    fn get_lockup_note_history(&self, index: LockupIndex) -> Vec<LockupNote>;
+
+    /// Resolves the id into the index accepted by the lockup methods, `None` if the lockup doesn't exist.
+    fn get_lockup_index(&self, lockup_id: LockupId) -> Option<LockupIndex>;
 
-    fn get_lockup(&self, index: LockupIndex) -> Option<LockupView>;
-    fn get_lockups(&self, indices: Vec<LockupIndex>) -> Vec<(LockupIndex, LockupView)>;
+    /// Returns whether a transfer of the lockup is pending, `None` if the lockup doesn't exist.
+    fn get_lockup_status(&self, index: LockupIndex) -> Option<LockupStatus>;
+
+    fn get_lockup_by_id(
+        &self,
+        lockup_id: LockupId,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<(LockupIndex, LockupView)>;
+
+    /// Returns the raw checkpoints of the lockup, with ISO-8601 timestamps and decimal balances if `humanize`.
+    /// The balances stay in raw units while the token decimals are unknown.
+    fn get_schedule(&self, lockup_index: LockupIndex, humanize: bool) -> Option<ScheduleView>;
+
+    /// Returns the tokens per day unlocking at `at_timestamp` and the timestamp of the next rate change.
+    fn get_unlock_rate(&self, lockup_index: LockupIndex, at_timestamp: Option<TimestampSec>) -> Option<UnlockRate>;
 
     fn get_num_lockups(&self) -> u32;
 
@@ -23,12 +143,115 @@ pub trait LockupViewApi {
         &self,
         from_index: Option<LockupIndex>,
         limit: Option<LockupIndex>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Vec<(LockupIndex, LockupView)>;
+
+    /// Unfinalized lockups with the given tag, finalized lockups are removed from the tag index.
+    /// `from_index` and `limit` refer to the position in the tag index rather than the lockup index,
+    /// each page is ordered by lockup index.
+    fn get_lockups_by_tag(
+        &self,
+        tag: String,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
     ) -> Vec<(LockupIndex, LockupView)>;
 
+    /// Aggregated balances of the unfinalized lockups with the given tag.
+    fn get_tag_stats(&self, tag: String, at_timestamp: Option<TimestampSec>) -> TagStats;
+
+    /// Returns the owners with the largest balance unlocking within the next `window_sec` seconds, summed over
+    /// their lockups and sorted by the amount, e.g. for an emission calendar. `limit` defaults to
+    /// `DEFAULT_UPCOMING_UNLOCKS_LIMIT`. The owners are hidden while
+    /// `Config::anonymize_upcoming_unlocks` is set. Only the page of `lockups_limit` lockups from `from_index`
+    /// is summed, all lockups by default. Once they don't fit into the view gas limit, the caller merges the pages,
+    /// passing a `limit` large enough to keep every owner of a page.
+    fn get_top_upcoming_unlocks(
+        &self,
+        window_sec: TimestampSec,
+        limit: Option<u32>,
+        from_index: Option<LockupIndex>,
+        lockups_limit: Option<LockupIndex>,
+    ) -> Vec<UpcomingUnlock>;
+
+    /// Returns the balance unlocking across the lockups from `from_index` in buckets of `bucket_sec` seconds
+    /// from `from_ts` to `to_ts`, e.g. for a supply unlock chart. At most `MAX_EMISSION_BUCKETS` buckets
+    /// are returned. `limit` defaults to the remaining lockups, once they don't fit into the view gas limit
+    /// the caller sums the buckets of the pages.
+    fn get_emission_schedule(
+        &self,
+        bucket_sec: TimestampSec,
+        from_ts: TimestampSec,
+        to_ts: TimestampSec,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> Vec<EmissionBucket>;
+
+    fn get_unclaimed_revocation(&self, lockup_index: LockupIndex) -> Option<UnclaimedRevocation>;
+
+    /// Checks that the sha256 hash of `preimage` matches the agreement hash bound to the lockup.
+    fn verify_agreement(&self, lockup_index: LockupIndex, preimage: Base64VecU8) -> bool;
+
+    fn get_num_grants(&self) -> u32;
+
+    fn get_grant(
+        &self,
+        grant_id: GrantIndex,
+        humanized: Option<bool>,
+        at_timestamp: Option<TimestampSec>,
+    ) -> Option<GrantView>;
+
     fn get_deposit_whitelist(&self) -> Vec<AccountId>;
 
     fn get_draft_operators_whitelist(&self) -> Vec<AccountId>;
 
+    fn get_payer_whitelist(&self) -> Vec<AccountId>;
+
+    fn get_lien_holders_whitelist(&self) -> Vec<AccountId>;
+
+    fn get_privacy_viewers_whitelist(&self) -> Vec<AccountId>;
+
+    fn get_vesting_oracles_whitelist(&self) -> Vec<AccountId>;
+
+    fn get_viewing_key(&self, account_id: AccountId) -> Option<PublicKey>;
+
+    fn get_manager_signing_key(&self) -> Option<PublicKey>;
+
+    /// Returns the nonce of the latest signed action of the manager, the next action should have a greater one.
+    fn get_signed_action_nonce(&self) -> u64;
+
+    /// Returns the message to sign offline with the manager signing key to authorize the action.
+    fn get_signed_action_message(&self, action: SignedAction) -> Base64VecU8;
+
+    fn get_update_guardians(&self) -> Vec<AccountId>;
+
+    /// Returns the code hashes approved by the update guardians and not deployed yet.
+    fn get_approved_code_hashes(&self) -> Vec<Base58CryptoHash>;
+
+    fn is_attested(&self, account_id: AccountId) -> bool;
+
+    fn is_blocked(&self, account_id: AccountId) -> bool;
+
+    fn get_payout_address(&self, account_id: AccountId) -> Option<PayoutAddress>;
+
+    fn get_account_remap(&self, old_account_id: AccountId) -> Option<AccountRemap>;
+
+    fn get_schedule_extension(&self) -> Option<ScheduleExtension>;
+
+    fn get_num_airdrops(&self) -> u32;
+
+    fn get_airdrop(&self, airdrop_id: AirdropIndex) -> Option<Airdrop>;
+
+    fn is_airdrop_lockup_registered(&self, airdrop_id: AirdropIndex, leaf_index: u32) -> bool;
+
+    /// Returns the last merkle root of all lockups computed by `compute_lockups_merkle_root`.
+    fn get_lockups_merkle_root(&self) -> Option<LockupsMerkleRoot>;
+
+    /// Returns the current record of the lockup hashed into the merkle root of all lockups.
+    fn get_lockup_leaf(&self, index: LockupIndex) -> Option<LockupLeaf>;
+
     fn hash_schedule(&self, schedule: Schedule) -> Base58CryptoHash;
 
     fn validate_schedule(
@@ -38,14 +261,50 @@ pub trait LockupViewApi {
         termination_schedule: Option<Schedule>,
     );
 
+    fn build_schedule_with_cliff(
+        &self,
+        total_balance: WrappedBalance,
+        initial_balance: WrappedBalance,
+        start_timestamp: TimestampSec,
+        cliff_timestamp: TimestampSec,
+        finish_timestamp: TimestampSec,
+    ) -> Schedule;
+
     fn get_next_draft_group_id(&self) -> DraftGroupIndex;
 
     fn get_next_draft_id(&self) -> DraftGroupIndex;
 
+    /// The ids the next lockup, draft and draft group will get, the ends of the ranges to paginate over.
+    fn get_next_indices(&self) -> NextIndices;
+
+    /// Same as `get_num_lockups`.
+    fn get_lockups_count(&self) -> u32;
+
+    /// The number of existing drafts, converted and deleted drafts excluded.
+    fn get_drafts_count(&self) -> u32;
+
+    /// Same as `get_num_draft_groups`.
+    fn get_draft_groups_count(&self) -> u32;
+
+    /// Drafts whose owner account creation has failed.
+    fn get_drafts_pending_account(&self) -> Vec<DraftIndex>;
+
     fn get_num_draft_groups(&self) -> u32;
 
     fn get_draft_group(&self, index: DraftGroupIndex) -> Option<DraftGroupView>;
 
+    /// Returns the funded, converted and remaining amounts of the draft group,
+    /// also after the group is removed once all its drafts are converted or deleted.
+    fn get_draft_group_status(&self, draft_group_id: DraftGroupIndex) -> Option<DraftGroupStatusView>;
+
+    /// Returns the `ft_transfer_call` message creating the lockup, e.g. for multisig proposals.
+    /// Fails if the lockup is invalid.
+    fn encode_create_lockup_msg(&self, spec: LockupCreate) -> String;
+
+    /// Returns the `ft_transfer_call` message funding the draft group, e.g. for multisig proposals.
+    /// Fails if the draft group doesn't exist.
+    fn encode_fund_draft_group_msg(&self, draft_group_id: DraftGroupIndex, try_convert: Option<bool>) -> String;
+
     fn get_draft_groups_paged(
         &self,
         // not the draft_id, but internal index used inside the LookupMap struct
@@ -57,5 +316,72 @@ pub trait LockupViewApi {
 
     fn get_drafts(&self, indices: Vec<DraftIndex>) -> Vec<(DraftIndex, DraftView)>;
 
+    /// Returns the draft groups waiting for the funding, the conversion or the deletion of drafts.
+    fn get_pending_operator_actions(&self) -> PendingOperatorActions;
+
+    /// Checks the drafts of the group without converting them, so the issues can be fixed before the conversion.
+    fn validate_draft_group_conversion(&self, draft_group_id: DraftGroupIndex) -> DraftGroupConversionReport;
+
+    fn get_num_terminations(&self) -> u32;
+
+    fn get_terminations(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<TerminationRecord>;
+
+    fn get_num_deposits(&self) -> u32;
+
+    fn get_deposit(&self, deposit_id: DepositIndex) -> Option<Deposit>;
+
+    /// Returns the deposits funding the lockup, including the funding of the draft group it's converted from.
+    /// Returns the unallocated balance of the lockup pool of the account, see `create_lockups`.
+    fn get_lockup_pool_balance(&self, account_id: AccountId) -> WrappedBalance;
+
+    fn get_lockup_funding(&self, index: LockupIndex) -> Vec<FundingSource>;
+
+    fn get_draft_group_funding(&self, draft_group_id: DraftGroupIndex) -> Option<FundingSource>;
+
+    fn get_token_flows(&self) -> TokenFlows;
+
+    /// Compares the unclaimed balance of all lockups, the unconverted balance of funded draft groups and airdrops
+    /// and the unallocated balance of lockup pools with `contract_balance`, the token balance of the contract
+    /// queried by the caller, to detect accounting drift.
+    /// The balance of claims awaiting their transfer callback is counted as claimed.
+    /// Only the page of `limit` lockups from `from_index` is summed into `lockups_balance`, all lockups by default.
+    /// Once they don't fit into the view gas limit, the caller sums `lockups_balance` of the pages
+    /// and compares it with the balances of a single report.
+    fn check_solvency(
+        &self,
+        contract_balance: WrappedBalance,
+        from_index: Option<LockupIndex>,
+        limit: Option<LockupIndex>,
+    ) -> SolvencyReport;
+
+    /// Returns the sequence number to be assigned to the next emitted event.
+    fn get_next_event_seq(&self) -> u64;
+
+    /// Whether the emitted events are stored for `get_events_since`, see `set_event_log_enabled`.
+    fn get_event_log_enabled(&self) -> bool;
+
+    /// Returns the emitted events starting from the given sequence number as `(seq, event_json)` pairs.
+    /// Only the latest events are kept in state, so the evicted ones are skipped.
+    fn get_events_since(&self, seq: u64, limit: Option<u32>) -> Vec<(u64, String)>;
+
+    fn get_config(&self) -> Config;
+
+    /// Returns the gas of the token calls and of the callbacks, the gas of a claim of `n` lockups is
+    /// `claim_reserved_gas + n * claim_gas_per_lockup` as computed by `GasConfig`.
+    fn get_gas_config(&self) -> GasConfig;
+
+    /// Returns the current claim fee and its pending change, if any.
+    fn get_fee_config(&self) -> FeeConfig;
+
+    /// Returns the amount that can still be claimed within the current throttling period,
+    /// `None` if claims are not throttled.
+    fn get_remaining_claim_quota(&self) -> Option<WrappedBalance>;
+
     fn get_version(&self) -> String;
+
+    /// Returns the version of the event schema, which changes only with the shape of the events.
+    fn event_version(&self) -> String;
+
+    /// Returns the token, the version and entity counts in a stable shape for monitoring probes.
+    fn health(&self) -> Health;
 }
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use near_sdk::{json_types::Base64VecU8, near};
+use nitka::make_integration_version;
+
+/// A page request of a web4 gateway, the fields not used by the contract are ignored.
+#[near(serializers=[json])]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Web4Request {
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub query: HashMap<String, Vec<String>>,
+}
+
+impl Web4Request {
+    /// The first value of the query parameter.
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.get(name)?.first().map(String::as_str)
+    }
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(export))]
+pub struct Web4Response {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub content_type: String,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub body: Base64VecU8,
+}
+
+impl Web4Response {
+    pub fn html(body: String) -> Self {
+        Self {
+            status: None,
+            content_type: "text/html; charset=UTF-8".to_string(),
+            body: body.into_bytes().into(),
+        }
+    }
+
+    pub fn json(body: String) -> Self {
+        Self {
+            status: None,
+            content_type: "application/json; charset=UTF-8".to_string(),
+            body: body.into_bytes().into(),
+        }
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
+
+/// Escapes the text to be embedded into an HTML page.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[make_integration_version]
+pub trait Web4Api {
+    /// Renders the contract pages for web4 gateways:
+    /// - `/` a summary of the locked balance with a form looking up the lockups of an account
+    /// - `/account?account_id=` the lockups of the account with their schedules
+    /// - `/summary.json` the summary as JSON, `total_locked` is null once there are too many lockups to sum
+    fn web4_get(&self, request: Web4Request) -> Web4Response;
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::serde_json;
+
+    use super::*;
+
+    #[test]
+    fn test_web4_request_from_gateway() {
+        let request: Web4Request = serde_json::from_str(
+            r#"{"accountId":"lockup.near","path":"/account","params":{},"query":{"account_id":["alice.near"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.path, "/account");
+        assert_eq!(request.query_param("account_id"), Some("alice.near"));
+        assert_eq!(request.query_param("from_index"), None);
+    }
+
+    #[test]
+    fn test_web4_response_json() {
+        let response = Web4Response::html("<p>ok</p>".to_string()).with_status(404);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"status":404,"contentType":"text/html; charset=UTF-8","body":"PHA+b2s8L3A+"}"#
+        );
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
@@ -7,3 +7,9 @@ rustup target add wasm32-unknown-unknown
 cargo build -p hodl-lockup --target wasm32-unknown-unknown --profile=contract --features integration-test
 
 cp ./target/wasm32-unknown-unknown/contract/hodl_lockup.wasm res/hodl_lockup.wasm
+
+echo ">> Building malicious token"
+
+cargo build -p malicious-token --target wasm32-unknown-unknown --profile=contract
+
+cp ./target/wasm32-unknown-unknown/contract/malicious_token.wasm res/malicious_token.wasm
@@ -0,0 +1,9 @@
+#!/bin/bash
+set -eox pipefail
+
+echo ">> Building contract with embedded ABI"
+
+# Requires cargo-near 0.6: https://github.com/near/cargo-near
+# The ABI is embedded into the wasm and exposed via the `__contract_abi` view,
+# the JSON ABI is also written to res/hodl_lockup_abi.json
+cargo near build --manifest-path contract/Cargo.toml --release --embed-abi --out-dir res